@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use spl_token_staking::state::{
+    ClaimDelegate, ExternalRewardPool, MaxVoterWeightRecord, RewardCheckpoint, RewardPoolExtension,
+    StakeDepositReceipt, StakePool, VoterWeightRecord, MAX_REWARD_POOLS, REWARD_POOL_EXTENSIONS_LEN,
+};
+
+/// Deserialize a [StakePool] from the data of its account, including the discriminator.
+pub fn deserialize_stake_pool(data: &[u8]) -> Result<StakePool> {
+    if data.len() < 8 {
+        return err!(ErrorCode::AccountDiscriminatorNotFound);
+    }
+    if data[..8] != StakePool::DISCRIMINATOR {
+        return err!(ErrorCode::AccountDiscriminatorMismatch);
+    }
+    let data = data
+        .get(8..8 + StakePool::LEN)
+        .ok_or(ErrorCode::AccountDidNotDeserialize)?;
+    Ok(bytemuck::pod_read_unaligned(data))
+}
+
+/// Deserialize the [RewardPoolExtension]s following a [StakePool] in the data of its account.
+/// They are zeroed when the StakePool has not been extended yet, as the program treats them.
+pub fn deserialize_reward_pool_extensions(
+    data: &[u8],
+) -> Result<[RewardPoolExtension; MAX_REWARD_POOLS]> {
+    deserialize_stake_pool(data)?;
+    let start = 8 + StakePool::LEN;
+    Ok(match data.get(start..start + REWARD_POOL_EXTENSIONS_LEN) {
+        Some(extensions) => bytemuck::pod_read_unaligned(extensions),
+        None => bytemuck::Zeroable::zeroed(),
+    })
+}
+
+/// Amounts of each RewardPool the StakeDepositReceipt could claim at `current_timestamp`, given
+/// the fetched data of its StakePool and the balance of each `reward_vault`, indexed like
+/// `reward_pools`. See [StakePool::get_claimable_rewards].
+pub fn get_claimable_rewards(
+    stake_pool_data: &[u8],
+    stake_deposit_receipt: &StakeDepositReceipt,
+    reward_vault_amounts: &[u64; MAX_REWARD_POOLS],
+    current_timestamp: i64,
+) -> Result<[u64; MAX_REWARD_POOLS]> {
+    deserialize_stake_pool(stake_pool_data)?.get_claimable_rewards(
+        &deserialize_reward_pool_extensions(stake_pool_data)?,
+        stake_deposit_receipt,
+        reward_vault_amounts,
+        current_timestamp,
+    )
+}
+
+pub fn deserialize_stake_deposit_receipt(data: &[u8]) -> Result<StakeDepositReceipt> {
+    StakeDepositReceipt::try_deserialize(&mut &data[..])
+}
+
+pub fn deserialize_claim_delegate(data: &[u8]) -> Result<ClaimDelegate> {
+    ClaimDelegate::try_deserialize(&mut &data[..])
+}
+
+pub fn deserialize_external_reward_pool(data: &[u8]) -> Result<ExternalRewardPool> {
+    ExternalRewardPool::try_deserialize(&mut &data[..])
+}
+
+pub fn deserialize_reward_checkpoint(data: &[u8]) -> Result<RewardCheckpoint> {
+    RewardCheckpoint::try_deserialize(&mut &data[..])
+}
+
+pub fn deserialize_voter_weight_record(data: &[u8]) -> Result<VoterWeightRecord> {
+    VoterWeightRecord::try_deserialize(&mut &data[..])
+}
+
+pub fn deserialize_max_voter_weight_record(data: &[u8]) -> Result<MaxVoterWeightRecord> {
+    MaxVoterWeightRecord::try_deserialize(&mut &data[..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spl_token_staking::state::{u128, RewardPool, SCALE_FACTOR_BASE};
+
+    #[test]
+    fn get_claimable_rewards_of_unextended_stake_pool() {
+        let scale = core::primitive::u128::from(SCALE_FACTOR_BASE);
+        let mut stake_pool: StakePool = bytemuck::Zeroable::zeroed();
+        stake_pool.total_weighted_stake = u128((2_000 * scale).to_le_bytes());
+        stake_pool.reward_pools[1] = RewardPool::new(&Pubkey::new_unique(), u128::default());
+        let mut data = StakePool::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&stake_pool));
+        let receipt = StakeDepositReceipt {
+            owner: Pubkey::default(),
+            payer: Pubkey::default(),
+            stake_pool: Pubkey::default(),
+            lockup_duration: 0,
+            deposit_timestamp: 0,
+            deposit_amount: 1_000,
+            effective_stake: u128((1_000 * scale).to_le_bytes()),
+            claimed_amounts: [u128::default(); MAX_REWARD_POOLS],
+        };
+
+        let mut reward_vault_amounts = [0u64; MAX_REWARD_POOLS];
+        reward_vault_amounts[1] = 3_000;
+        let mut expected = [0u64; MAX_REWARD_POOLS];
+        expected[1] = 1_500;
+        assert_eq!(
+            get_claimable_rewards(&data, &receipt, &reward_vault_amounts, 0).unwrap(),
+            expected
+        );
+        assert!(get_claimable_rewards(&data[..8], &receipt, &reward_vault_amounts, 0).is_err());
+    }
+}
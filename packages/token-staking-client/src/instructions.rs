@@ -0,0 +1,1528 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::{token, token_2022};
+use spl_token_staking::state::{
+    ExternalRewardPool, RewardPoolExtension, StakePool, StakePoolRole, WeightCurve,
+    MAX_REWARD_POOLS,
+};
+use spl_token_staking::{accounts, instruction, ID};
+
+use crate::pda::{
+    find_claim_delegate_address, find_external_reward_pool_address,
+    find_external_reward_vault_address, find_max_voter_weight_record_address,
+    find_metadata_address, find_reward_checkpoint_address, find_reward_vault_address,
+    find_stake_deposit_receipt_address, find_stake_mint_address, find_stake_pool_address,
+    find_vault_address, find_voter_weight_record_address,
+};
+
+/// Accounts of a RewardPool that can not be read from the StakePool, in the order of the occupied
+/// RewardPools.
+#[derive(Clone, Copy, Debug)]
+pub struct RewardPoolAccounts {
+    /// Mint of the RewardPool's `reward_vault`
+    pub reward_mint: Pubkey,
+    /// Token account of the owner receiving the rewards
+    pub owner_reward_account: Pubkey,
+    /// Token program of `reward_mint`, either SPL Token or Token-2022
+    pub token_program: Pubkey,
+}
+
+impl RewardPoolAccounts {
+    /// Use the owner's associated token account of `reward_mint` for the RewardPool with the
+    /// given extension, see [deserialize_reward_pool_extensions](crate::accounts::deserialize_reward_pool_extensions).
+    pub fn associated(
+        reward_pool_extension: &RewardPoolExtension,
+        owner: &Pubkey,
+        reward_mint: &Pubkey,
+    ) -> Self {
+        // RewardPools added before the token program was recorded are SPL Token
+        let token_program = if reward_pool_extension.token_program == Pubkey::default() {
+            token::ID
+        } else {
+            reward_pool_extension.token_program
+        };
+        Self {
+            reward_mint: *reward_mint,
+            token_program,
+            owner_reward_account: get_associated_token_address_with_program_id(
+                owner,
+                reward_mint,
+                &token_program,
+            ),
+        }
+    }
+}
+
+/// Indexes of the occupied RewardPools, in the order their remaining accounts are passed.
+pub fn occupied_reward_pool_indexes(stake_pool: &StakePool) -> Vec<usize> {
+    (0..MAX_REWARD_POOLS)
+        .filter(|index| !stake_pool.reward_pools[*index].is_empty())
+        .collect()
+}
+
+/// Remaining accounts of instructions recalculating rewards, such as `deposit`: the
+/// `reward_vault` of each occupied RewardPool.
+pub fn reward_vault_remaining_accounts(stake_pool: &StakePool) -> Vec<AccountMeta> {
+    occupied_reward_pool_indexes(stake_pool)
+        .into_iter()
+        .map(|index| AccountMeta::new_readonly(stake_pool.reward_pools[index].reward_vault, false))
+        .collect()
+}
+
+/// Remaining accounts of instructions claiming rewards, such as `claim_all` and `withdraw`: the
+/// `reward_vault` and owner's reward account of each RewardPool selected by `reward_pool_mask`
+/// in pairs, followed by the reward mint of each selected Token-2022 RewardPool.
+///
+/// `reward_pools` must hold an entry for every occupied RewardPool, in order.
+pub fn claim_remaining_accounts(
+    stake_pool: &StakePool,
+    reward_pools: &[RewardPoolAccounts],
+    reward_pool_mask: u16,
+) -> Vec<AccountMeta> {
+    let indexes = occupied_reward_pool_indexes(stake_pool);
+    assert_eq!(
+        indexes.len(),
+        reward_pools.len(),
+        "RewardPoolAccounts required for every occupied RewardPool"
+    );
+    let selected: Vec<(usize, &RewardPoolAccounts)> = indexes
+        .into_iter()
+        .zip(reward_pools)
+        .filter(|(index, _)| reward_pool_mask & (1 << index) != 0)
+        .collect();
+
+    let mut remaining_accounts = Vec::with_capacity(selected.len() * 3);
+    for (index, accounts) in selected.iter() {
+        remaining_accounts.push(AccountMeta::new(
+            stake_pool.reward_pools[*index].reward_vault,
+            false,
+        ));
+        remaining_accounts.push(AccountMeta::new(accounts.owner_reward_account, false));
+    }
+    for (_, accounts) in selected.iter() {
+        if accounts.token_program == token_2022::ID {
+            remaining_accounts.push(AccountMeta::new_readonly(accounts.reward_mint, false));
+        }
+    }
+    remaining_accounts
+}
+
+/// Remaining accounts appended by instructions syncing the RewardCheckpoints of a
+/// StakeDepositReceipt, such as `withdraw`: each ExternalRewardPool the receipt joined and its
+/// RewardCheckpoint in pairs.
+///
+/// `external_reward_pools` must hold every ExternalRewardPool the receipt has not left.
+pub fn reward_checkpoint_remaining_accounts(
+    stake_deposit_receipt: &Pubkey,
+    external_reward_pools: &[Pubkey],
+) -> Vec<AccountMeta> {
+    external_reward_pools
+        .iter()
+        .flat_map(|reward_pool| {
+            [
+                AccountMeta::new(*reward_pool, false),
+                AccountMeta::new(
+                    find_reward_checkpoint_address(reward_pool, stake_deposit_receipt).0,
+                    false,
+                ),
+            ]
+        })
+        .collect()
+}
+
+fn build(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining: Vec<AccountMeta>,
+) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining);
+    Instruction {
+        program_id: ID,
+        accounts: metas,
+        data: data.data(),
+    }
+}
+
+/// `mint_token_program` is the token program of `mint`, either SPL Token or Token-2022.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_stake_pool(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    mint_token_program: &Pubkey,
+    nonce: u8,
+    max_weight: u64,
+    min_duration: u64,
+    max_duration: u64,
+    weight_curve: WeightCurve,
+    non_transferable_stake_mint: bool,
+) -> Instruction {
+    let (stake_pool, _) = find_stake_pool_address(nonce, mint, authority);
+    build(
+        accounts::InitializeStakePool {
+            payer: *payer,
+            authority: *authority,
+            mint: *mint,
+            stake_pool,
+            stake_mint: find_stake_mint_address(&stake_pool).0,
+            vault: find_vault_address(&stake_pool).0,
+            token_program: token::ID,
+            token_2022_program: Some(token_2022::ID),
+            mint_token_program: *mint_token_program,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeStakePool {
+            nonce,
+            max_weight,
+            min_duration,
+            max_duration,
+            weight_curve,
+            non_transferable_stake_mint,
+        },
+        Vec::new(),
+    )
+}
+
+/// DANGEROUSLY transfer the StakePool to `new_authority` without its signature, prefer
+/// [propose_authority].
+pub fn transfer_authority(
+    stake_pool: &Pubkey,
+    authority: &Pubkey,
+    new_authority: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::TransferAuthority {
+            authority: *authority,
+            new_authority: *new_authority,
+            stake_pool: *stake_pool,
+        },
+        instruction::TransferAuthority {},
+        Vec::new(),
+    )
+}
+
+pub fn propose_authority(
+    stake_pool: &Pubkey,
+    authority: &Pubkey,
+    new_authority: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ProposeAuthority {
+            authority: *authority,
+            new_authority: *new_authority,
+            stake_pool: *stake_pool,
+        },
+        instruction::ProposeAuthority {},
+        Vec::new(),
+    )
+}
+
+pub fn accept_authority(stake_pool: &Pubkey, pending_authority: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptAuthority {
+            pending_authority: *pending_authority,
+            stake_pool: *stake_pool,
+        },
+        instruction::AcceptAuthority {},
+        Vec::new(),
+    )
+}
+
+pub fn cancel_authority_transfer(stake_pool: &Pubkey, authority: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelAuthorityTransfer {
+            authority: *authority,
+            stake_pool: *stake_pool,
+        },
+        instruction::CancelAuthorityTransfer {},
+        Vec::new(),
+    )
+}
+
+/// DANGEROUSLY mint `amount` of the StakePool's `stake_mint` to `destination`.
+pub fn dangerously_mint_stake_mint(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    payer: &Pubkey,
+    authority: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::DangerouslyMintStakeMint {
+            payer: *payer,
+            authority: *authority,
+            stake_mint: stake_pool.stake_mint,
+            destination: *destination,
+            stake_pool: *stake_pool_key,
+            token_program: token::ID,
+            token_2022_program: Some(token_2022::ID),
+        },
+        instruction::DangerouslyMintStakeMint { amount },
+        Vec::new(),
+    )
+}
+
+/// `token_program` is the token program of `reward_mint`, either SPL Token or Token-2022.
+pub fn add_reward_pool(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    stake_pool: &Pubkey,
+    reward_mint: &Pubkey,
+    token_program: &Pubkey,
+    index: u8,
+) -> Instruction {
+    build(
+        accounts::AddRewardPool {
+            payer: *payer,
+            authority: *authority,
+            reward_mint: *reward_mint,
+            stake_pool: *stake_pool,
+            reward_vault: find_reward_vault_address(stake_pool, reward_mint).0,
+            token_program: *token_program,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+        },
+        instruction::AddRewardPool { index },
+        Vec::new(),
+    )
+}
+
+pub fn retire_reward_pool(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    authority: &Pubkey,
+    index: u8,
+) -> Instruction {
+    build(
+        accounts::RetireRewardPool {
+            authority: *authority,
+            stake_pool: *stake_pool_key,
+        },
+        instruction::RetireRewardPool { index },
+        reward_vault_remaining_accounts(stake_pool),
+    )
+}
+
+/// Transfer the remaining rewards of the retired RewardPool at `index` to `destination`.
+/// `token_program` is the token program of `reward_mint`.
+pub fn sweep_reward_pool(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    authority: &Pubkey,
+    reward_mint: &Pubkey,
+    destination: &Pubkey,
+    token_program: &Pubkey,
+    index: u8,
+) -> Instruction {
+    build(
+        accounts::SweepRewardPool {
+            authority: *authority,
+            stake_pool: *stake_pool_key,
+            reward_vault: stake_pool.reward_pools[usize::from(index)].reward_vault,
+            reward_mint: *reward_mint,
+            destination: *destination,
+            token_program: *token_program,
+            system_program: system_program::ID,
+        },
+        instruction::SweepRewardPool { index },
+        Vec::new(),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn set_reward_pool_emission(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    index: u8,
+    rewards_per_second: u64,
+    start_time: i64,
+    end_time: i64,
+) -> Instruction {
+    build(
+        accounts::SetRewardPoolEmission {
+            authority: *authority,
+            payer: *payer,
+            stake_pool: *stake_pool_key,
+            system_program: system_program::ID,
+        },
+        instruction::SetRewardPoolEmission {
+            index,
+            rewards_per_second,
+            start_time,
+            end_time,
+        },
+        reward_vault_remaining_accounts(stake_pool),
+    )
+}
+
+/// Fund the RewardPool at `index` with `amount` of `reward_mint` from `from`, streaming the
+/// undistributed rewards over `duration` seconds, or accruing them immediately when 0.
+/// `token_program` is the token program of `reward_mint`.
+#[allow(clippy::too_many_arguments)]
+pub fn fund_reward_pool(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    funder: &Pubkey,
+    from: &Pubkey,
+    reward_mint: &Pubkey,
+    token_program: &Pubkey,
+    index: u8,
+    amount: u64,
+    duration: u64,
+) -> Instruction {
+    build(
+        accounts::FundRewardPool {
+            funder: *funder,
+            stake_pool: *stake_pool_key,
+            reward_vault: stake_pool.reward_pools[usize::from(index)].reward_vault,
+            reward_mint: *reward_mint,
+            from: *from,
+            token_program: *token_program,
+            system_program: system_program::ID,
+        },
+        instruction::FundRewardPool {
+            index,
+            amount,
+            duration,
+        },
+        reward_vault_remaining_accounts(stake_pool),
+    )
+}
+
+/// `treasury` is a token account of the StakePool's `mint` receiving the penalties.
+pub fn set_early_withdraw_penalty(
+    stake_pool: &Pubkey,
+    authority: &Pubkey,
+    treasury: &Pubkey,
+    penalty_bps: u16,
+) -> Instruction {
+    build(
+        accounts::SetEarlyWithdrawPenalty {
+            authority: *authority,
+            stake_pool: *stake_pool,
+            treasury: *treasury,
+        },
+        instruction::SetEarlyWithdrawPenalty { penalty_bps },
+        Vec::new(),
+    )
+}
+
+pub fn update_pool_config(
+    stake_pool: &Pubkey,
+    authority: &Pubkey,
+    base_weight: u64,
+    max_weight: u64,
+    min_duration: u64,
+    max_duration: u64,
+) -> Instruction {
+    build(
+        accounts::UpdatePoolConfig {
+            authority: *authority,
+            stake_pool: *stake_pool,
+        },
+        instruction::UpdatePoolConfig {
+            base_weight,
+            max_weight,
+            min_duration,
+            max_duration,
+        },
+        Vec::new(),
+    )
+}
+
+/// Assign `key` to `role`, or unassign the role with Pubkey default.
+pub fn set_role(
+    stake_pool: &Pubkey,
+    authority: &Pubkey,
+    role: StakePoolRole,
+    key: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::SetRole {
+            authority: *authority,
+            stake_pool: *stake_pool,
+        },
+        instruction::SetRole { role, key: *key },
+        Vec::new(),
+    )
+}
+
+fn set_flags_accounts(stake_pool: &Pubkey, authority: &Pubkey) -> accounts::SetFlags {
+    accounts::SetFlags {
+        authority: *authority,
+        stake_pool: *stake_pool,
+    }
+}
+
+pub fn set_flags(stake_pool: &Pubkey, authority: &Pubkey, flags: u8) -> Instruction {
+    build(
+        set_flags_accounts(stake_pool, authority),
+        instruction::SetFlags { flags },
+        Vec::new(),
+    )
+}
+
+pub fn enable_flags(stake_pool: &Pubkey, authority: &Pubkey, mask: u8) -> Instruction {
+    build(
+        set_flags_accounts(stake_pool, authority),
+        instruction::EnableFlags { mask },
+        Vec::new(),
+    )
+}
+
+pub fn disable_flags(stake_pool: &Pubkey, authority: &Pubkey, mask: u8) -> Instruction {
+    build(
+        set_flags_accounts(stake_pool, authority),
+        instruction::DisableFlags { mask },
+        Vec::new(),
+    )
+}
+
+/// Deposit `amount` of the StakePool's `mint` from `from` into a new StakeDepositReceipt of
+/// `owner`, minting the `stake_mint` to `destination`.
+#[allow(clippy::too_many_arguments)]
+pub fn deposit(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    payer: &Pubkey,
+    owner: &Pubkey,
+    from: &Pubkey,
+    destination: &Pubkey,
+    mint_token_program: &Pubkey,
+    nonce: u32,
+    amount: u64,
+    lockup_duration: u64,
+) -> Instruction {
+    build(
+        accounts::Deposit {
+            payer: *payer,
+            owner: *owner,
+            from: *from,
+            vault: stake_pool.vault,
+            mint: stake_pool.mint,
+            stake_mint: stake_pool.stake_mint,
+            destination: *destination,
+            stake_pool: *stake_pool_key,
+            stake_deposit_receipt: find_stake_deposit_receipt_address(owner, stake_pool_key, nonce)
+                .0,
+            token_program: token::ID,
+            token_2022_program: Some(token_2022::ID),
+            mint_token_program: *mint_token_program,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+        },
+        instruction::Deposit {
+            nonce,
+            amount,
+            lockup_duration,
+        },
+        reward_vault_remaining_accounts(stake_pool),
+    )
+}
+
+fn claim_base(
+    stake_pool_key: &Pubkey,
+    owner: &Pubkey,
+    stake_deposit_receipt: &Pubkey,
+) -> accounts::ClaimBase {
+    accounts::ClaimBase {
+        owner: *owner,
+        stake_pool: *stake_pool_key,
+        stake_deposit_receipt: *stake_deposit_receipt,
+        token_program: token::ID,
+        token_2022_program: Some(token_2022::ID),
+    }
+}
+
+/// Claim the rewards of all RewardPools. `reward_pools` must hold an entry for every occupied
+/// RewardPool, in order.
+pub fn claim_all(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    owner: &Pubkey,
+    stake_deposit_receipt: &Pubkey,
+    reward_pools: &[RewardPoolAccounts],
+) -> Instruction {
+    build(
+        accounts::ClaimAll {
+            claim_base: claim_base(stake_pool_key, owner, stake_deposit_receipt),
+        },
+        instruction::ClaimAll {},
+        claim_remaining_accounts(stake_pool, reward_pools, u16::MAX),
+    )
+}
+
+/// Claim the rewards of the RewardPools selected by `reward_pool_mask`. `reward_pools` must hold
+/// an entry for every occupied RewardPool, in order.
+pub fn claim_selected(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    owner: &Pubkey,
+    stake_deposit_receipt: &Pubkey,
+    reward_pools: &[RewardPoolAccounts],
+    reward_pool_mask: u16,
+) -> Instruction {
+    build(
+        accounts::ClaimSelected {
+            claim_base: claim_base(stake_pool_key, owner, stake_deposit_receipt),
+        },
+        instruction::ClaimSelected { reward_pool_mask },
+        claim_remaining_accounts(stake_pool, reward_pools, reward_pool_mask),
+    )
+}
+
+/// Allow `delegate` to claim the rewards of all StakeDepositReceipts of `owner` in the StakePool
+/// with [crank_claim].
+pub fn set_claim_delegate(stake_pool: &Pubkey, owner: &Pubkey, delegate: &Pubkey) -> Instruction {
+    build(
+        accounts::SetClaimDelegate {
+            owner: *owner,
+            stake_pool: *stake_pool,
+            claim_delegate: find_claim_delegate_address(owner, stake_pool).0,
+            system_program: system_program::ID,
+        },
+        instruction::SetClaimDelegate {
+            delegate: *delegate,
+        },
+        Vec::new(),
+    )
+}
+
+pub fn revoke_claim_delegate(stake_pool: &Pubkey, owner: &Pubkey) -> Instruction {
+    build(
+        accounts::RevokeClaimDelegate {
+            owner: *owner,
+            claim_delegate: find_claim_delegate_address(owner, stake_pool).0,
+        },
+        instruction::RevokeClaimDelegate {},
+        Vec::new(),
+    )
+}
+
+/// Claim the rewards of the RewardPools selected by `reward_pool_mask` on behalf of `owner`,
+/// signed by their claim delegate `cranker`. `reward_pools` must hold an entry for every occupied
+/// RewardPool, in order, with the owner's associated token accounts, see
+/// [RewardPoolAccounts::associated].
+pub fn crank_claim(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    cranker: &Pubkey,
+    owner: &Pubkey,
+    stake_deposit_receipt: &Pubkey,
+    reward_pools: &[RewardPoolAccounts],
+    reward_pool_mask: u16,
+) -> Instruction {
+    build(
+        accounts::CrankClaim {
+            cranker: *cranker,
+            owner: *owner,
+            stake_pool: *stake_pool_key,
+            stake_deposit_receipt: *stake_deposit_receipt,
+            claim_delegate: find_claim_delegate_address(owner, stake_pool_key).0,
+            token_program: token::ID,
+            token_2022_program: Some(token_2022::ID),
+        },
+        instruction::CrankClaim { reward_pool_mask },
+        claim_remaining_accounts(stake_pool, reward_pools, reward_pool_mask),
+    )
+}
+
+/// Withdraw all tokens of a StakeDepositReceipt to `destination`, burning the `stake_mint` from
+/// `from` and claiming the rewards of all RewardPools. `reward_pools` must hold an entry for
+/// every occupied RewardPool, in order, and `external_reward_pools` every ExternalRewardPool the
+/// receipt has joined.
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    owner: &Pubkey,
+    stake_deposit_receipt: &Pubkey,
+    from: &Pubkey,
+    destination: &Pubkey,
+    mint_token_program: &Pubkey,
+    reward_pools: &[RewardPoolAccounts],
+    external_reward_pools: &[Pubkey],
+) -> Instruction {
+    let mut remaining_accounts = claim_remaining_accounts(stake_pool, reward_pools, u16::MAX);
+    remaining_accounts.extend(reward_checkpoint_remaining_accounts(
+        stake_deposit_receipt,
+        external_reward_pools,
+    ));
+    build(
+        accounts::Withdraw {
+            claim_base: claim_base(stake_pool_key, owner, stake_deposit_receipt),
+            vault: stake_pool.vault,
+            mint: stake_pool.mint,
+            stake_mint: stake_pool.stake_mint,
+            from: *from,
+            destination: *destination,
+            treasury: (stake_pool.treasury != Pubkey::default()).then_some(stake_pool.treasury),
+            mint_token_program: *mint_token_program,
+        },
+        instruction::Withdraw {},
+        remaining_accounts,
+    )
+}
+
+/// Withdraw `amount` of an unlocked StakeDepositReceipt's tokens, see [withdraw].
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw_partial(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    owner: &Pubkey,
+    stake_deposit_receipt: &Pubkey,
+    from: &Pubkey,
+    destination: &Pubkey,
+    mint_token_program: &Pubkey,
+    reward_pools: &[RewardPoolAccounts],
+    external_reward_pools: &[Pubkey],
+    amount: u64,
+) -> Instruction {
+    let mut remaining_accounts = claim_remaining_accounts(stake_pool, reward_pools, u16::MAX);
+    remaining_accounts.extend(reward_checkpoint_remaining_accounts(
+        stake_deposit_receipt,
+        external_reward_pools,
+    ));
+    build(
+        accounts::WithdrawPartial {
+            claim_base: claim_base(stake_pool_key, owner, stake_deposit_receipt),
+            vault: stake_pool.vault,
+            mint: stake_pool.mint,
+            stake_mint: stake_pool.stake_mint,
+            from: *from,
+            destination: *destination,
+            mint_token_program: *mint_token_program,
+        },
+        instruction::WithdrawPartial { amount },
+        remaining_accounts,
+    )
+}
+
+/// Extend the lockup of a StakeDepositReceipt to `new_lockup_duration`, minting the additional
+/// `stake_mint` to `destination`. `reward_pools` must hold an entry for every occupied
+/// RewardPool, in order.
+pub fn extend_lockup(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    owner: &Pubkey,
+    stake_deposit_receipt: &Pubkey,
+    destination: &Pubkey,
+    reward_pools: &[RewardPoolAccounts],
+    new_lockup_duration: u64,
+) -> Instruction {
+    build(
+        accounts::ExtendLockup {
+            claim_base: claim_base(stake_pool_key, owner, stake_deposit_receipt),
+            stake_mint: stake_pool.stake_mint,
+            destination: *destination,
+        },
+        instruction::ExtendLockup {
+            new_lockup_duration,
+        },
+        claim_remaining_accounts(stake_pool, reward_pools, u16::MAX),
+    )
+}
+
+/// Restake the claimable rewards of the RewardPool at `index`, which must distribute the
+/// StakePool's `mint`, minting the additional `stake_mint` to `destination`. `reward_pools` must
+/// hold an entry for every occupied RewardPool, in order.
+#[allow(clippy::too_many_arguments)]
+pub fn compound(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    owner: &Pubkey,
+    stake_deposit_receipt: &Pubkey,
+    destination: &Pubkey,
+    mint_token_program: &Pubkey,
+    reward_pools: &[RewardPoolAccounts],
+    index: u8,
+) -> Instruction {
+    build(
+        accounts::Compound {
+            claim_base: claim_base(stake_pool_key, owner, stake_deposit_receipt),
+            vault: stake_pool.vault,
+            mint: stake_pool.mint,
+            reward_vault: stake_pool.reward_pools[usize::from(index)].reward_vault,
+            stake_mint: stake_pool.stake_mint,
+            destination: *destination,
+            mint_token_program: *mint_token_program,
+        },
+        instruction::Compound { index },
+        claim_remaining_accounts(stake_pool, reward_pools, u16::MAX),
+    )
+}
+
+/// Merge `source_stake_deposit_receipt` into `stake_deposit_receipt`, minting any rounding
+/// difference of the `stake_mint` to `destination`. `reward_pools` must hold an entry for every
+/// occupied RewardPool, in order.
+pub fn merge_receipts(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    owner: &Pubkey,
+    stake_deposit_receipt: &Pubkey,
+    source_stake_deposit_receipt: &Pubkey,
+    destination: &Pubkey,
+    reward_pools: &[RewardPoolAccounts],
+) -> Instruction {
+    build(
+        accounts::MergeReceipts {
+            claim_base: claim_base(stake_pool_key, owner, stake_deposit_receipt),
+            source_stake_deposit_receipt: *source_stake_deposit_receipt,
+            stake_mint: stake_pool.stake_mint,
+            destination: *destination,
+        },
+        instruction::MergeReceipts {},
+        claim_remaining_accounts(stake_pool, reward_pools, u16::MAX),
+    )
+}
+
+/// Split `amount` of a StakeDepositReceipt's deposit into a new StakeDepositReceipt of the same
+/// owner derived from `new_nonce`, burning any rounding difference of the `stake_mint` from
+/// `from`. `reward_pools` must hold an entry for every occupied RewardPool, in order, and
+/// `external_reward_pools` every ExternalRewardPool the receipt has joined.
+#[allow(clippy::too_many_arguments)]
+pub fn split_receipt(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    payer: &Pubkey,
+    owner: &Pubkey,
+    stake_deposit_receipt: &Pubkey,
+    from: &Pubkey,
+    reward_pools: &[RewardPoolAccounts],
+    external_reward_pools: &[Pubkey],
+    amount: u64,
+    new_nonce: u32,
+) -> Instruction {
+    let mut remaining_accounts = claim_remaining_accounts(stake_pool, reward_pools, u16::MAX);
+    remaining_accounts.extend(reward_checkpoint_remaining_accounts(
+        stake_deposit_receipt,
+        external_reward_pools,
+    ));
+    build(
+        accounts::SplitReceipt {
+            claim_base: claim_base(stake_pool_key, owner, stake_deposit_receipt),
+            payer: *payer,
+            new_stake_deposit_receipt: find_stake_deposit_receipt_address(
+                owner,
+                stake_pool_key,
+                new_nonce,
+            )
+            .0,
+            stake_mint: stake_pool.stake_mint,
+            from: *from,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+        },
+        instruction::SplitReceipt { amount, new_nonce },
+        remaining_accounts,
+    )
+}
+
+/// Transfer a StakeDepositReceipt to a new StakeDepositReceipt of `new_owner` derived from
+/// `new_nonce`, moving the `stake_mint` from `from` to `destination`. `reward_pools` must hold an
+/// entry for every occupied RewardPool, in order, receiving the rewards of the current owner.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_receipt(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    payer: &Pubkey,
+    owner: &Pubkey,
+    stake_deposit_receipt: &Pubkey,
+    new_owner: &Pubkey,
+    from: &Pubkey,
+    destination: &Pubkey,
+    reward_pools: &[RewardPoolAccounts],
+    new_nonce: u32,
+) -> Instruction {
+    build(
+        accounts::TransferReceipt {
+            claim_base: claim_base(stake_pool_key, owner, stake_deposit_receipt),
+            payer: *payer,
+            new_owner: *new_owner,
+            new_stake_deposit_receipt: find_stake_deposit_receipt_address(
+                new_owner,
+                stake_pool_key,
+                new_nonce,
+            )
+            .0,
+            stake_mint: stake_pool.stake_mint,
+            from: *from,
+            destination: *destination,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+        },
+        instruction::TransferReceipt { new_nonce },
+        claim_remaining_accounts(stake_pool, reward_pools, u16::MAX),
+    )
+}
+
+pub fn create_voter_weight_record(
+    stake_pool: &Pubkey,
+    payer: &Pubkey,
+    governing_token_owner: &Pubkey,
+    realm: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::CreateVoterWeightRecord {
+            payer: *payer,
+            governing_token_owner: *governing_token_owner,
+            stake_pool: *stake_pool,
+            realm: *realm,
+            voter_weight_record: find_voter_weight_record_address(
+                stake_pool,
+                realm,
+                governing_token_owner,
+            )
+            .0,
+            system_program: system_program::ID,
+        },
+        instruction::CreateVoterWeightRecord {},
+        Vec::new(),
+    )
+}
+
+pub fn create_max_voter_weight_record(
+    stake_pool: &Pubkey,
+    payer: &Pubkey,
+    realm: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::CreateMaxVoterWeightRecord {
+            payer: *payer,
+            stake_pool: *stake_pool,
+            realm: *realm,
+            max_voter_weight_record: find_max_voter_weight_record_address(stake_pool, realm).0,
+            system_program: system_program::ID,
+        },
+        instruction::CreateMaxVoterWeightRecord {},
+        Vec::new(),
+    )
+}
+
+/// Set the weight of the VoterWeightRecord of `governing_token_owner` to the effective stake of
+/// `stake_deposit_receipts`, locking them for `VOTE_LOCK_DURATION`.
+pub fn update_voter_weight_record(
+    stake_pool: &Pubkey,
+    governing_token_owner: &Pubkey,
+    realm: &Pubkey,
+    stake_deposit_receipts: &[Pubkey],
+) -> Instruction {
+    build(
+        accounts::UpdateVoterWeightRecord {
+            governing_token_owner: *governing_token_owner,
+            stake_pool: *stake_pool,
+            voter_weight_record: find_voter_weight_record_address(
+                stake_pool,
+                realm,
+                governing_token_owner,
+            )
+            .0,
+            system_program: system_program::ID,
+        },
+        instruction::UpdateVoterWeightRecord {},
+        stake_deposit_receipts
+            .iter()
+            .map(|stake_deposit_receipt| AccountMeta::new(*stake_deposit_receipt, false))
+            .collect(),
+    )
+}
+
+pub fn update_max_voter_weight_record(stake_pool: &Pubkey, realm: &Pubkey) -> Instruction {
+    build(
+        accounts::UpdateMaxVoterWeightRecord {
+            stake_pool: *stake_pool,
+            max_voter_weight_record: find_max_voter_weight_record_address(stake_pool, realm).0,
+        },
+        instruction::UpdateMaxVoterWeightRecord {},
+        Vec::new(),
+    )
+}
+
+/// `token_program` is the token program of `reward_mint`, either SPL Token or Token-2022.
+pub fn add_external_reward_pool(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    stake_pool: &Pubkey,
+    reward_mint: &Pubkey,
+    token_program: &Pubkey,
+    id: u32,
+) -> Instruction {
+    let (reward_pool, _) = find_external_reward_pool_address(stake_pool, id);
+    build(
+        accounts::AddExternalRewardPool {
+            payer: *payer,
+            authority: *authority,
+            reward_mint: *reward_mint,
+            stake_pool: *stake_pool,
+            reward_pool,
+            reward_vault: find_external_reward_vault_address(&reward_pool).0,
+            token_program: *token_program,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+        },
+        instruction::AddExternalRewardPool { id },
+        Vec::new(),
+    )
+}
+
+/// Transfer `amount` of rewards from `from` into an ExternalRewardPool, streamed over `duration`
+/// seconds.
+pub fn fund_external_reward_pool(
+    reward_pool_key: &Pubkey,
+    reward_pool: &ExternalRewardPool,
+    funder: &Pubkey,
+    from: &Pubkey,
+    amount: u64,
+    duration: u64,
+) -> Instruction {
+    build(
+        accounts::FundExternalRewardPool {
+            funder: *funder,
+            reward_pool: *reward_pool_key,
+            reward_vault: reward_pool.reward_vault,
+            reward_mint: reward_pool.reward_mint,
+            from: *from,
+            token_program: reward_pool.token_program,
+        },
+        instruction::FundExternalRewardPool { amount, duration },
+        Vec::new(),
+    )
+}
+
+pub fn join_external_reward_pool(
+    reward_pool_key: &Pubkey,
+    payer: &Pubkey,
+    owner: &Pubkey,
+    stake_deposit_receipt: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::JoinExternalRewardPool {
+            payer: *payer,
+            owner: *owner,
+            reward_pool: *reward_pool_key,
+            stake_deposit_receipt: *stake_deposit_receipt,
+            reward_checkpoint: find_reward_checkpoint_address(
+                reward_pool_key,
+                stake_deposit_receipt,
+            )
+            .0,
+            system_program: system_program::ID,
+        },
+        instruction::JoinExternalRewardPool {},
+        Vec::new(),
+    )
+}
+
+pub fn sync_reward_checkpoint(
+    reward_pool_key: &Pubkey,
+    stake_deposit_receipt: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::SyncRewardCheckpoint {
+            reward_pool: *reward_pool_key,
+            reward_checkpoint: find_reward_checkpoint_address(
+                reward_pool_key,
+                stake_deposit_receipt,
+            )
+            .0,
+            stake_deposit_receipt: *stake_deposit_receipt,
+        },
+        instruction::SyncRewardCheckpoint {},
+        Vec::new(),
+    )
+}
+
+pub fn claim_external_reward_pool(
+    reward_pool_key: &Pubkey,
+    reward_pool: &ExternalRewardPool,
+    owner: &Pubkey,
+    stake_deposit_receipt: &Pubkey,
+    owner_reward_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ClaimExternalRewardPool {
+            owner: *owner,
+            stake_pool: reward_pool.stake_pool,
+            reward_pool: *reward_pool_key,
+            reward_checkpoint: find_reward_checkpoint_address(
+                reward_pool_key,
+                stake_deposit_receipt,
+            )
+            .0,
+            stake_deposit_receipt: *stake_deposit_receipt,
+            reward_vault: reward_pool.reward_vault,
+            reward_mint: reward_pool.reward_mint,
+            owner_reward_account: *owner_reward_account,
+            token_program: reward_pool.token_program,
+        },
+        instruction::ClaimExternalRewardPool {},
+        Vec::new(),
+    )
+}
+
+pub fn leave_external_reward_pool(
+    reward_pool_key: &Pubkey,
+    owner: &Pubkey,
+    stake_deposit_receipt: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::LeaveExternalRewardPool {
+            owner: *owner,
+            reward_pool: *reward_pool_key,
+            reward_checkpoint: find_reward_checkpoint_address(
+                reward_pool_key,
+                stake_deposit_receipt,
+            )
+            .0,
+            stake_deposit_receipt: *stake_deposit_receipt,
+            system_program: system_program::ID,
+        },
+        instruction::LeaveExternalRewardPool {},
+        Vec::new(),
+    )
+}
+
+pub fn retire_external_reward_pool(
+    reward_pool_key: &Pubkey,
+    reward_pool: &ExternalRewardPool,
+    authority: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::RetireExternalRewardPool {
+            authority: *authority,
+            stake_pool: reward_pool.stake_pool,
+            reward_pool: *reward_pool_key,
+        },
+        instruction::RetireExternalRewardPool {},
+        Vec::new(),
+    )
+}
+
+/// Transfer the remaining rewards of a sweepable ExternalRewardPool to `destination`.
+pub fn sweep_external_reward_pool(
+    reward_pool_key: &Pubkey,
+    reward_pool: &ExternalRewardPool,
+    authority: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::SweepExternalRewardPool {
+            authority: *authority,
+            stake_pool: reward_pool.stake_pool,
+            reward_pool: *reward_pool_key,
+            reward_vault: reward_pool.reward_vault,
+            reward_mint: reward_pool.reward_mint,
+            destination: *destination,
+            token_program: reward_pool.token_program,
+        },
+        instruction::SweepExternalRewardPool {},
+        Vec::new(),
+    )
+}
+
+/// Create or update the metadata of the StakePool's `stake_mint`.
+pub fn update_token_meta(
+    stake_pool_key: &Pubkey,
+    stake_pool: &StakePool,
+    authority: &Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Instruction {
+    build(
+        accounts::UpdateTokenMeta {
+            authority: *authority,
+            metadata_account: find_metadata_address(&stake_pool.stake_mint).0,
+            stake_pool: *stake_pool_key,
+            stake_mint: stake_pool.stake_mint,
+            metadata_program: mpl_token_metadata::ID,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+        },
+        instruction::UpdateTokenMeta { name, symbol, uri },
+        Vec::new(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stake_pool_with_reward_pools() -> StakePool {
+        let mut stake_pool: StakePool = bytemuck::Zeroable::zeroed();
+        stake_pool.reward_pools[0].reward_vault = Pubkey::new_unique();
+        stake_pool.reward_pools[2].reward_vault = Pubkey::new_unique();
+        stake_pool
+    }
+
+    /// Pubkey, signer and writable flag of each account of `ix`, to compare against the
+    /// `#[derive(Accounts)]` struct of the instruction.
+    fn metas(ix: &Instruction) -> Vec<(Pubkey, bool, bool)> {
+        ix.accounts
+            .iter()
+            .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+            .collect()
+    }
+
+    #[test]
+    fn claim_remaining_accounts_are_ordered() {
+        let stake_pool = stake_pool_with_reward_pools();
+        let reward_pools = [
+            RewardPoolAccounts {
+                reward_mint: Pubkey::new_unique(),
+                owner_reward_account: Pubkey::new_unique(),
+                token_program: token::ID,
+            },
+            RewardPoolAccounts {
+                reward_mint: Pubkey::new_unique(),
+                owner_reward_account: Pubkey::new_unique(),
+                token_program: token_2022::ID,
+            },
+        ];
+
+        let keys: Vec<Pubkey> = claim_remaining_accounts(&stake_pool, &reward_pools, u16::MAX)
+            .iter()
+            .map(|meta| meta.pubkey)
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                stake_pool.reward_pools[0].reward_vault,
+                reward_pools[0].owner_reward_account,
+                stake_pool.reward_pools[2].reward_vault,
+                reward_pools[1].owner_reward_account,
+                // only the Token-2022 RewardPool requires its mint
+                reward_pools[1].reward_mint,
+            ]
+        );
+
+        // unselected RewardPools are left out
+        let keys: Vec<Pubkey> = claim_remaining_accounts(&stake_pool, &reward_pools, 1)
+            .iter()
+            .map(|meta| meta.pubkey)
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                stake_pool.reward_pools[0].reward_vault,
+                reward_pools[0].owner_reward_account
+            ]
+        );
+    }
+
+    #[test]
+    fn deposit_passes_reward_vaults() {
+        let stake_pool = stake_pool_with_reward_pools();
+        let ix = deposit(
+            &Pubkey::new_unique(),
+            &stake_pool,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &token::ID,
+            0,
+            1_000,
+            0,
+        );
+        let remaining: Vec<Pubkey> = ix.accounts[ix.accounts.len() - 2..]
+            .iter()
+            .map(|meta| meta.pubkey)
+            .collect();
+        assert_eq!(
+            remaining,
+            vec![
+                stake_pool.reward_pools[0].reward_vault,
+                stake_pool.reward_pools[2].reward_vault
+            ]
+        );
+    }
+
+    #[test]
+    fn associated_defaults_to_spl_token() {
+        let owner = Pubkey::new_unique();
+        let reward_mint = Pubkey::new_unique();
+        let extension: RewardPoolExtension = bytemuck::Zeroable::zeroed();
+        let accounts = RewardPoolAccounts::associated(&extension, &owner, &reward_mint);
+        assert_eq!(accounts.token_program, token::ID);
+        assert_eq!(
+            accounts.owner_reward_account,
+            get_associated_token_address_with_program_id(&owner, &reward_mint, &token::ID)
+        );
+    }
+
+    #[test]
+    fn authority_instructions_match_accounts() {
+        let stake_pool = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let new_authority = Pubkey::new_unique();
+
+        assert_eq!(
+            metas(&transfer_authority(&stake_pool, &authority, &new_authority)),
+            vec![
+                (authority, true, true),
+                (new_authority, false, false),
+                (stake_pool, false, true),
+            ]
+        );
+        assert_eq!(
+            metas(&propose_authority(&stake_pool, &authority, &new_authority)),
+            vec![
+                (authority, true, false),
+                (new_authority, false, false),
+                (stake_pool, false, true),
+            ]
+        );
+        assert_eq!(
+            metas(&accept_authority(&stake_pool, &new_authority)),
+            vec![(new_authority, true, false), (stake_pool, false, true)]
+        );
+        assert_eq!(
+            metas(&cancel_authority_transfer(&stake_pool, &authority)),
+            vec![(authority, true, false), (stake_pool, false, true)]
+        );
+
+        let treasury = Pubkey::new_unique();
+        assert_eq!(
+            metas(&set_early_withdraw_penalty(
+                &stake_pool,
+                &authority,
+                &treasury,
+                100
+            )),
+            vec![
+                (authority, true, false),
+                (stake_pool, false, true),
+                (treasury, false, false),
+            ]
+        );
+        for ix in [
+            update_pool_config(&stake_pool, &authority, 1, 2, 0, 100),
+            set_role(
+                &stake_pool,
+                &authority,
+                StakePoolRole::Pauser,
+                &Pubkey::new_unique(),
+            ),
+        ] {
+            assert_eq!(
+                metas(&ix),
+                vec![(authority, true, false), (stake_pool, false, true)]
+            );
+        }
+        for ix in [
+            set_flags(&stake_pool, &authority, 1),
+            enable_flags(&stake_pool, &authority, 1),
+            disable_flags(&stake_pool, &authority, 1),
+        ] {
+            assert_eq!(
+                metas(&ix),
+                vec![(authority, true, true), (stake_pool, false, true)]
+            );
+        }
+    }
+
+    #[test]
+    fn reward_pool_instructions_match_accounts() {
+        let stake_pool_key = Pubkey::new_unique();
+        let mut stake_pool = stake_pool_with_reward_pools();
+        stake_pool.stake_mint = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let reward_vaults = [
+            (stake_pool.reward_pools[0].reward_vault, false, false),
+            (stake_pool.reward_pools[2].reward_vault, false, false),
+        ];
+
+        let mut expected = vec![(authority, true, false), (stake_pool_key, false, true)];
+        expected.extend(reward_vaults.iter().copied());
+        assert_eq!(
+            metas(&retire_reward_pool(
+                &stake_pool_key,
+                &stake_pool,
+                &authority,
+                2
+            )),
+            expected
+        );
+
+        let mut expected = vec![
+            (authority, true, false),
+            (payer, true, true),
+            (stake_pool_key, false, true),
+            (system_program::ID, false, false),
+        ];
+        expected.extend(reward_vaults.iter().copied());
+        assert_eq!(
+            metas(&set_reward_pool_emission(
+                &stake_pool_key,
+                &stake_pool,
+                &authority,
+                &payer,
+                2,
+                10,
+                0,
+                100,
+            )),
+            expected
+        );
+
+        let reward_mint = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        assert_eq!(
+            metas(&sweep_reward_pool(
+                &stake_pool_key,
+                &stake_pool,
+                &authority,
+                &reward_mint,
+                &destination,
+                &token_2022::ID,
+                2,
+            )),
+            vec![
+                (authority, true, true),
+                (stake_pool_key, false, true),
+                (stake_pool.reward_pools[2].reward_vault, false, true),
+                (reward_mint, false, false),
+                (destination, false, true),
+                (token_2022::ID, false, false),
+                (system_program::ID, false, false),
+            ]
+        );
+
+        assert_eq!(
+            metas(&dangerously_mint_stake_mint(
+                &stake_pool_key,
+                &stake_pool,
+                &payer,
+                &authority,
+                &destination,
+                1_000,
+            )),
+            vec![
+                (payer, true, true),
+                (authority, true, false),
+                (stake_pool.stake_mint, false, true),
+                (destination, false, true),
+                (stake_pool_key, false, true),
+                (token::ID, false, false),
+                (token_2022::ID, false, false),
+            ]
+        );
+
+        assert_eq!(
+            metas(&update_token_meta(
+                &stake_pool_key,
+                &stake_pool,
+                &authority,
+                "Staked".to_string(),
+                "STK".to_string(),
+                String::new(),
+            )),
+            vec![
+                (authority, true, false),
+                (find_metadata_address(&stake_pool.stake_mint).0, false, true),
+                (stake_pool_key, false, false),
+                (stake_pool.stake_mint, false, false),
+                (mpl_token_metadata::ID, false, false),
+                (sysvar::rent::ID, false, false),
+                (system_program::ID, false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn claim_delegate_instructions_match_accounts() {
+        let stake_pool = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let claim_delegate = find_claim_delegate_address(&owner, &stake_pool).0;
+
+        assert_eq!(
+            metas(&set_claim_delegate(
+                &stake_pool,
+                &owner,
+                &Pubkey::new_unique()
+            )),
+            vec![
+                (owner, true, true),
+                (stake_pool, false, false),
+                (claim_delegate, false, true),
+                (system_program::ID, false, false),
+            ]
+        );
+        assert_eq!(
+            metas(&revoke_claim_delegate(&stake_pool, &owner)),
+            vec![(owner, true, true), (claim_delegate, false, true)]
+        );
+    }
+
+    #[test]
+    fn voter_weight_instructions_match_accounts() {
+        let stake_pool = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let realm = Pubkey::new_unique();
+        let voter_weight_record = find_voter_weight_record_address(&stake_pool, &realm, &owner).0;
+        let max_voter_weight_record = find_max_voter_weight_record_address(&stake_pool, &realm).0;
+
+        assert_eq!(
+            metas(&create_voter_weight_record(
+                &stake_pool,
+                &payer,
+                &owner,
+                &realm
+            )),
+            vec![
+                (payer, true, true),
+                (owner, false, false),
+                (stake_pool, false, false),
+                (realm, false, false),
+                (voter_weight_record, false, true),
+                (system_program::ID, false, false),
+            ]
+        );
+        assert_eq!(
+            metas(&create_max_voter_weight_record(&stake_pool, &payer, &realm)),
+            vec![
+                (payer, true, true),
+                (stake_pool, false, false),
+                (realm, false, false),
+                (max_voter_weight_record, false, true),
+                (system_program::ID, false, false),
+            ]
+        );
+
+        // the counted StakeDepositReceipts are writable to store their vote lock
+        let receipts = [Pubkey::new_unique(), Pubkey::new_unique()];
+        assert_eq!(
+            metas(&update_voter_weight_record(
+                &stake_pool,
+                &owner,
+                &realm,
+                &receipts
+            )),
+            vec![
+                (owner, true, true),
+                (stake_pool, false, false),
+                (voter_weight_record, false, true),
+                (system_program::ID, false, false),
+                (receipts[0], false, true),
+                (receipts[1], false, true),
+            ]
+        );
+        assert_eq!(
+            metas(&update_max_voter_weight_record(&stake_pool, &realm)),
+            vec![
+                (stake_pool, false, false),
+                (max_voter_weight_record, false, true),
+            ]
+        );
+    }
+}
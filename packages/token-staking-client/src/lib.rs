@@ -0,0 +1,14 @@
+//! Rust client for the spl-token-staking program.
+//!
+//! * [pda] derives the addresses of all program accounts
+//! * [instructions] builds instructions, including the remaining accounts derived from a fetched
+//!   [StakePool](spl_token_staking::state::StakePool)
+//! * [accounts] deserializes program accounts fetched over RPC and calculates the claimable
+//!   rewards of a StakeDepositReceipt with [get_claimable_rewards]
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+
+pub use accounts::get_claimable_rewards;
+pub use spl_token_staking::{self, ID};
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::Pubkey;
+use spl_token_staking::ID;
+
+pub fn find_stake_pool_address(nonce: u8, mint: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            &nonce.to_le_bytes(),
+            mint.as_ref(),
+            authority.as_ref(),
+            b"stakePool",
+        ],
+        &ID,
+    )
+}
+
+pub fn find_stake_mint_address(stake_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[stake_pool.as_ref(), b"stakeMint"], &ID)
+}
+
+pub fn find_vault_address(stake_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[stake_pool.as_ref(), b"vault"], &ID)
+}
+
+/// Vault of a RewardPool embedded in the StakePool
+pub fn find_reward_vault_address(stake_pool: &Pubkey, reward_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[stake_pool.as_ref(), reward_mint.as_ref(), b"rewardVault"],
+        &ID,
+    )
+}
+
+pub fn find_stake_deposit_receipt_address(
+    owner: &Pubkey,
+    stake_pool: &Pubkey,
+    nonce: u32,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            owner.as_ref(),
+            stake_pool.as_ref(),
+            &nonce.to_le_bytes(),
+            b"stakeDepositReceipt",
+        ],
+        &ID,
+    )
+}
+
+pub fn find_claim_delegate_address(owner: &Pubkey, stake_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), stake_pool.as_ref(), b"claimDelegate"],
+        &ID,
+    )
+}
+
+pub fn find_external_reward_pool_address(stake_pool: &Pubkey, id: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[stake_pool.as_ref(), &id.to_le_bytes(), b"rewardPool"],
+        &ID,
+    )
+}
+
+/// Vault of an [ExternalRewardPool](spl_token_staking::state::ExternalRewardPool)
+pub fn find_external_reward_vault_address(reward_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[reward_pool.as_ref(), b"rewardVault"], &ID)
+}
+
+pub fn find_reward_checkpoint_address(
+    reward_pool: &Pubkey,
+    stake_deposit_receipt: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            reward_pool.as_ref(),
+            stake_deposit_receipt.as_ref(),
+            b"rewardCheckpoint",
+        ],
+        &ID,
+    )
+}
+
+pub fn find_voter_weight_record_address(
+    stake_pool: &Pubkey,
+    realm: &Pubkey,
+    governing_token_owner: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            stake_pool.as_ref(),
+            realm.as_ref(),
+            governing_token_owner.as_ref(),
+            b"voterWeightRecord",
+        ],
+        &ID,
+    )
+}
+
+pub fn find_max_voter_weight_record_address(stake_pool: &Pubkey, realm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[stake_pool.as_ref(), realm.as_ref(), b"maxVoterWeightRecord"],
+        &ID,
+    )
+}
+
+/// Metaplex metadata account of the `stake_mint`, see `update_token_meta`.
+pub fn find_metadata_address(stake_mint: &Pubkey) -> (Pubkey, u8) {
+    mpl_token_metadata::pda::find_metadata_account(stake_mint)
+}
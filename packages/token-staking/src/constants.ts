@@ -8,6 +8,10 @@ export const YEAR_IN_SECONDS = 365 * DAY_IN_SECONDS;
 export const STAKE_DEPOSIT_RECEIPT_DISCRIMINATOR = [
   210, 98, 254, 196, 151, 68, 235, 0,
 ];
+export const MAX_REWARD_POOLS = 10;
+/** Size of the StakePool account data, excluding the discriminator */
+export const STAKE_POOL_LEN = 1112;
+export const REWARD_POOL_EXTENSION_LEN = 144;
 export const ESCAPE_HATCH_ENABLED = 1;
 
 export const DEPOSITS_DISABLED = 4;
@@ -11,6 +11,14 @@ export const _SplTokenStakingIDL = {
       docs: [
         "Create a [StakePool](state::StakePool) and initialize the Mint that will",
         "represent effective stake weight.",
+        "",
+        "`weight_curve` determines how the lockup duration maps to a weight, see",
+        "[WeightCurve](state::WeightCurve).",
+        "",
+        "When `non_transferable_stake_mint` is set, the `stake_mint` is created as a Token-2022",
+        "Mint with the NonTransferable extension, so voting weight stays with the staker. Such",
+        "StakePools require the `token_2022_program` in every instruction minting, burning or",
+        "moving the `stake_mint`.",
       ],
       accounts: [
         {
@@ -30,7 +38,7 @@ export const _SplTokenStakingIDL = {
           isMut: false,
           isSigner: false,
           docs: [
-            "SPL Token Mint of the underlying token to be deposited for staking",
+            "SPL Token or Token-2022 Mint of the underlying token to be deposited for staking",
           ],
         },
         {
@@ -42,7 +50,9 @@ export const _SplTokenStakingIDL = {
           name: "stakeMint",
           isMut: true,
           isSigner: false,
-          docs: ["An SPL token Mint for the effective stake weight token"],
+          docs: [
+            "An SPL token Mint for the effective stake weight token, created by the handler",
+          ],
         },
         {
           name: "vault",
@@ -54,6 +64,22 @@ export const _SplTokenStakingIDL = {
           name: "tokenProgram",
           isMut: false,
           isSigner: false,
+          docs: ["Token program of the `stake_mint`"],
+        },
+        {
+          name: "token2022Program",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+          docs: [
+            "Token program of a non-transferable `stake_mint`, only required when creating one",
+          ],
+        },
+        {
+          name: "mintTokenProgram",
+          isMut: false,
+          isSigner: false,
+          docs: ["Token program of the `mint`, either SPL Token or Token-2022"],
         },
         {
           name: "rent",
@@ -83,6 +109,16 @@ export const _SplTokenStakingIDL = {
           name: "maxDuration",
           type: "u64",
         },
+        {
+          name: "weightCurve",
+          type: {
+            defined: "WeightCurve",
+          },
+        },
+        {
+          name: "nonTransferableStakeMint",
+          type: "bool",
+        },
       ],
     },
     {
@@ -90,7 +126,7 @@ export const _SplTokenStakingIDL = {
       docs: [
         "DANGEROUSLY Update `authority` of [StakePool](state::StakePool) to `new_authority`.",
         "This is useful for quickly setting up a StakePool and then transfering it to a",
-        "form of governance.",
+        "form of governance. Prefer `propose_authority` and `accept_authority` otherwise.",
       ],
       accounts: [
         {
@@ -113,6 +149,80 @@ export const _SplTokenStakingIDL = {
       ],
       args: [],
     },
+    {
+      name: "proposeAuthority",
+      docs: [
+        "Propose `new_authority` as the `authority` of [StakePool](state::StakePool). The transfer",
+        "only completes once `new_authority` signs `accept_authority`, so a mistyped key can not",
+        "take over the StakePool. Replaces any previously proposed authority.",
+        "",
+        "Can only be invoked by the StakePool's authority.",
+      ],
+      accounts: [
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["Current authority of the StakePool"],
+        },
+        {
+          name: "newAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "stakePool",
+          isMut: true,
+          isSigner: false,
+          docs: ["StakePool that will have it's authority transferred"],
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "acceptAuthority",
+      docs: [
+        "Complete a transfer started by `propose_authority`, making the signing",
+        "`pending_authority` the `authority` of [StakePool](state::StakePool).",
+      ],
+      accounts: [
+        {
+          name: "pendingAuthority",
+          isMut: false,
+          isSigner: true,
+          docs: ["Proposed authority of the StakePool"],
+        },
+        {
+          name: "stakePool",
+          isMut: true,
+          isSigner: false,
+          docs: ["StakePool that will have it's authority updated"],
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "cancelAuthorityTransfer",
+      docs: [
+        "Clear the `pending_authority` of [StakePool](state::StakePool).",
+        "",
+        "Can only be invoked by the StakePool's authority.",
+      ],
+      accounts: [
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["Current authority of the StakePool"],
+        },
+        {
+          name: "stakePool",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
     {
       name: "dangerouslyMintStakeMint",
       docs: [
@@ -152,6 +262,15 @@ export const _SplTokenStakingIDL = {
           isMut: false,
           isSigner: false,
         },
+        {
+          name: "token2022Program",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+          docs: [
+            "Token program of a non-transferable `stake_mint`, only required when minting one",
+          ],
+        },
       ],
       args: [
         {
@@ -165,7 +284,7 @@ export const _SplTokenStakingIDL = {
       docs: [
         "Add a [RewardPool](state::RewardPool) to an existing [StakePool](state::StakePool).",
         "",
-        "Can only be invoked by the StakePool's authority.",
+        "Can only be invoked by the StakePool's authority or reward manager.",
       ],
       accounts: [
         {
@@ -178,14 +297,14 @@ export const _SplTokenStakingIDL = {
           name: "authority",
           isMut: false,
           isSigner: true,
-          docs: ["Authority of the StakePool"],
+          docs: ["Authority or reward manager of the StakePool"],
         },
         {
           name: "rewardMint",
           isMut: false,
           isSigner: false,
           docs: [
-            "SPL Token Mint of the token that will be distributed as rewards",
+            "SPL Token or Token-2022 Mint of the token that will be distributed as rewards",
           ],
         },
         {
@@ -204,6 +323,9 @@ export const _SplTokenStakingIDL = {
           name: "tokenProgram",
           isMut: false,
           isSigner: false,
+          docs: [
+            "Token program of the `reward_mint`, either SPL Token or Token-2022",
+          ],
         },
         {
           name: "rent",
@@ -224,114 +346,217 @@ export const _SplTokenStakingIDL = {
       ],
     },
     {
-      name: "setFlags",
+      name: "retireRewardPool",
       docs: [
-        "Update various stakepool settings. Simply sum the settings together and pass as a single",
-        "number. Allowed settings:",
-        "* ESCAPE_HATCH_ENABLED -  1",
-        "* PLACEHOLDER_A - 2",
-        "* PLACEHOLDER_B - 4",
+        "Retire the [RewardPool](state::RewardPool) at `index`. The RewardPool stops accruing rewards",
+        "and stakers have `RETIRED_REWARD_POOL_CLAIM_WINDOW` to claim already accrued rewards, after",
+        "which the authority may invoke `sweep_reward_pool`.",
+        "",
+        "Remaining accounts are required: pass the `reward_vault` of each reward pool. These must be",
+        "passed in the same order as `StakePool.reward_pools`",
         "",
         "Can only be invoked by the StakePool's authority.",
       ],
       accounts: [
         {
           name: "authority",
-          isMut: true,
+          isMut: false,
           isSigner: true,
-          docs: ["Current authority of the StakePool"],
+          docs: ["Authority of the StakePool"],
         },
         {
           name: "stakePool",
           isMut: true,
           isSigner: false,
+          docs: ["StakePool the RewardPool belongs to"],
         },
       ],
       args: [
         {
-          name: "flags",
+          name: "index",
           type: "u8",
         },
       ],
     },
     {
-      name: "deposit",
+      name: "sweepRewardPool",
       docs: [
-        "Deposit (aka Stake) a wallet's tokens to the specified [StakePool](state::StakePool).",
-        "Depending on the `lockup_duration` and the StakePool's weighting configuration, the",
-        "wallet initiating the deposit will receive tokens representing their effective stake",
-        "(i.e. deposited amount multiplied by the lockup weight).",
-        "",
-        "For each RewardPool, the latest amount per effective stake will be recalculated to ensure",
-        "the latest accumulated rewards are attributed to all previous depositors and not the deposit",
-        "resulting from this instruction.",
-        "",
-        "A [StakeDepositReceipt](state::StakeDepositReceipt) will be created to track the",
-        "lockup duration, effective weight, and claimable rewards.",
+        "Transfer the remaining balance of a retired [RewardPool](state::RewardPool) to `destination`",
+        "once the claim window has ended, close its vault and free the slot at `index` for reuse.",
         "",
-        "Remaining accounts are required: pass the `reward_vault` of each reward pool. These must be",
-        "passed in the same order as `StakePool.reward_pools`",
+        "Can only be invoked by the StakePool's authority.",
       ],
       accounts: [
         {
-          name: "payer",
+          name: "authority",
           isMut: true,
           isSigner: true,
+          docs: [
+            "Authority of the StakePool, receives the rent of the closed `reward_vault` and pays for",
+            "extending the StakePool",
+          ],
         },
         {
-          name: "owner",
+          name: "stakePool",
+          isMut: true,
+          isSigner: false,
+          docs: ["StakePool the RewardPool belongs to"],
+        },
+        {
+          name: "rewardVault",
+          isMut: true,
+          isSigner: false,
+          docs: ["Vault of the retired RewardPool, closed after sweeping"],
+        },
+        {
+          name: "rewardMint",
+          isMut: false,
+          isSigner: false,
+          docs: ["Mint of the RewardPool's `reward_vault`"],
+        },
+        {
+          name: "destination",
+          isMut: true,
+          isSigner: false,
+          docs: ["Token account the remaining rewards will be transferred to"],
+        },
+        {
+          name: "tokenProgram",
           isMut: false,
           isSigner: false,
           docs: [
-            "Owner of the StakeDepositReceipt, which may differ",
-            "from the account staking.",
+            "Token program of the `reward_mint`, either SPL Token or Token-2022",
           ],
         },
         {
-          name: "from",
-          isMut: true,
+          name: "systemProgram",
+          isMut: false,
           isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "index",
+          type: "u8",
+        },
+      ],
+    },
+    {
+      name: "setRewardPoolEmission",
+      docs: [
+        "Stream the rewards of the [RewardPool](state::RewardPool) at `index` at `rewards_per_second`",
+        "between `start_time` and `end_time` instead of crediting `reward_vault` balance increases",
+        "to stakers immediately. Tokens received by the `reward_vault` are held as undistributed and",
+        "emitted over time, so a deposit made right before the vault is topped up does not receive",
+        "a share of the whole top up. Rewards emitted under the previous schedule are credited first.",
+        "",
+        "Remaining accounts are required: pass the `reward_vault` of each reward pool. These must be",
+        "passed in the same order as `StakePool.reward_pools`",
+        "",
+        "Can only be invoked by the StakePool's authority or reward manager.",
+      ],
+      accounts: [
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["Authority or reward manager of the StakePool"],
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
           docs: [
-            "Token Account to transfer stake_mint from, to be deposited into the vault",
+            "Payer of the rent when the StakePool is extended to store the emission schedule",
           ],
         },
         {
-          name: "vault",
+          name: "stakePool",
           isMut: true,
           isSigner: false,
-          docs: ["Vault of the StakePool token will be transfer to"],
+          docs: ["StakePool the RewardPool belongs to"],
         },
         {
-          name: "stakeMint",
-          isMut: true,
+          name: "systemProgram",
+          isMut: false,
           isSigner: false,
         },
+      ],
+      args: [
+        {
+          name: "index",
+          type: "u8",
+        },
         {
-          name: "destination",
+          name: "rewardsPerSecond",
+          type: "u64",
+        },
+        {
+          name: "startTime",
+          type: "i64",
+        },
+        {
+          name: "endTime",
+          type: "i64",
+        },
+      ],
+    },
+    {
+      name: "fundRewardPool",
+      docs: [
+        "Transfer `amount` of rewards from the funder into the `reward_vault` of the",
+        "[RewardPool](state::RewardPool) at `index`, emitting a `RewardPoolFunded` event. Retired",
+        "RewardPools can not be funded.",
+        "",
+        "When `duration` is greater than 0, all undistributed rewards of the RewardPool, including",
+        "`amount`, are streamed evenly from now over `duration` seconds. Only the StakePool's",
+        "authority or reward manager may set a schedule. With a `duration` of 0 the current schedule is unchanged",
+        "and the rewards are accounted for like any other transfer to the `reward_vault`.",
+        "",
+        "Remaining accounts are required: pass the `reward_vault` of each reward pool. These must be",
+        "passed in the same order as `StakePool.reward_pools`",
+      ],
+      accounts: [
+        {
+          name: "funder",
           isMut: true,
-          isSigner: false,
-          docs: ["Token account the StakePool token will be transfered to"],
+          isSigner: true,
+          docs: [
+            "Wallet transferring the rewards. Must be the StakePool authority or reward manager to set",
+            "a schedule.",
+          ],
         },
         {
           name: "stakePool",
           isMut: true,
           isSigner: false,
-          docs: ["StakePool owning the vault that will receive the deposit"],
+          docs: ["StakePool the RewardPool belongs to"],
         },
         {
-          name: "stakeDepositReceipt",
+          name: "rewardVault",
           isMut: true,
           isSigner: false,
+          docs: ["Vault of the RewardPool at `index`"],
         },
         {
-          name: "tokenProgram",
+          name: "rewardMint",
           isMut: false,
           isSigner: false,
+          docs: ["Mint of the RewardPool's `reward_vault`"],
         },
         {
-          name: "rent",
+          name: "from",
+          isMut: true,
+          isSigner: false,
+          docs: ["Token account the rewards are transferred from"],
+        },
+        {
+          name: "tokenProgram",
           isMut: false,
           isSigner: false,
+          docs: [
+            "Token program of the `reward_mint`, either SPL Token or Token-2022",
+          ],
         },
         {
           name: "systemProgram",
@@ -341,410 +566,3325 @@ export const _SplTokenStakingIDL = {
       ],
       args: [
         {
-          name: "nonce",
-          type: "u32",
+          name: "index",
+          type: "u8",
         },
         {
           name: "amount",
           type: "u64",
         },
         {
-          name: "lockupDuration",
+          name: "duration",
           type: "u64",
         },
       ],
     },
     {
-      name: "claimAll",
+      name: "setEarlyWithdrawPenalty",
       docs: [
-        "Claim unclaimed rewards from all RewardPools for a specific StakeDepositReceipt.",
+        "Allow withdrawing locked StakeDepositReceipts for a penalty of up to `penalty_bps` of the",
+        "deposit, scaled by the fraction of the lockup remaining. Penalties are transferred to",
+        "`treasury`, which may be the `reward_vault` of a RewardPool distributing the staked mint",
+        "to return penalties to the remaining stakers. A `penalty_bps` of 0 disables early withdraws.",
         "",
-        "For each RewardPool, the latest amount per effective stake will be recalculated to ensure",
-        "the latest accumulated rewards are accounted for in the claimable amount. The StakeDepositReceipt",
-        "is also updated so that the latest claimed amount is equivalent, so that their claimable amount",
-        "is 0 after invoking the claim instruction.",
+        "Can only be invoked by the StakePool's authority.",
       ],
       accounts: [
         {
-          name: "claimBase",
-          accounts: [
-            {
-              name: "owner",
-              isMut: true,
-              isSigner: true,
-              docs: ["Owner of the StakeDepositReceipt"],
-            },
-            {
-              name: "stakePool",
-              isMut: true,
-              isSigner: false,
-            },
-            {
-              name: "stakeDepositReceipt",
-              isMut: true,
-              isSigner: false,
-              docs: [
-                "StakeDepositReceipt of the owner that will be used to claim respective rewards",
-              ],
-            },
-            {
-              name: "tokenProgram",
-              isMut: false,
-              isSigner: false,
-            },
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["Authority of the StakePool"],
+        },
+        {
+          name: "stakePool",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: false,
+          isSigner: false,
+          docs: [
+            "Token Account of the StakePool's `mint` to receive early withdraw penalties",
           ],
         },
       ],
-      args: [],
+      args: [
+        {
+          name: "penaltyBps",
+          type: "u16",
+        },
+      ],
     },
     {
-      name: "withdraw",
+      name: "updatePoolConfig",
       docs: [
-        "Withdraw (aka Unstake) a wallet's tokens for a specific StakeDepositReceipt. The StakePool's",
-        "total weighted stake will be decreased by the effective stake amount of the StakeDepositReceipt",
-        "and the original amount deposited will be transferred out of the vault.",
-        "",
-        "All rewards will be claimed. So, for each RewardPool, the latest amount per effective stake will",
-        "be recalculated to ensure the latest accumulated rewards are accounted for in the claimable amount.",
-        "The StakeDepositReceipt is also updated so that the latest claimed amount is equivalent, so that",
-        "their claimable amount is 0 after invoking the withdraw instruction.",
+        "Update the lockup and weight configuration of a [StakePool](state::StakePool). Only applies",
+        "to future deposits, existing StakeDepositReceipts keep their effective stake.",
         "",
-        "StakeDepositReceipt account is closed after this instruction.",
+        "`max_weight` may only change within the same digit shift, since the `stake_mint`",
+        "decimals are derived from it. The StakePool's weight curve must remain valid.",
         "",
-        "Remaining accounts are required: pass the `reward_vault` of each reward pool. These must be",
-        "passed in the same order as `StakePool.reward_pools`. The owner (the token account which",
-        "gains the withdrawn funds) must also be passed be, in pairs like so:",
-        "* `<reward_vault[0]><owner[0]>`",
-        "* `<reward_vault[1]><owner[1]>",
-        "* ...etc",
+        "Can only be invoked by the StakePool's authority.",
       ],
       accounts: [
         {
-          name: "claimBase",
-          accounts: [
-            {
-              name: "owner",
-              isMut: true,
-              isSigner: true,
-              docs: ["Owner of the StakeDepositReceipt"],
-            },
-            {
-              name: "stakePool",
-              isMut: true,
-              isSigner: false,
-            },
-            {
-              name: "stakeDepositReceipt",
-              isMut: true,
-              isSigner: false,
-              docs: [
-                "StakeDepositReceipt of the owner that will be used to claim respective rewards",
-              ],
-            },
-            {
-              name: "tokenProgram",
-              isMut: false,
-              isSigner: false,
-            },
-          ],
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["Authority of the StakePool"],
         },
         {
-          name: "vault",
+          name: "stakePool",
           isMut: true,
           isSigner: false,
-          docs: ["Vault of the StakePool token will be transferred from"],
+        },
+      ],
+      args: [
+        {
+          name: "baseWeight",
+          type: "u64",
         },
         {
-          name: "stakeMint",
+          name: "maxWeight",
+          type: "u64",
+        },
+        {
+          name: "minDuration",
+          type: "u64",
+        },
+        {
+          name: "maxDuration",
+          type: "u64",
+        },
+      ],
+    },
+    {
+      name: "setRole",
+      docs: [
+        "Assign `key` to a `role` of the [StakePool](state::StakePool), allowing it to perform a",
+        "subset of the authority's actions. Pass Pubkey default to unassign the role.",
+        "* `Pauser` - may toggle `ESCAPE_HATCH_ENABLED` and `DISABLE_DEPOSITS`",
+        "* `RewardManager` - may add RewardPools, set their emission and fund them with a schedule",
+        "* `MetadataAdmin` - may update the `stake_mint` metadata",
+        "",
+        "Can only be invoked by the StakePool's authority.",
+      ],
+      accounts: [
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["Current authority of the StakePool"],
+        },
+        {
+          name: "stakePool",
           isMut: true,
           isSigner: false,
-          docs: ["stake_mint of StakePool that will be burned"],
         },
+      ],
+      args: [
         {
-          name: "from",
+          name: "role",
+          type: {
+            defined: "StakePoolRole",
+          },
+        },
+        {
+          name: "key",
+          type: "publicKey",
+        },
+      ],
+    },
+    {
+      name: "setFlags",
+      docs: [
+        "Update various stakepool settings. Simply sum the settings together and pass as a single",
+        "number, which replaces all current settings. Allowed settings:",
+        "* ESCAPE_HATCH_ENABLED - 1",
+        "* DISABLE_DEPOSITS - 4",
+        "* DEPOSIT_IGNORES_LP - 8",
+        "* WITHDRAW_IGNORES_LP - 16",
+        "",
+        "Can only be invoked by the StakePool's authority, or its pauser when only changing",
+        "ESCAPE_HATCH_ENABLED or DISABLE_DEPOSITS.",
+      ],
+      accounts: [
+        {
+          name: "authority",
           isMut: true,
-          isSigner: false,
+          isSigner: true,
           docs: [
-            "Token Account holding weighted stake representation token to burn",
+            "Current authority of the StakePool, or its pauser when only changing `PAUSER_FLAGS`",
           ],
         },
         {
-          name: "destination",
+          name: "stakePool",
           isMut: true,
           isSigner: false,
-          docs: ["Token account to transfer the previously staked token to"],
         },
       ],
-      args: [],
+      args: [
+        {
+          name: "flags",
+          type: "u8",
+        },
+      ],
     },
     {
-      name: "updateTokenMeta",
+      name: "enableFlags",
+      docs: [
+        "Enable the settings in `mask`, leaving all other settings unchanged. Allowed settings are",
+        "the same as `set_flags`.",
+        "",
+        "Can only be invoked by the StakePool's authority, or its pauser when only changing",
+        "ESCAPE_HATCH_ENABLED or DISABLE_DEPOSITS.",
+      ],
       accounts: [
         {
           name: "authority",
-          isMut: false,
+          isMut: true,
           isSigner: true,
+          docs: [
+            "Current authority of the StakePool, or its pauser when only changing `PAUSER_FLAGS`",
+          ],
         },
         {
-          name: "metadataAccount",
+          name: "stakePool",
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
+        {
+          name: "mask",
+          type: "u8",
+        },
+      ],
+    },
+    {
+      name: "disableFlags",
+      docs: [
+        "Disable the settings in `mask`, leaving all other settings unchanged. Allowed settings are",
+        "the same as `set_flags`.",
+        "",
+        "Can only be invoked by the StakePool's authority, or its pauser when only changing",
+        "ESCAPE_HATCH_ENABLED or DISABLE_DEPOSITS.",
+      ],
+      accounts: [
+        {
+          name: "authority",
+          isMut: true,
+          isSigner: true,
+          docs: [
+            "Current authority of the StakePool, or its pauser when only changing `PAUSER_FLAGS`",
+          ],
+        },
         {
           name: "stakePool",
-          isMut: false,
+          isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: "stakeMint",
+          name: "mask",
+          type: "u8",
+        },
+      ],
+    },
+    {
+      name: "deposit",
+      docs: [
+        "Deposit (aka Stake) a wallet's tokens to the specified [StakePool](state::StakePool).",
+        "Depending on the `lockup_duration` and the StakePool's weighting configuration, the",
+        "wallet initiating the deposit will receive tokens representing their effective stake",
+        "(i.e. deposited amount multiplied by the lockup weight).",
+        "",
+        "For each RewardPool, the latest amount per effective stake will be recalculated to ensure",
+        "the latest accumulated rewards are attributed to all previous depositors and not the deposit",
+        "resulting from this instruction.",
+        "",
+        "A [StakeDepositReceipt](state::StakeDepositReceipt) will be created to track the",
+        "lockup duration, effective weight, and claimable rewards.",
+        "",
+        "Remaining accounts are required: pass the `reward_vault` of each reward pool. These must be",
+        "passed in the same order as `StakePool.reward_pools`",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "owner",
           isMut: false,
           isSigner: false,
+          docs: [
+            "Owner of the StakeDepositReceipt, which may differ",
+            "from the account staking.",
+          ],
         },
         {
-          name: "metadataProgram",
-          isMut: false,
+          name: "from",
+          isMut: true,
           isSigner: false,
+          docs: [
+            "Token Account to transfer stake_mint from, to be deposited into the vault",
+          ],
         },
         {
-          name: "rent",
-          isMut: false,
+          name: "vault",
+          isMut: true,
           isSigner: false,
+          docs: ["Vault of the StakePool token will be transfer to"],
         },
         {
-          name: "systemProgram",
+          name: "mint",
           isMut: false,
           isSigner: false,
+          docs: ["Mint of the StakePool's underlying token"],
         },
-      ],
-      args: [
         {
-          name: "name",
-          type: "string",
+          name: "stakeMint",
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: "symbol",
-          type: "string",
+          name: "destination",
+          isMut: true,
+          isSigner: false,
+          docs: ["Token account the StakePool token will be transfered to"],
         },
         {
-          name: "uri",
-          type: "string",
+          name: "stakePool",
+          isMut: true,
+          isSigner: false,
+          docs: ["StakePool owning the vault that will receive the deposit"],
         },
-      ],
-    },
-  ],
-  accounts: [
-    {
-      name: "stakePool",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "creator",
-            docs: [
+        {
+          name: "stakeDepositReceipt",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "token2022Program",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+          docs: [
+            "Token program of a non-transferable `stake_mint`, only required for such StakePools",
+          ],
+        },
+        {
+          name: "mintTokenProgram",
+          isMut: false,
+          isSigner: false,
+          docs: ["Token program of the `mint`, either SPL Token or Token-2022"],
+        },
+        {
+          name: "rent",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "nonce",
+          type: "u32",
+        },
+        {
+          name: "amount",
+          type: "u64",
+        },
+        {
+          name: "lockupDuration",
+          type: "u64",
+        },
+      ],
+    },
+    {
+      name: "claimAll",
+      docs: [
+        "Claim unclaimed rewards from all RewardPools for a specific StakeDepositReceipt.",
+        "",
+        "For each RewardPool, the latest amount per effective stake will be recalculated to ensure",
+        "the latest accumulated rewards are accounted for in the claimable amount. The StakeDepositReceipt",
+        "is also updated so that the latest claimed amount is equivalent, so that their claimable amount",
+        "is 0 after invoking the claim instruction.",
+      ],
+      accounts: [
+        {
+          name: "claimBase",
+          accounts: [
+            {
+              name: "owner",
+              isMut: true,
+              isSigner: true,
+              docs: ["Owner of the StakeDepositReceipt"],
+            },
+            {
+              name: "stakePool",
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: "stakeDepositReceipt",
+              isMut: true,
+              isSigner: false,
+              docs: [
+                "StakeDepositReceipt of the owner that will be used to claim respective rewards",
+              ],
+            },
+            {
+              name: "tokenProgram",
+              isMut: false,
+              isSigner: false,
+            },
+            {
+              name: "token2022Program",
+              isMut: false,
+              isSigner: false,
+              isOptional: true,
+              docs: [
+                "Token program of Token-2022 RewardPools and a non-transferable `stake_mint`, only",
+                "required when using either",
+              ],
+            },
+          ],
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "claimSelected",
+      docs: [
+        "Claim unclaimed rewards from the RewardPools selected by `reward_pool_mask` for a specific",
+        "StakeDepositReceipt, where bit `n` of the mask selects the RewardPool at index `n`. Claimed",
+        "amounts of the other RewardPools are left untouched, so they remain claimable later.",
+        "",
+        "Remaining accounts are required: pass the `reward_vault` and owner's reward token account",
+        "of each selected RewardPool in pairs, in the same order as `StakePool.reward_pools`.",
+      ],
+      accounts: [
+        {
+          name: "claimBase",
+          accounts: [
+            {
+              name: "owner",
+              isMut: true,
+              isSigner: true,
+              docs: ["Owner of the StakeDepositReceipt"],
+            },
+            {
+              name: "stakePool",
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: "stakeDepositReceipt",
+              isMut: true,
+              isSigner: false,
+              docs: [
+                "StakeDepositReceipt of the owner that will be used to claim respective rewards",
+              ],
+            },
+            {
+              name: "tokenProgram",
+              isMut: false,
+              isSigner: false,
+            },
+            {
+              name: "token2022Program",
+              isMut: false,
+              isSigner: false,
+              isOptional: true,
+              docs: [
+                "Token program of Token-2022 RewardPools and a non-transferable `stake_mint`, only",
+                "required when using either",
+              ],
+            },
+          ],
+        },
+      ],
+      args: [
+        {
+          name: "rewardPoolMask",
+          type: "u16",
+        },
+      ],
+    },
+    {
+      name: "setClaimDelegate",
+      docs: [
+        "Allow `delegate` to claim the rewards of the owner's StakeDepositReceipts in the",
+        "[StakePool](state::StakePool) with `crank_claim`. Pass Pubkey default to allow anyone,",
+        "e.g. an automated distribution crank. Use `revoke_claim_delegate` before changing the",
+        "delegate.",
+      ],
+      accounts: [
+        {
+          name: "owner",
+          isMut: true,
+          isSigner: true,
+          docs: [
+            "Owner of the StakeDepositReceipts, pays for the ClaimDelegate",
+          ],
+        },
+        {
+          name: "stakePool",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "claimDelegate",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "delegate",
+          type: "publicKey",
+        },
+      ],
+    },
+    {
+      name: "revokeClaimDelegate",
+      docs: [
+        "Close the owner's [ClaimDelegate](state::ClaimDelegate), disabling `crank_claim` for",
+        "their StakeDepositReceipts.",
+      ],
+      accounts: [
+        {
+          name: "owner",
+          isMut: true,
+          isSigner: true,
+          docs: [
+            "Owner of the StakeDepositReceipts, receives the ClaimDelegate rent",
+          ],
+        },
+        {
+          name: "claimDelegate",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "crankClaim",
+      docs: [
+        "Claim unclaimed rewards of the RewardPools selected by `reward_pool_mask` for a specific",
+        "StakeDepositReceipt on behalf of its owner, exactly like `claim_selected`. The owner must",
+        "have opted in with `set_claim_delegate` and the signer must be their delegate, unless",
+        "anyone is allowed. Rewards may only be transferred to the owner's associated token",
+        "accounts.",
+        "",
+        "Remaining accounts are required: pass the `reward_vault` and owner's associated token",
+        "account of each selected RewardPool in pairs, exactly the same as `claim_selected`.",
+      ],
+      accounts: [
+        {
+          name: "cranker",
+          isMut: false,
+          isSigner: true,
+          docs: [
+            "Claims on behalf of the owner. Must be the owner's claim delegate, unless the owner",
+            "allows anyone to claim.",
+          ],
+        },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "stakePool",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "stakeDepositReceipt",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "StakeDepositReceipt of the owner that will be used to claim respective rewards",
+          ],
+        },
+        {
+          name: "claimDelegate",
+          isMut: false,
+          isSigner: false,
+          docs: ["Created by the owner to opt in to claims on their behalf"],
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "token2022Program",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+          docs: [
+            "Token program of Token-2022 RewardPools, only required when claiming from them",
+          ],
+        },
+      ],
+      args: [
+        {
+          name: "rewardPoolMask",
+          type: "u16",
+        },
+      ],
+    },
+    {
+      name: "withdraw",
+      docs: [
+        "Withdraw (aka Unstake) a wallet's tokens for a specific StakeDepositReceipt. The StakePool's",
+        "total weighted stake will be decreased by the effective stake amount of the StakeDepositReceipt",
+        "and the original amount deposited will be transferred out of the vault.",
+        "",
+        "All rewards will be claimed. So, for each RewardPool, the latest amount per effective stake will",
+        "be recalculated to ensure the latest accumulated rewards are accounted for in the claimable amount.",
+        "The StakeDepositReceipt is also updated so that the latest claimed amount is equivalent, so that",
+        "their claimable amount is 0 after invoking the withdraw instruction.",
+        "",
+        "StakeDepositReceipt account is closed after this instruction.",
+        "",
+        "When the StakePool has an early withdraw penalty, a locked StakeDepositReceipt may be",
+        "withdrawn by passing the StakePool's `treasury`, which receives the penalty.",
+        "",
+        "Remaining accounts are required: pass the `reward_vault` of each reward pool. These must be",
+        "passed in the same order as `StakePool.reward_pools`. The owner (the token account which",
+        "gains the withdrawn funds) must also be passed be, in pairs like so:",
+        "* `<reward_vault[0]><owner[0]>`",
+        "* `<reward_vault[1]><owner[1]>",
+        "* ...etc",
+        "",
+        "The StakeDepositReceipt's RewardCheckpoints follow as the last remaining accounts, an",
+        "`<external_reward_pool><reward_checkpoint>` pair for each. They keep their unclaimed rewards",
+        "after the receipt is closed.",
+      ],
+      accounts: [
+        {
+          name: "claimBase",
+          accounts: [
+            {
+              name: "owner",
+              isMut: true,
+              isSigner: true,
+              docs: ["Owner of the StakeDepositReceipt"],
+            },
+            {
+              name: "stakePool",
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: "stakeDepositReceipt",
+              isMut: true,
+              isSigner: false,
+              docs: [
+                "StakeDepositReceipt of the owner that will be used to claim respective rewards",
+              ],
+            },
+            {
+              name: "tokenProgram",
+              isMut: false,
+              isSigner: false,
+            },
+            {
+              name: "token2022Program",
+              isMut: false,
+              isSigner: false,
+              isOptional: true,
+              docs: [
+                "Token program of Token-2022 RewardPools and a non-transferable `stake_mint`, only",
+                "required when using either",
+              ],
+            },
+          ],
+        },
+        {
+          name: "vault",
+          isMut: true,
+          isSigner: false,
+          docs: ["Vault of the StakePool token will be transferred from"],
+        },
+        {
+          name: "mint",
+          isMut: false,
+          isSigner: false,
+          docs: ["Mint of the StakePool's underlying token"],
+        },
+        {
+          name: "stakeMint",
+          isMut: true,
+          isSigner: false,
+          docs: ["stake_mint of StakePool that will be burned"],
+        },
+        {
+          name: "from",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "Token Account holding weighted stake representation token to burn",
+          ],
+        },
+        {
+          name: "destination",
+          isMut: true,
+          isSigner: false,
+          docs: ["Token account to transfer the previously staked token to"],
+        },
+        {
+          name: "treasury",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          docs: [
+            "Treasury of the StakePool, only required to withdraw before the lockup ends",
+          ],
+        },
+        {
+          name: "mintTokenProgram",
+          isMut: false,
+          isSigner: false,
+          docs: ["Token program of the `mint`, either SPL Token or Token-2022"],
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "withdrawPartial",
+      docs: [
+        "Withdraw (aka Unstake) part of a StakeDepositReceipt's tokens. The receipt remains open with",
+        "its `deposit_amount` reduced by `amount` and its effective stake reduced proportionally. The",
+        "StakePool's total weighted stake is decreased by the removed effective stake and the matching",
+        "amount of `stake_mint` is burned.",
+        "",
+        "All rewards will be claimed prior to reducing the stake, so the StakeDepositReceipt's claimable",
+        "amount is 0 after invoking the instruction. `amount` must be less than the deposited amount,",
+        "use `withdraw` to exit the position entirely.",
+        "",
+        "Takes the accounts of `withdraw` without the `treasury`, as the StakeDepositReceipt must be",
+        "unlocked.",
+        "",
+        "Remaining accounts are required: pass the `reward_vault` and owner's reward token account of",
+        "each reward pool in pairs, followed by the RewardCheckpoints, exactly the same as `withdraw`.",
+      ],
+      accounts: [
+        {
+          name: "claimBase",
+          accounts: [
+            {
+              name: "owner",
+              isMut: true,
+              isSigner: true,
+              docs: ["Owner of the StakeDepositReceipt"],
+            },
+            {
+              name: "stakePool",
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: "stakeDepositReceipt",
+              isMut: true,
+              isSigner: false,
+              docs: [
+                "StakeDepositReceipt of the owner that will be used to claim respective rewards",
+              ],
+            },
+            {
+              name: "tokenProgram",
+              isMut: false,
+              isSigner: false,
+            },
+            {
+              name: "token2022Program",
+              isMut: false,
+              isSigner: false,
+              isOptional: true,
+              docs: [
+                "Token program of Token-2022 RewardPools and a non-transferable `stake_mint`, only",
+                "required when using either",
+              ],
+            },
+          ],
+        },
+        {
+          name: "vault",
+          isMut: true,
+          isSigner: false,
+          docs: ["Vault of the StakePool token will be transferred from"],
+        },
+        {
+          name: "mint",
+          isMut: false,
+          isSigner: false,
+          docs: ["Mint of the StakePool's underlying token"],
+        },
+        {
+          name: "stakeMint",
+          isMut: true,
+          isSigner: false,
+          docs: ["stake_mint of StakePool that will be burned"],
+        },
+        {
+          name: "from",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "Token Account holding weighted stake representation token to burn",
+          ],
+        },
+        {
+          name: "destination",
+          isMut: true,
+          isSigner: false,
+          docs: ["Token account to transfer the previously staked token to"],
+        },
+        {
+          name: "mintTokenProgram",
+          isMut: false,
+          isSigner: false,
+          docs: ["Token program of the `mint`, either SPL Token or Token-2022"],
+        },
+      ],
+      args: [
+        {
+          name: "amount",
+          type: "u64",
+        },
+      ],
+    },
+    {
+      name: "extendLockup",
+      docs: [
+        "Extend the lockup of an existing StakeDepositReceipt without withdrawing. The lockup",
+        "restarts at the current time with `new_lockup_duration`, which must not be shorter than the",
+        "receipt's current `lockup_duration`. The effective stake is recalculated with the new weight,",
+        "the StakePool's total weighted stake is increased by the difference and the additional",
+        "`stake_mint` is minted to the owner.",
+        "",
+        "All rewards will be claimed prior to updating the effective stake, so the StakeDepositReceipt's",
+        "claimable amount is 0 after invoking the instruction.",
+        "",
+        "Remaining accounts are required: pass the `reward_vault` and owner's reward token account of",
+        "each reward pool in pairs, exactly the same as `claim_all`.",
+      ],
+      accounts: [
+        {
+          name: "claimBase",
+          accounts: [
+            {
+              name: "owner",
+              isMut: true,
+              isSigner: true,
+              docs: ["Owner of the StakeDepositReceipt"],
+            },
+            {
+              name: "stakePool",
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: "stakeDepositReceipt",
+              isMut: true,
+              isSigner: false,
+              docs: [
+                "StakeDepositReceipt of the owner that will be used to claim respective rewards",
+              ],
+            },
+            {
+              name: "tokenProgram",
+              isMut: false,
+              isSigner: false,
+            },
+            {
+              name: "token2022Program",
+              isMut: false,
+              isSigner: false,
+              isOptional: true,
+              docs: [
+                "Token program of Token-2022 RewardPools and a non-transferable `stake_mint`, only",
+                "required when using either",
+              ],
+            },
+          ],
+        },
+        {
+          name: "stakeMint",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "stake_mint of StakePool that will be minted for the additional effective stake",
+          ],
+        },
+        {
+          name: "destination",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "Token account the additional StakePool token will be transfered to",
+          ],
+        },
+      ],
+      args: [
+        {
+          name: "newLockupDuration",
+          type: "u64",
+        },
+      ],
+    },
+    {
+      name: "compound",
+      docs: [
+        "Restake the claimable rewards of the RewardPool at `index` into the StakeDepositReceipt,",
+        "for RewardPools distributing the StakePool's `mint`. The rewards are moved to the StakePool",
+        "vault and added to the receipt's deposit at its existing weight and lockup. The StakePool's",
+        "total weighted stake is increased and the additional `stake_mint` is minted to the owner.",
+        "Receipts with a lockup must still be locked, use `extend_lockup` to relock them first.",
+        "",
+        "Rewards of all other RewardPools will be claimed prior to increasing the effective stake.",
+        "",
+        "Remaining accounts are required: pass the `reward_vault` and owner's reward token account of",
+        "each reward pool in pairs, exactly the same as `claim_all`.",
+      ],
+      accounts: [
+        {
+          name: "claimBase",
+          accounts: [
+            {
+              name: "owner",
+              isMut: true,
+              isSigner: true,
+              docs: ["Owner of the StakeDepositReceipt"],
+            },
+            {
+              name: "stakePool",
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: "stakeDepositReceipt",
+              isMut: true,
+              isSigner: false,
+              docs: [
+                "StakeDepositReceipt of the owner that will be used to claim respective rewards",
+              ],
+            },
+            {
+              name: "tokenProgram",
+              isMut: false,
+              isSigner: false,
+            },
+            {
+              name: "token2022Program",
+              isMut: false,
+              isSigner: false,
+              isOptional: true,
+              docs: [
+                "Token program of Token-2022 RewardPools and a non-transferable `stake_mint`, only",
+                "required when using either",
+              ],
+            },
+          ],
+        },
+        {
+          name: "vault",
+          isMut: true,
+          isSigner: false,
+          docs: ["Vault of the StakePool receiving the compounded rewards"],
+        },
+        {
+          name: "mint",
+          isMut: false,
+          isSigner: false,
+          docs: [
+            "Mint of the StakePool's underlying token, which the RewardPool must distribute",
+          ],
+        },
+        {
+          name: "rewardVault",
+          isMut: true,
+          isSigner: false,
+          docs: ["Vault of the RewardPool at `index`"],
+        },
+        {
+          name: "stakeMint",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "stake_mint of StakePool that will be minted for the additional effective stake",
+          ],
+        },
+        {
+          name: "destination",
+          isMut: true,
+          isSigner: false,
+          docs: ["Token account the StakePool token will be transfered to"],
+        },
+        {
+          name: "mintTokenProgram",
+          isMut: false,
+          isSigner: false,
+          docs: ["Token program of the `mint`, either SPL Token or Token-2022"],
+        },
+      ],
+      args: [
+        {
+          name: "index",
+          type: "u8",
+        },
+      ],
+    },
+    {
+      name: "mergeReceipts",
+      docs: [
+        "Merge a source StakeDepositReceipt into a destination StakeDepositReceipt of the same owner",
+        "and StakePool. The deposited amounts and effective stakes are summed and the merged receipt",
+        "unlocks at the later of the two unlock times. The StakePool's total weighted stake is unchanged.",
+        "",
+        "All rewards of both receipts will be claimed prior to merging. The source StakeDepositReceipt",
+        "account is closed after this instruction.",
+        "",
+        "Remaining accounts are required: pass the `reward_vault` and owner's reward token account of",
+        "each reward pool in pairs, exactly the same as `claim_all`.",
+      ],
+      accounts: [
+        {
+          name: "claimBase",
+          accounts: [
+            {
+              name: "owner",
+              isMut: true,
+              isSigner: true,
+              docs: ["Owner of the StakeDepositReceipt"],
+            },
+            {
+              name: "stakePool",
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: "stakeDepositReceipt",
+              isMut: true,
+              isSigner: false,
+              docs: [
+                "StakeDepositReceipt of the owner that will be used to claim respective rewards",
+              ],
+            },
+            {
+              name: "tokenProgram",
+              isMut: false,
+              isSigner: false,
+            },
+            {
+              name: "token2022Program",
+              isMut: false,
+              isSigner: false,
+              isOptional: true,
+              docs: [
+                "Token program of Token-2022 RewardPools and a non-transferable `stake_mint`, only",
+                "required when using either",
+              ],
+            },
+          ],
+        },
+        {
+          name: "sourceStakeDepositReceipt",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "StakeDepositReceipt that will be merged into the destination and closed",
+          ],
+        },
+        {
+          name: "stakeMint",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "stake_mint of StakePool that will be minted to cover rounding of the merged stake",
+          ],
+        },
+        {
+          name: "destination",
+          isMut: true,
+          isSigner: false,
+          docs: ["Token account the StakePool token will be transfered to"],
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "splitReceipt",
+      docs: [
+        "Split `amount` of a StakeDepositReceipt's deposit, and the proportional effective stake, into",
+        "a new StakeDepositReceipt of the same owner derived from `new_nonce`. The new receipt has the",
+        "same lockup end as the original. The StakePool's total weighted stake is unchanged.",
+        "",
+        "All rewards will be claimed prior to splitting, so both receipts start with a claimable",
+        "amount of 0.",
+        "",
+        "Remaining accounts are required: pass the `reward_vault` and owner's reward token account of",
+        "each reward pool in pairs, exactly the same as `claim_all`, followed by the RewardCheckpoints",
+        "of the StakeDepositReceipt like `withdraw`.",
+      ],
+      accounts: [
+        {
+          name: "claimBase",
+          accounts: [
+            {
+              name: "owner",
+              isMut: true,
+              isSigner: true,
+              docs: ["Owner of the StakeDepositReceipt"],
+            },
+            {
+              name: "stakePool",
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: "stakeDepositReceipt",
+              isMut: true,
+              isSigner: false,
+              docs: [
+                "StakeDepositReceipt of the owner that will be used to claim respective rewards",
+              ],
+            },
+            {
+              name: "tokenProgram",
+              isMut: false,
+              isSigner: false,
+            },
+            {
+              name: "token2022Program",
+              isMut: false,
+              isSigner: false,
+              isOptional: true,
+              docs: [
+                "Token program of Token-2022 RewardPools and a non-transferable `stake_mint`, only",
+                "required when using either",
+              ],
+            },
+          ],
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+          docs: ["Payer of rent for the new StakeDepositReceipt"],
+        },
+        {
+          name: "newStakeDepositReceipt",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "StakeDepositReceipt that will receive the split portion of the stake",
+          ],
+        },
+        {
+          name: "stakeMint",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "stake_mint of StakePool that will be burned to cover rounding of the split stake",
+          ],
+        },
+        {
+          name: "from",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "Token Account holding weighted stake representation token to burn",
+          ],
+        },
+        {
+          name: "rent",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "amount",
+          type: "u64",
+        },
+        {
+          name: "newNonce",
+          type: "u32",
+        },
+      ],
+    },
+    {
+      name: "transferReceipt",
+      docs: [
+        "Transfer a StakeDepositReceipt to `new_owner`. Since the owner is part of the receipt's",
+        "address, the position is moved to a new StakeDepositReceipt derived from `new_owner` and",
+        "`new_nonce`, keeping the deposit, effective stake and lockup. The effective stake amount of",
+        "`stake_mint` is transferred to the new owner, so they are able to withdraw.",
+        "",
+        "All rewards will be claimed to the current owner prior to the transfer. The original",
+        "StakeDepositReceipt account is closed after this instruction.",
+        "",
+        "Remaining accounts are required: pass the `reward_vault` and owner's reward token account of",
+        "each reward pool in pairs, exactly the same as `claim_all`.",
+      ],
+      accounts: [
+        {
+          name: "claimBase",
+          accounts: [
+            {
+              name: "owner",
+              isMut: true,
+              isSigner: true,
+              docs: ["Owner of the StakeDepositReceipt"],
+            },
+            {
+              name: "stakePool",
+              isMut: true,
+              isSigner: false,
+            },
+            {
+              name: "stakeDepositReceipt",
+              isMut: true,
+              isSigner: false,
+              docs: [
+                "StakeDepositReceipt of the owner that will be used to claim respective rewards",
+              ],
+            },
+            {
+              name: "tokenProgram",
+              isMut: false,
+              isSigner: false,
+            },
+            {
+              name: "token2022Program",
+              isMut: false,
+              isSigner: false,
+              isOptional: true,
+              docs: [
+                "Token program of Token-2022 RewardPools and a non-transferable `stake_mint`, only",
+                "required when using either",
+              ],
+            },
+          ],
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+          docs: ["Payer of rent for the new StakeDepositReceipt"],
+        },
+        {
+          name: "newOwner",
+          isMut: false,
+          isSigner: false,
+          docs: ["Owner of the new StakeDepositReceipt."],
+        },
+        {
+          name: "newStakeDepositReceipt",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "StakeDepositReceipt of the new owner that the position is moved to",
+          ],
+        },
+        {
+          name: "stakeMint",
+          isMut: true,
+          isSigner: false,
+          docs: ["stake_mint of the StakePool"],
+        },
+        {
+          name: "from",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "Token Account holding weighted stake representation token to transfer",
+          ],
+        },
+        {
+          name: "destination",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "Token account of the new owner the weighted stake representation token will be transferred to",
+          ],
+        },
+        {
+          name: "rent",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "newNonce",
+          type: "u32",
+        },
+      ],
+    },
+    {
+      name: "createVoterWeightRecord",
+      docs: [
+        "Create the SPL Governance [VoterWeightRecord](state::VoterWeightRecord) of",
+        "`governing_token_owner` for `realm`, allowing this program to be used as the voter weight",
+        "addin of a Realm whose governing token is the StakePool's `stake_mint`. Members then vote",
+        "with their locked stake directly, without depositing `stake_mint` into the Realm.",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+          docs: ["Payer of the VoterWeightRecord rent"],
+        },
+        {
+          name: "governingTokenOwner",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "stakePool",
+          isMut: false,
+          isSigner: false,
+          docs: ["StakePool whose `stake_mint` is the Realm's governing token"],
+        },
+        {
+          name: "realm",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "voterWeightRecord",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "createMaxVoterWeightRecord",
+      docs: [
+        "Create the SPL Governance [MaxVoterWeightRecord](state::MaxVoterWeightRecord) of `realm`",
+        "for the StakePool's `stake_mint`.",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+          docs: ["Payer of the MaxVoterWeightRecord rent"],
+        },
+        {
+          name: "stakePool",
+          isMut: false,
+          isSigner: false,
+          docs: ["StakePool whose `stake_mint` is the Realm's governing token"],
+        },
+        {
+          name: "realm",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "maxVoterWeightRecord",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "updateVoterWeightRecord",
+      docs: [
+        "Set the `voter_weight` of a [VoterWeightRecord](state::VoterWeightRecord) to the",
+        "effective stake of the owner's StakeDepositReceipts, in `stake_mint` tokens. The weight",
+        "expires after the current slot, so this must be invoked in the same transaction as the",
+        "governance instruction relying on it.",
+        "",
+        "Must be signed by the `governing_token_owner`. The counted StakeDepositReceipts are locked",
+        "for `VOTE_LOCK_DURATION` seconds, during which they can not be withdrawn, split,",
+        "transferred or merged into another receipt, so their stake can not vote twice.",
+        "",
+        "Remaining accounts: pass each StakeDepositReceipt of the `governing_token_owner` in the",
+        "StakePool that should count towards the weight, writable. Receipts must be distinct.",
+      ],
+      accounts: [
+        {
+          name: "governingTokenOwner",
+          isMut: true,
+          isSigner: true,
+          docs: [
+            "Owner of the StakeDepositReceipts, pays the rent of storing their vote lock",
+          ],
+        },
+        {
+          name: "stakePool",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "voterWeightRecord",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "updateMaxVoterWeightRecord",
+      docs: [
+        "Set the `max_voter_weight` of a [MaxVoterWeightRecord](state::MaxVoterWeightRecord) to",
+        "the StakePool's total weighted stake, in `stake_mint` tokens. Expires after the current",
+        "slot like `update_voter_weight_record`.",
+      ],
+      accounts: [
+        {
+          name: "stakePool",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "maxVoterWeightRecord",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "addExternalRewardPool",
+      docs: [
+        "Add an [ExternalRewardPool](state::ExternalRewardPool) with its own account and reward",
+        "vault, derived from the StakePool and `id`. Unlike the RewardPools embedded in the",
+        "StakePool, a StakePool may have any number of them.",
+        "",
+        "Can only be invoked by the StakePool's authority or reward manager.",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+          docs: ["Payer of rent"],
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["Authority or reward manager of the StakePool"],
+        },
+        {
+          name: "rewardMint",
+          isMut: false,
+          isSigner: false,
+          docs: [
+            "SPL Token or Token-2022 Mint of the token that will be distributed as rewards",
+          ],
+        },
+        {
+          name: "stakePool",
+          isMut: false,
+          isSigner: false,
+          docs: ["StakePool the ExternalRewardPool is added to"],
+        },
+        {
+          name: "rewardPool",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "rewardVault",
+          isMut: true,
+          isSigner: false,
+          docs: ["An SPL token Account for holding rewards to be claimed"],
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+          docs: [
+            "Token program of the `reward_mint`, either SPL Token or Token-2022",
+          ],
+        },
+        {
+          name: "rent",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "id",
+          type: "u32",
+        },
+      ],
+    },
+    {
+      name: "fundExternalRewardPool",
+      docs: [
+        "Transfer `amount` of rewards into an ExternalRewardPool and stream them, together with",
+        "any undistributed rewards, evenly over `duration` seconds to the StakeDepositReceipts that",
+        "joined it, pro rata to their effective stake.",
+      ],
+      accounts: [
+        {
+          name: "funder",
+          isMut: false,
+          isSigner: true,
+          docs: ["Wallet transferring the rewards"],
+        },
+        {
+          name: "rewardPool",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "rewardVault",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "rewardMint",
+          isMut: false,
+          isSigner: false,
+          docs: ["Mint of the ExternalRewardPool's `reward_vault`"],
+        },
+        {
+          name: "from",
+          isMut: true,
+          isSigner: false,
+          docs: ["Token account the rewards are transferred from"],
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+          docs: [
+            "Token program of the `reward_mint`, either SPL Token or Token-2022",
+          ],
+        },
+      ],
+      args: [
+        {
+          name: "amount",
+          type: "u64",
+        },
+        {
+          name: "duration",
+          type: "u64",
+        },
+      ],
+    },
+    {
+      name: "joinExternalRewardPool",
+      docs: [
+        "Opt a StakeDepositReceipt into an ExternalRewardPool by creating its",
+        "[RewardCheckpoint](state::RewardCheckpoint). Only rewards emitted afterwards are earned.",
+        "The StakeDepositReceipt can not be transferred or merged into another until it has left",
+        "all ExternalRewardPools, and must pass its RewardCheckpoints when withdrawing or splitting.",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+          docs: ["Payer of rent"],
+        },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+          docs: ["Owner of the StakeDepositReceipt"],
+        },
+        {
+          name: "rewardPool",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "stakeDepositReceipt",
+          isMut: true,
+          isSigner: false,
+          docs: [
+            "StakeDepositReceipt joining, extended to count its RewardCheckpoints if needed",
+          ],
+        },
+        {
+          name: "rewardCheckpoint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "syncRewardCheckpoint",
+      docs: [
+        "Settle the rewards of a RewardCheckpoint and update it to the current effective stake of",
+        "its StakeDepositReceipt. Permissionless.",
+      ],
+      accounts: [
+        {
+          name: "rewardPool",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "rewardCheckpoint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "stakeDepositReceipt",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "claimExternalRewardPool",
+      docs: [
+        "Sync a RewardCheckpoint and transfer all of its unclaimed rewards to the owner.",
+      ],
+      accounts: [
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+          docs: ["Owner of the StakeDepositReceipt"],
+        },
+        {
+          name: "stakePool",
+          isMut: false,
+          isSigner: false,
+          docs: ["StakePool owning the `reward_vault`"],
+        },
+        {
+          name: "rewardPool",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "rewardCheckpoint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "stakeDepositReceipt",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "rewardVault",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "rewardMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "ownerRewardAccount",
+          isMut: true,
+          isSigner: false,
+          docs: ["Token account of the owner receiving the rewards"],
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+          docs: [
+            "Token program of the `reward_mint`, either SPL Token or Token-2022",
+          ],
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "leaveExternalRewardPool",
+      docs: [
+        "Close a RewardCheckpoint, removing its effective stake from the ExternalRewardPool. All",
+        "rewards must have been claimed first, unless the ExternalRewardPool is sweepable. Works",
+        "after the StakeDepositReceipt was withdrawn.",
+      ],
+      accounts: [
+        {
+          name: "owner",
+          isMut: true,
+          isSigner: true,
+          docs: [
+            "Owner of the StakeDepositReceipt, receives the rent of the RewardCheckpoint",
+          ],
+        },
+        {
+          name: "rewardPool",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "rewardCheckpoint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "stakeDepositReceipt",
+          isMut: true,
+          isSigner: false,
+          docs: ["decreased unless it was withdrawn"],
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "retireExternalRewardPool",
+      docs: [
+        "Retire an ExternalRewardPool, so it can no longer be joined or funded and stops streaming.",
+        "Rewards not emitted yet are left to be swept. RewardCheckpoints",
+        "may claim for another `RETIRED_REWARD_POOL_CLAIM_WINDOW` seconds.",
+        "",
+        "Can only be invoked by the StakePool's authority.",
+      ],
+      accounts: [
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["Authority of the StakePool"],
+        },
+        {
+          name: "stakePool",
+          isMut: false,
+          isSigner: false,
+          docs: ["StakePool the ExternalRewardPool belongs to"],
+        },
+        {
+          name: "rewardPool",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "sweepExternalRewardPool",
+      docs: [
+        "Transfer the remaining rewards of an ExternalRewardPool retired for longer than the claim",
+        "window to `destination`.",
+        "",
+        "Can only be invoked by the StakePool's authority.",
+      ],
+      accounts: [
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["Authority of the StakePool"],
+        },
+        {
+          name: "stakePool",
+          isMut: false,
+          isSigner: false,
+          docs: [
+            "StakePool the ExternalRewardPool belongs to, owner of the `reward_vault`",
+          ],
+        },
+        {
+          name: "rewardPool",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "rewardVault",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "rewardMint",
+          isMut: false,
+          isSigner: false,
+          docs: ["Mint of the ExternalRewardPool's `reward_vault`"],
+        },
+        {
+          name: "destination",
+          isMut: true,
+          isSigner: false,
+          docs: ["Token account the remaining rewards will be transferred to"],
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+          docs: [
+            "Token program of the `reward_mint`, either SPL Token or Token-2022",
+          ],
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "updateTokenMeta",
+      docs: [
+        "Create or update the metadata of the `stake_mint`.",
+        "",
+        "Can only be invoked by the StakePool's authority or metadata admin.",
+      ],
+      accounts: [
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "metadataAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "stakePool",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "stakeMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "metadataProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "rent",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "name",
+          type: "string",
+        },
+        {
+          name: "symbol",
+          type: "string",
+        },
+        {
+          name: "uri",
+          type: "string",
+        },
+      ],
+    },
+  ],
+  accounts: [
+    {
+      name: "stakePool",
+      docs: [
+        "The account of a StakePool may be followed by the [RewardPoolExtension] of each RewardPool,",
+        "see [StakePoolLoader]. They are allocated by the first instruction writing to them, so the",
+        "StakePool layout is unchanged for existing accounts.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "creator",
+            docs: [
               "The original creator of the StakePool. Necessary for signer seeds",
             ],
             type: "publicKey",
           },
           {
-            name: "authority",
-            docs: ["Pubkey that can make updates to StakePool"],
+            name: "authority",
+            docs: ["Pubkey that can make updates to StakePool"],
+            type: "publicKey",
+          },
+          {
+            name: "totalWeightedStake",
+            docs: [
+              "Total amount staked that accounts for the lock up period weighting.\n    Note, this is not equal to the amount of SPL Tokens staked.",
+            ],
+            type: "u128",
+          },
+          {
+            name: "vault",
+            docs: ["Token Account to store the staked SPL Token"],
+            type: "publicKey",
+          },
+          {
+            name: "mint",
+            docs: ["Mint of the token being staked"],
+            type: "publicKey",
+          },
+          {
+            name: "stakeMint",
+            docs: ["Mint of the token representing effective stake"],
+            type: "publicKey",
+          },
+          {
+            name: "rewardPools",
+            docs: [
+              "Array of RewardPools that apply to the stake pool.",
+              "Unused entries are Pubkey default. In arbitrary order, and may have gaps.",
+            ],
+            type: {
+              array: [
+                {
+                  defined: "RewardPool",
+                },
+                10,
+              ],
+            },
+          },
+          {
+            name: "baseWeight",
+            docs: [
+              "The minimum weight received for staking. In terms of 1 / SCALE_FACTOR_BASE.",
+              "Examples:",
+              "* `min_weight = 1 x SCALE_FACTOR_BASE` = minmum of 1x multiplier for > min_duration staking",
+              "* `min_weight = 2 x SCALE_FACTOR_BASE` = minmum of 2x multiplier for > min_duration staking",
+            ],
+            type: "u64",
+          },
+          {
+            name: "maxWeight",
+            docs: [
+              "Maximum weight for staking lockup (i.e. weight multiplier when locked",
+              "up for max duration). In terms of 1 / SCALE_FACTOR_BASE. Examples:",
+              "* A `max_weight = 1 x SCALE_FACTOR_BASE` = 1x multiplier for max staking duration",
+              "* A `max_weight = 2 x SCALE_FACTOR_BASE` = 2x multiplier for max staking duration",
+            ],
+            type: "u64",
+          },
+          {
+            name: "minDuration",
+            docs: [
+              "Minimum duration for lockup. At this point, the staker would receive the base weight. In seconds.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "maxDuration",
+            docs: [
+              "Maximum duration for lockup. At this point, the staker would receive the max weight. In seconds.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "nonce",
+            docs: ["Nonce to derive multiple stake pools from same mint"],
+            type: "u8",
+          },
+          {
+            name: "bumpSeed",
+            docs: ["Bump seed for stake_mint"],
+            type: "u8",
+          },
+          {
+            name: "flags",
+            docs: [
+              "Each bit constrols a setting, see [PoolFlags]. Add settings together as needed. Settings",
+              "supported:",
+              "* `ESCAPE_HATCH_ENABLED` - 1, allows withdraws regardless of lockup time",
+              "* `DISABLE_DEPOSITS` - 4, causes the deposit ix to always fail",
+              "* `DEPOSIT_IGNORES_LP` - 8, the deposit ix will not mint any lp token",
+              "* `WITHDRAW_IGNORES_LP` - 16, the withdraw ix will not burn any lp token",
+              "",
+              "Do not access directly, use functions such as `escape_hatch_enabled`",
+            ],
+            type: "u8",
+          },
+          {
+            name: "padding0",
+            type: {
+              array: ["u8", 1],
+            },
+          },
+          {
+            name: "earlyWithdrawPenaltyBps",
+            docs: [
+              "Maximum penalty in basis points of the deposit for withdrawing before the lockup ends. The",
+              "penalty is scaled by the fraction of the lockup remaining. 0 disables early withdraws.",
+            ],
+            type: "u16",
+          },
+          {
+            name: "padding1",
+            type: {
+              array: ["u8", 2],
+            },
+          },
+          {
+            name: "treasury",
+            docs: [
+              "Token Account of `mint` receiving early withdraw penalties. May be the `reward_vault` of",
+              "a RewardPool distributing `mint`, to return penalties to the remaining stakers.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "weightCurve",
+            docs: [
+              "Function mapping lockup durations to weights. See [WeightCurve].",
+            ],
+            type: {
+              defined: "WeightCurveData",
+            },
+          },
+          {
+            name: "pendingAuthority",
+            docs: [
+              "Proposed new `authority`, which must accept to complete the transfer. Pubkey default",
+              "when there is no pending transfer.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "pauser",
+            docs: [
+              "May toggle `ESCAPE_HATCH_ENABLED` and `DISABLE_DEPOSITS` in addition to the `authority`.",
+              "Pubkey default when unassigned.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "rewardManager",
+            docs: [
+              "May add and fund RewardPools and set their emissions in addition to the `authority`.",
+              "Pubkey default when unassigned.",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "metadataAdmin",
+            docs: [
+              "May update the `stake_mint` metadata in addition to the `authority`. Pubkey default when",
+              "unassigned.",
+            ],
+            type: "publicKey",
+          },
+        ],
+      },
+    },
+    {
+      name: "stakeDepositReceipt",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "owner",
+            docs: ["Pubkey that owns the staked assets"],
+            type: "publicKey",
+          },
+          {
+            name: "payer",
+            docs: ["Pubkey that paid for the deposit"],
+            type: "publicKey",
+          },
+          {
+            name: "stakePool",
+            docs: ["StakePool the deposit is for"],
+            type: "publicKey",
+          },
+          {
+            name: "lockupDuration",
+            docs: ["Duration of the lockup period in seconds"],
+            type: "u64",
+          },
+          {
+            name: "depositTimestamp",
+            docs: ["Timestamp in seconds of when the stake lockup began"],
+            type: "i64",
+          },
+          {
+            name: "depositAmount",
+            docs: ["Amount of SPL token deposited"],
+            type: "u64",
+          },
+          {
+            name: "effectiveStake",
+            docs: ["Amount of stake weighted by lockup duration."],
+            type: "u128",
+          },
+          {
+            name: "claimedAmounts",
+            docs: [
+              "The amount per reward that has been claimed or perceived to be claimed. Indexes align with",
+              "the StakedPool reward_pools property.",
+            ],
+            type: {
+              array: ["u128", 10],
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "externalRewardPool",
+      docs: [
+        "RewardPool in its own account, so a StakePool may have an unbounded number of them in addition",
+        "to the `MAX_REWARD_POOLS` embedded in the StakePool. StakeDepositReceipts opt in with a",
+        "[RewardCheckpoint] and only opted in effective stake earns rewards, so deposits never iterate",
+        "ExternalRewardPools. Each checkpoint is settled lazily when it is synced.",
+        "",
+        "Funding is streamed over a duration, like a streaming [RewardPool], so joining right before a",
+        "fund only earns the share emitted while the stake is present. Instructions decreasing the",
+        "effective stake of a receipt sync all of its RewardCheckpoints, so the stake of every",
+        "checkpoint is backed by the receipt.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "stakePool",
+            docs: ["StakePool the RewardPool belongs to"],
+            type: "publicKey",
+          },
+          {
+            name: "rewardMint",
+            docs: ["Mint of the reward token"],
+            type: "publicKey",
+          },
+          {
+            name: "rewardVault",
+            docs: [
+              "Token Account to store the reward token, owned by the StakePool",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "tokenProgram",
+            docs: [
+              "Token program of the `reward_mint`, either SPL Token or Token-2022",
+            ],
+            type: "publicKey",
+          },
+          {
+            name: "totalEffectiveStake",
+            docs: [
+              "Sum of the effective stake of all RewardCheckpoints of the RewardPool",
+            ],
+            type: "u128",
+          },
+          {
+            name: "rewardsPerEffectiveStake",
+            docs: [
+              "Ever increasing accumulator of the amount of rewards per effective stake, increased",
+              "whenever the RewardPool is funded. Shifted by SCALE_FACTOR_BASE_SQUARED like",
+              "[RewardPool].",
+            ],
+            type: "u128",
+          },
+          {
+            name: "rewardsPerSecond",
+            docs: ["Amount of rewards emitted per second while streaming"],
+            type: "u64",
+          },
+          {
+            name: "startTime",
+            docs: ["Timestamp in seconds of when the emission starts"],
+            type: "i64",
+          },
+          {
+            name: "endTime",
+            docs: [
+              "Timestamp in seconds of when the emission ends, 0 when the RewardPool is not streaming",
+            ],
+            type: "i64",
+          },
+          {
+            name: "lastUpdateTime",
+            docs: [
+              "Timestamp in seconds of the last time emissions were distributed",
+            ],
+            type: "i64",
+          },
+          {
+            name: "undistributedAmount",
+            docs: [
+              "Amount of tokens in the `reward_vault` that have not been emitted yet. Tokens left when\n    the emission ends, or emitted while no stake has joined, are carried into the next schedule.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "retiredAt",
+            docs: ["Timestamp the RewardPool was retired at, 0 while active"],
+            type: "i64",
+          },
+          {
+            name: "id",
+            docs: ["Id to derive multiple RewardPools from the same StakePool"],
+            type: "u32",
+          },
+        ],
+      },
+    },
+    {
+      name: "rewardCheckpoint",
+      docs: [
+        "Participation of a StakeDepositReceipt in an [ExternalRewardPool].",
+        "",
+        "The checkpoint keeps its own copy of the receipt's effective stake, which is counted in the",
+        "RewardPool's `total_effective_stake` and brought up to date whenever it is synced. Increases",
+        "of the receipt's effective stake are earned on from the next sync, decreases are synced by the",
+        "instruction making them.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "owner",
+            docs: ["Owner of the StakeDepositReceipt"],
+            type: "publicKey",
+          },
+          {
+            name: "rewardPool",
+            docs: ["ExternalRewardPool the checkpoint participates in"],
+            type: "publicKey",
+          },
+          {
+            name: "stakeDepositReceipt",
+            docs: ["StakeDepositReceipt earning the rewards"],
+            type: "publicKey",
+          },
+          {
+            name: "effectiveStake",
+            docs: [
+              "Effective stake counted in the RewardPool's `total_effective_stake`",
+            ],
+            type: "u128",
+          },
+          {
+            name: "rewardsPerEffectiveStake",
+            docs: [
+              "The RewardPool's `rewards_per_effective_stake` when the checkpoint was last synced",
+            ],
+            type: "u128",
+          },
+          {
+            name: "unclaimed",
+            docs: ["Rewards settled by syncing that have not been claimed yet"],
+            type: "u64",
+          },
+          {
+            name: "receiptClosed",
+            docs: [
+              "True once the StakeDepositReceipt was withdrawn. The checkpoint then only holds its\n    unclaimed rewards until it leaves.",
+            ],
+            type: "bool",
+          },
+        ],
+      },
+    },
+    {
+      name: "claimDelegate",
+      docs: [
+        "Allows claiming the rewards of an owner's StakeDepositReceipts on their behalf with",
+        "`crank_claim`. Rewards are always transferred to the owner's associated token accounts.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "owner",
+            docs: ["Owner of the StakeDepositReceipts"],
+            type: "publicKey",
+          },
+          {
+            name: "stakePool",
+            docs: ["StakePool the StakeDepositReceipts belong to"],
             type: "publicKey",
           },
           {
-            name: "totalWeightedStake",
+            name: "delegate",
             docs: [
-              "Total amount staked that accounts for the lock up period weighting.\n    Note, this is not equal to the amount of SPL Tokens staked.",
+              "Pubkey allowed to claim on behalf of the owner. Pubkey default allows anyone",
             ],
-            type: "u128",
+            type: "publicKey",
+          },
+        ],
+      },
+    },
+  ],
+  types: [
+    {
+      name: "StakePoolRole",
+      docs: [
+        "Admin roles of a StakePool that may be assigned by the `authority`",
+      ],
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Pauser",
           },
           {
-            name: "vault",
-            docs: ["Token Account to store the staked SPL Token"],
-            type: "publicKey",
+            name: "RewardManager",
           },
           {
-            name: "mint",
-            docs: ["Mint of the token being staked"],
+            name: "MetadataAdmin",
+          },
+        ],
+      },
+    },
+    {
+      name: "RewardPool",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "rewardVault",
+            docs: ["Token Account to store the reward SPL Token"],
             type: "publicKey",
           },
           {
-            name: "stakeMint",
-            docs: ["Mint of the token representing effective stake"],
+            name: "rewardsPerEffectiveStake",
+            docs: [
+              "Ever increasing accumulator of the amount of rewards per effective stake.\n    Said another way, if a user deposited before any rewards were added to the\n    `vault`, then this would be the token amount per effective stake they could\n    claim.",
+            ],
+            type: "u128",
+          },
+          {
+            name: "lastAmount",
+            docs: ["latest amount of tokens in the vault"],
+            type: "u64",
+          },
+          {
+            name: "retiredAt",
+            docs: [
+              "Timestamp in seconds of when the RewardPool was retired, 0 while the RewardPool is active.\n    Retired RewardPools no longer accrue rewards.",
+            ],
+            type: "i64",
+          },
+        ],
+      },
+    },
+    {
+      name: "RewardPoolExtension",
+      docs: [
+        "Additional per RewardPool state that does not fit within the `RewardPool` layout.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "baseRewardsPerEffectiveStake",
+            docs: [
+              "`rewards_per_effective_stake` of the RewardPool when it was added to the slot. Any\n    claimed amount lower than this belongs to a previous RewardPool of the same slot and must be\n    treated as this value.",
+            ],
+            type: "u128",
+          },
+          {
+            name: "rewardsPerSecond",
+            docs: [
+              "Amount of reward tokens emitted per second between `start_time` and `end_time`",
+            ],
+            type: "u64",
+          },
+          {
+            name: "startTime",
+            docs: ["Timestamp in seconds of when the emission starts"],
+            type: "i64",
+          },
+          {
+            name: "endTime",
+            docs: [
+              "Timestamp in seconds of when the emission ends. 0 when the RewardPool is not streaming,\n    in which case any `reward_vault` balance increase is immediately credited to stakers.",
+            ],
+            type: "i64",
+          },
+          {
+            name: "lastUpdateTime",
+            docs: [
+              "Timestamp in seconds of the last time emissions were credited",
+            ],
+            type: "i64",
+          },
+          {
+            name: "undistributedAmount",
+            docs: [
+              "Amount of tokens in the `reward_vault` that have not been emitted yet. Tokens left when\n    the emission ends are carried into the next schedule.",
+            ],
+            type: "u64",
+          },
+          {
+            name: "tokenProgram",
+            docs: [
+              "Token program of the `reward_vault`. Default for SPL Token, RewardPools added before\n    Token-2022 support leave this unset.",
+            ],
             type: "publicKey",
           },
           {
-            name: "rewardPools",
+            name: "reserved0",
+            type: {
+              array: ["u8", 32],
+            },
+          },
+          {
+            name: "reserved1",
+            type: {
+              array: ["u8", 24],
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "WeightCurvePoint",
+      docs: [
+        "A lockup duration and the weight received for it. In terms of seconds and",
+        "1 / SCALE_FACTOR_BASE.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "duration",
+            type: "u64",
+          },
+          {
+            name: "weight",
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "WeightCurve",
+      docs: [
+        "Function mapping a lockup duration to a stake weight, chosen when initializing a StakePool.",
+        "All weights must be within `base_weight` and `max_weight`.",
+      ],
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Linear",
+          },
+          {
+            name: "Step",
+            fields: [
+              {
+                name: "tiers",
+                type: {
+                  vec: {
+                    defined: "WeightCurvePoint",
+                  },
+                },
+              },
+            ],
+          },
+          {
+            name: "Power",
+            fields: [
+              {
+                name: "exponent",
+                type: "u8",
+              },
+            ],
+          },
+          {
+            name: "Piecewise",
+            fields: [
+              {
+                name: "points",
+                type: {
+                  vec: {
+                    defined: "WeightCurvePoint",
+                  },
+                },
+              },
+            ],
+          },
+          {
+            name: "Exponential",
+            fields: [
+              {
+                name: "doublings",
+                type: "u8",
+              },
+            ],
+          },
+        ],
+      },
+    },
+    {
+      name: "WeightCurveData",
+      docs: [
+        "Zero copy representation of a [WeightCurve]. A zeroed value is `Linear`, so StakePools",
+        "created before weight curves existed are unaffected.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "kind",
+            docs: ["One of the `WEIGHT_CURVE_*` values"],
+            type: "u8",
+          },
+          {
+            name: "exponent",
+            docs: ["Exponent of a `Power` curve"],
+            type: "u8",
+          },
+          {
+            name: "numPoints",
             docs: [
-              "Array of RewardPools that apply to the stake pool.",
-              "Unused entries are Pubkey default. In arbitrary order, and may have gaps.",
+              "Number of `points` in use by a `Step` or `Piecewise` curve",
             ],
+            type: "u8",
+          },
+          {
+            name: "doublings",
+            docs: ["Doublings of an `Exponential` curve"],
+            type: "u8",
+          },
+          {
+            name: "padding0",
+            type: {
+              array: ["u8", 4],
+            },
+          },
+          {
+            name: "points",
             type: {
               array: [
                 {
-                  defined: "RewardPool",
+                  defined: "WeightCurvePoint",
                 },
-                10,
+                5,
               ],
             },
           },
           {
-            name: "baseWeight",
+            name: "reserved0",
+            type: {
+              array: ["u8", 8],
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "VoterWeightAction",
+      docs: [
+        "Governance action a [VoterWeightRecord] may be restricted to. Defined by the SPL Governance",
+        "addin api.",
+      ],
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "CastVote",
+          },
+          {
+            name: "CommentProposal",
+          },
+          {
+            name: "CreateGovernance",
+          },
+          {
+            name: "CreateProposal",
+          },
+          {
+            name: "SignOffProposal",
+          },
+        ],
+      },
+    },
+    {
+      name: "VoterWeightRecord",
+      docs: [
+        "Voting weight of a `governing_token_owner`, in the layout of the SPL Governance addin api.",
+        "The weight is the sum of the owner's StakeDepositReceipts effective stake in `stake_mint`",
+        "tokens, for Realms using the StakePool's `stake_mint` as their governing token.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "realm",
+            docs: ["The Realm the VoterWeightRecord belongs to"],
+            type: "publicKey",
+          },
+          {
+            name: "governingTokenMint",
             docs: [
-              "The minimum weight received for staking. In terms of 1 / SCALE_FACTOR_BASE.",
-              "Examples:",
-              "* `min_weight = 1 x SCALE_FACTOR_BASE` = minmum of 1x multiplier for > min_duration staking",
-              "* `min_weight = 2 x SCALE_FACTOR_BASE` = minmum of 2x multiplier for > min_duration staking",
+              "Governing Token Mint of the Realm, the `stake_mint` of the StakePool",
             ],
-            type: "u64",
+            type: "publicKey",
           },
           {
-            name: "maxWeight",
+            name: "governingTokenOwner",
             docs: [
-              "Maximum weight for staking lockup (i.e. weight multiplier when locked",
-              "up for max duration). In terms of 1 / SCALE_FACTOR_BASE. Examples:",
-              "* A `max_weight = 1 x SCALE_FACTOR_BASE` = 1x multiplier for max staking duration",
-              "* A `max_weight = 2 x SCALE_FACTOR_BASE` = 2x multiplier for max staking duration",
+              "Owner of the StakeDepositReceipts the weight is derived from",
             ],
+            type: "publicKey",
+          },
+          {
+            name: "voterWeight",
+            docs: ["Voter's weight at `voter_weight_expiry`"],
             type: "u64",
           },
           {
-            name: "minDuration",
+            name: "voterWeightExpiry",
             docs: [
-              "Minimum duration for lockup. At this point, the staker would receive the base weight. In seconds.",
+              "Slot the weight is valid for. SPL Governance only accepts the weight in this slot, so it",
+              "must be updated in the same transaction as the governance instruction.",
             ],
-            type: "u64",
+            type: {
+              option: "u64",
+            },
           },
           {
-            name: "maxDuration",
+            name: "weightAction",
             docs: [
-              "Maximum duration for lockup. At this point, the staker would receive the max weight. In seconds.",
+              "Governance action the weight is valid for, any action when None",
             ],
-            type: "u64",
+            type: {
+              option: {
+                defined: "VoterWeightAction",
+              },
+            },
           },
           {
-            name: "nonce",
-            docs: ["Nonce to derive multiple stake pools from same mint"],
-            type: "u8",
+            name: "weightActionTarget",
+            docs: ["Target of `weight_action`, any target when None"],
+            type: {
+              option: "publicKey",
+            },
+          },
+          {
+            name: "reserved",
+            docs: ["Reserved by the addin api"],
+            type: {
+              array: ["u8", 8],
+            },
           },
+        ],
+      },
+    },
+    {
+      name: "MaxVoterWeightRecord",
+      docs: [
+        "Maximum voting weight of a Realm's governing token, in the layout of the SPL Governance",
+        "addin api. The weight is the StakePool's `total_weighted_stake` in `stake_mint` tokens.",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
           {
-            name: "bumpSeed",
-            docs: ["Bump seed for stake_mint"],
-            type: "u8",
+            name: "realm",
+            docs: ["The Realm the MaxVoterWeightRecord belongs to"],
+            type: "publicKey",
           },
           {
-            name: "flags",
+            name: "governingTokenMint",
             docs: [
-              "Each bit constrols a setting. Add settings together as needed. Settings supported:",
-              "* `ESCAPE_HATCH_ENABLED` - 1, allows withdraws regardless of lockup time",
-              "* `PLACEHOLDER_A` - 2",
-              "* `DISABLE_DEPOSITS` - 4, causes the deposit ix to always fail",
-              "* `DEPOSIT_IGNORES_LP` - 8, the deposit ix will not mint any lp token",
-              "* `WITHDRAW_IGNORES_LP` - 16, the withdraw ix will not burn any lp token",
-              "",
-              "Do not access directly, use functions such as `escape_hatch_enabled`",
+              "Governing Token Mint of the Realm, the `stake_mint` of the StakePool",
             ],
-            type: "u8",
+            type: "publicKey",
           },
           {
-            name: "padding0",
+            name: "maxVoterWeight",
+            docs: ["Max voter weight at `max_voter_weight_expiry`"],
+            type: "u64",
+          },
+          {
+            name: "maxVoterWeightExpiry",
+            docs: ["Slot the max weight is valid for, see [VoterWeightRecord]"],
             type: {
-              array: ["u8", 5],
+              option: "u64",
             },
           },
           {
-            name: "reserved0",
+            name: "reserved",
+            docs: ["Reserved by the addin api"],
             type: {
-              array: ["u8", 256],
+              array: ["u8", 8],
             },
           },
         ],
       },
     },
+  ],
+  events: [
+    {
+      name: "RewardPoolFunded",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "rewardPoolIndex",
+          type: "u8",
+          index: false,
+        },
+        {
+          name: "funder",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "amount",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "duration",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "DepositEvent",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "owner",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "stakeDepositReceipt",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "amount",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "lockupDuration",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "effectiveStake",
+          type: "u128",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "WithdrawEvent",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "owner",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "stakeDepositReceipt",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "amount",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "penalty",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "effectiveStake",
+          type: "u128",
+          index: false,
+        },
+        {
+          name: "closed",
+          type: "bool",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "ClaimEvent",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "owner",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "stakeDepositReceipt",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "amounts",
+          type: {
+            array: ["u64", 10],
+          },
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "CompoundEvent",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "owner",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "stakeDepositReceipt",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "rewardPoolIndex",
+          type: "u8",
+          index: false,
+        },
+        {
+          name: "amount",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "effectiveStake",
+          type: "u128",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "RewardPoolAdded",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "rewardPoolIndex",
+          type: "u8",
+          index: false,
+        },
+        {
+          name: "rewardMint",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "rewardVault",
+          type: "publicKey",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "FlagsChanged",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "authority",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "oldFlags",
+          type: "u8",
+          index: false,
+        },
+        {
+          name: "newFlags",
+          type: "u8",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "AuthorityTransferred",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "oldAuthority",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "newAuthority",
+          type: "publicKey",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "RewardsAccrued",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "rewardVault",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "rewardPoolIndex",
+          type: "u8",
+          index: false,
+        },
+        {
+          name: "amount",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "rewardsPerEffectiveStake",
+          type: "u128",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "ExtendLockupEvent",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "owner",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "stakeDepositReceipt",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "lockupDuration",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "effectiveStake",
+          type: "u128",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "MergeReceiptsEvent",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "owner",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "stakeDepositReceipt",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "sourceStakeDepositReceipt",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "effectiveStake",
+          type: "u128",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "SplitReceiptEvent",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "owner",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "stakeDepositReceipt",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "newStakeDepositReceipt",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "amount",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "effectiveStake",
+          type: "u128",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "TransferReceiptEvent",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "oldOwner",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "newOwner",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "stakeDepositReceipt",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "newStakeDepositReceipt",
+          type: "publicKey",
+          index: false,
+        },
+      ],
+    },
     {
-      name: "stakeDepositReceipt",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "owner",
-            docs: ["Pubkey that owns the staked assets"],
-            type: "publicKey",
-          },
-          {
-            name: "payer",
-            docs: ["Pubkey that paid for the deposit"],
-            type: "publicKey",
-          },
-          {
-            name: "stakePool",
-            docs: ["StakePool the deposit is for"],
-            type: "publicKey",
-          },
-          {
-            name: "lockupDuration",
-            docs: ["Duration of the lockup period in seconds"],
-            type: "u64",
-          },
-          {
-            name: "depositTimestamp",
-            docs: ["Timestamp in seconds of when the stake lockup began"],
-            type: "i64",
-          },
-          {
-            name: "depositAmount",
-            docs: ["Amount of SPL token deposited"],
-            type: "u64",
-          },
-          {
-            name: "effectiveStake",
-            docs: ["Amount of stake weighted by lockup duration."],
-            type: "u128",
-          },
-          {
-            name: "claimedAmounts",
-            docs: [
-              "The amount per reward that has been claimed or perceived to be claimed. Indexes align with",
-              "the StakedPool reward_pools property.",
-            ],
-            type: {
-              array: ["u128", 10],
-            },
-          },
-        ],
-      },
+      name: "RewardPoolRetired",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "rewardPoolIndex",
+          type: "u8",
+          index: false,
+        },
+        {
+          name: "retiredAt",
+          type: "i64",
+          index: false,
+        },
+      ],
     },
-  ],
-  types: [
     {
-      name: "RewardPool",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "rewardVault",
-            docs: ["Token Account to store the reward SPL Token"],
-            type: "publicKey",
-          },
-          {
-            name: "rewardsPerEffectiveStake",
-            docs: [
-              "Ever increasing accumulator of the amount of rewards per effective stake.\n    Said another way, if a user deposited before any rewards were added to the\n    `vault`, then this would be the token amount per effective stake they could\n    claim.",
-            ],
-            type: "u128",
-          },
-          {
-            name: "lastAmount",
-            docs: ["latest amount of tokens in the vault"],
-            type: "u64",
-          },
-          {
-            name: "padding0",
-            type: {
-              array: ["u8", 8],
-            },
+      name: "RewardPoolSwept",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "rewardPoolIndex",
+          type: "u8",
+          index: false,
+        },
+        {
+          name: "destination",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "amount",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "RewardPoolEmissionSet",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "rewardPoolIndex",
+          type: "u8",
+          index: false,
+        },
+        {
+          name: "rewardsPerSecond",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "startTime",
+          type: "i64",
+          index: false,
+        },
+        {
+          name: "endTime",
+          type: "i64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "PoolConfigUpdated",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "baseWeight",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "maxWeight",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "minDuration",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "maxDuration",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "RoleSet",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "role",
+          type: {
+            defined: "StakePoolRole",
           },
-        ],
-      },
+          index: false,
+        },
+        {
+          name: "key",
+          type: "publicKey",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "AuthorityTransferProposed",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "authority",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "pendingAuthority",
+          type: "publicKey",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "AuthorityTransferCancelled",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "authority",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "pendingAuthority",
+          type: "publicKey",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "EarlyWithdrawPenaltySet",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "penaltyBps",
+          type: "u16",
+          index: false,
+        },
+        {
+          name: "treasury",
+          type: "publicKey",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "TokenMetaUpdated",
+      fields: [
+        {
+          name: "stakePool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "name",
+          type: "string",
+          index: false,
+        },
+        {
+          name: "symbol",
+          type: "string",
+          index: false,
+        },
+        {
+          name: "uri",
+          type: "string",
+          index: false,
+        },
+      ],
     },
   ],
   errors: [
@@ -818,6 +3958,141 @@ export const _SplTokenStakingIDL = {
       name: "DepositsDisabled",
       msg: "Deposits disabled by administrator",
     },
+    {
+      code: 6014,
+      name: "InvalidWithdrawAmount",
+      msg: "Amount must be greater than 0 and less than the deposited amount",
+    },
+    {
+      code: 6015,
+      name: "InvalidLockupExtension",
+      msg: "Lockup duration and effective stake can only be increased",
+    },
+    {
+      code: 6016,
+      name: "DuplicateStakeDepositReceipt",
+      msg: "StakeDepositReceipts must be distinct",
+    },
+    {
+      code: 6017,
+      name: "InvalidSplitAmount",
+      msg: "Split amount must be greater than 0 and less than the deposited amount",
+    },
+    {
+      code: 6018,
+      name: "RewardPoolIndexEmpty",
+      msg: "RewardPool index is not occupied",
+    },
+    {
+      code: 6019,
+      name: "RewardPoolRetired",
+      msg: "RewardPool is retired",
+    },
+    {
+      code: 6020,
+      name: "RewardPoolNotSweepable",
+      msg: "RewardPool is not retired or the claim window has not ended",
+    },
+    {
+      code: 6021,
+      name: "StakePoolNotExtended",
+      msg: "StakePool must be extended to store RewardPool state",
+    },
+    {
+      code: 6022,
+      name: "InvalidEmissionSchedule",
+      msg: "Emission must end in the future and after it starts",
+    },
+    {
+      code: 6023,
+      name: "InvalidEarlyWithdrawPenalty",
+      msg: "Early withdraw penalty must not exceed 10000 bps",
+    },
+    {
+      code: 6024,
+      name: "InvalidTreasury",
+      msg: "Treasury is invalid",
+    },
+    {
+      code: 6025,
+      name: "InvalidWeightCurve",
+      msg: "Weight curve points must be ordered and within the StakePool's durations and weights",
+    },
+    {
+      code: 6026,
+      name: "InvalidMaxWeightDigitShift",
+      msg: "Max weight change would alter the stake mint decimals",
+    },
+    {
+      code: 6027,
+      name: "InvalidPendingAuthority",
+      msg: "Signer is not the pending authority",
+    },
+    {
+      code: 6028,
+      name: "InvalidFlags",
+      msg: "Flags contain unknown bits",
+    },
+    {
+      code: 6029,
+      name: "InvalidMint",
+      msg: "Mint does not match the StakePool mint",
+    },
+    {
+      code: 6030,
+      name: "MissingToken2022Program",
+      msg: "Token-2022 program is required for Token-2022 RewardPools and stake mints",
+    },
+    {
+      code: 6031,
+      name: "InvalidRewardPoolMask",
+      msg: "RewardPool mask must only select occupied RewardPools",
+    },
+    {
+      code: 6032,
+      name: "InvalidClaimDelegate",
+      msg: "Signer is not the claim delegate of the owner",
+    },
+    {
+      code: 6033,
+      name: "InvalidOwnerRewardAccount",
+      msg: "Reward token account must be the owner's associated token account",
+    },
+    {
+      code: 6034,
+      name: "NoRewardCheckpoints",
+      msg: "RewardPool has no effective stake to distribute rewards to",
+    },
+    {
+      code: 6035,
+      name: "RewardCheckpointNotClaimed",
+      msg: "RewardCheckpoint has unclaimed rewards",
+    },
+    {
+      code: 6036,
+      name: "StakeDepositReceiptHasRewardCheckpoints",
+      msg: "StakeDepositReceipt must leave its ExternalRewardPools first",
+    },
+    {
+      code: 6037,
+      name: "RewardPoolVaultBalanceDecreased",
+      msg: "RewardPool vault balance is below its last recorded amount",
+    },
+    {
+      code: 6038,
+      name: "StakeDepositReceiptUnlocked",
+      msg: "StakeDepositReceipt lockup has ended, extend the lockup to compound",
+    },
+    {
+      code: 6039,
+      name: "InvalidRewardCheckpoints",
+      msg: "Every RewardCheckpoint of the StakeDepositReceipt must be passed once",
+    },
+    {
+      code: 6040,
+      name: "StakeDepositReceiptVoting",
+      msg: "StakeDepositReceipt is locked until the vote it was counted for has ended",
+    },
   ],
 } as const;
 
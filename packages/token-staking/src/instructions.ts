@@ -1,8 +1,10 @@
 import * as anchor from "@coral-xyz/anchor";
 import { SPL_TOKEN_PROGRAM_ID } from "@coral-xyz/spl-token";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
 import { SCALE_FACTOR_BASE } from "./constants";
 import { SplTokenStaking } from "./idl";
 import { SplTokenStakingV0 } from "./idl_v0";
+import { WeightCurve } from "./types";
 
 /**
  * Initialize the StakePool and set configuration parameters.
@@ -14,6 +16,9 @@ import { SplTokenStakingV0 } from "./idl_v0";
  * @param minDuration
  * @param maxDuration
  * @param authority - defaults to `program.provider.publicKey`
+ * @param weightCurve - defaults to linear
+ * @param mintTokenProgram - token program of `mint`, defaults to SPL Token
+ * @param nonTransferableStakeMint - create the `stakeMint` as a non-transferable Token-2022 mint
  */
 export const initStakePool = async (
   program: anchor.Program<SplTokenStaking | SplTokenStakingV0>,
@@ -22,7 +27,10 @@ export const initStakePool = async (
   maxWeight = new anchor.BN(SCALE_FACTOR_BASE.toString()),
   minDuration = new anchor.BN(0),
   maxDuration = new anchor.BN("18446744073709551615"),
-  authority?: anchor.Address
+  authority?: anchor.Address,
+  weightCurve: WeightCurve = { linear: {} },
+  mintTokenProgram: anchor.web3.PublicKey = SPL_TOKEN_PROGRAM_ID,
+  nonTransferableStakeMint = false
 ) => {
   const _authority = authority
     ? new anchor.web3.PublicKey(authority)
@@ -45,7 +53,14 @@ export const initStakePool = async (
     program.programId
   );
   await program.methods
-    .initializeStakePool(nonce, maxWeight, minDuration, maxDuration)
+    .initializeStakePool(
+      nonce,
+      maxWeight,
+      minDuration,
+      maxDuration,
+      weightCurve,
+      nonTransferableStakeMint
+    )
     .accounts({
       payer: program.provider.publicKey,
       authority: _authority,
@@ -54,6 +69,8 @@ export const initStakePool = async (
       mint,
       vault: vaultKey,
       tokenProgram: SPL_TOKEN_PROGRAM_ID,
+      token2022Program: nonTransferableStakeMint ? TOKEN_2022_PROGRAM_ID : null,
+      mintTokenProgram,
       rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       systemProgram: anchor.web3.SystemProgram.programId,
     })
@@ -68,6 +85,7 @@ export const initStakePool = async (
  * @param rewardMint
  * @param rewardPoolIndex
  * @param authority
+ * @param rewardTokenProgram - token program of `rewardMint`, defaults to SPL Token
  * @returns
  */
 export const addRewardPool = async (
@@ -76,7 +94,8 @@ export const addRewardPool = async (
   stakePoolMint: anchor.Address,
   rewardMint: anchor.web3.PublicKey,
   rewardPoolIndex = 0,
-  authority?: anchor.Address
+  authority?: anchor.Address,
+  rewardTokenProgram: anchor.web3.PublicKey = SPL_TOKEN_PROGRAM_ID
 ) => {
   const _authority = authority
     ? new anchor.web3.PublicKey(authority)
@@ -106,7 +125,7 @@ export const addRewardPool = async (
       rewardMint,
       stakePool: stakePoolKey,
       rewardVault: rewardVaultKey,
-      tokenProgram: SPL_TOKEN_PROGRAM_ID,
+      tokenProgram: rewardTokenProgram,
       rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       systemProgram: anchor.web3.SystemProgram.programId,
     })
@@ -119,12 +138,14 @@ export const addRewardPool = async (
  * @param payer
  * @param owner
  * @param stakePoolKey
+ * @param mint - underlying mint of the StakePool
  * @param from
  * @param stakeMintAccount
  * @param amount
  * @param duration
  * @param receiptNonce
  * @param rewardVaults
+ * @param mintTokenProgram - token program of `mint`, defaults to SPL Token
  * @returns
  */
 export const createStakeBuilder = (
@@ -132,12 +153,14 @@ export const createStakeBuilder = (
   payer: anchor.web3.PublicKey,
   owner: anchor.web3.PublicKey,
   stakePoolKey: anchor.Address,
+  mint: anchor.Address,
   from: anchor.Address,
   stakeMintAccount: anchor.Address,
   amount: anchor.BN,
   duration: anchor.BN,
   receiptNonce: number,
-  rewardVaults: anchor.web3.PublicKey[] = []
+  rewardVaults: anchor.web3.PublicKey[] = [],
+  mintTokenProgram: anchor.web3.PublicKey = SPL_TOKEN_PROGRAM_ID
 ) => {
   const _stakePoolKey =
     typeof stakePoolKey === "string"
@@ -169,10 +192,12 @@ export const createStakeBuilder = (
       from,
       stakePool: stakePoolKey,
       vault: vaultKey,
+      mint,
       stakeMint,
       destination: stakeMintAccount,
       stakeDepositReceipt: stakeReceiptKey,
       tokenProgram: SPL_TOKEN_PROGRAM_ID,
+      mintTokenProgram,
       rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       systemProgram: anchor.web3.SystemProgram.programId,
     })
@@ -190,12 +215,14 @@ export const createStakeBuilder = (
  * @param payer
  * @param owner
  * @param stakePoolKey
+ * @param mint - underlying mint of the StakePool
  * @param from
  * @param stakeMintAccount
  * @param amount
  * @param duration
  * @param receiptNonce
  * @param rewardVaults
+ * @param mintTokenProgram - token program of `mint`, defaults to SPL Token
  * @returns
  */
 export const createStakeInstruction = async (
@@ -203,24 +230,28 @@ export const createStakeInstruction = async (
   payer: anchor.web3.PublicKey,
   owner: anchor.web3.PublicKey,
   stakePoolkey: anchor.Address,
+  mint: anchor.Address,
   from: anchor.Address,
   stakeMintAccount: anchor.Address,
   amount: anchor.BN,
   duration: anchor.BN,
   receiptNonce: number,
-  rewardVaults: anchor.web3.PublicKey[] = []
+  rewardVaults: anchor.web3.PublicKey[] = [],
+  mintTokenProgram: anchor.web3.PublicKey = SPL_TOKEN_PROGRAM_ID
 ) => {
   return createStakeBuilder(
     program,
     payer,
     owner,
     stakePoolkey,
+    mint,
     from,
     stakeMintAccount,
     amount,
     duration,
     receiptNonce,
-    rewardVaults
+    rewardVaults,
+    mintTokenProgram
   ).instruction();
 };
 
@@ -230,6 +261,7 @@ export const createStakeInstruction = async (
  * @param payer
  * @param owner
  * @param stakePoolKey
+ * @param mint - underlying mint of the StakePool
  * @param from
  * @param stakeMintAccount
  * @param amount
@@ -237,12 +269,14 @@ export const createStakeInstruction = async (
  * @param receiptNonce
  * @param rewardVaults
  * @param options
+ * @param mintTokenProgram - token program of `mint`, defaults to SPL Token
  */
 export const deposit = async (
   program: anchor.Program<SplTokenStaking | SplTokenStakingV0>,
   payer: anchor.web3.PublicKey,
   owner: anchor.web3.PublicKey,
   stakePoolKey: anchor.Address,
+  mint: anchor.Address,
   from: anchor.Address,
   stakeMintAccount: anchor.Address,
   amount: anchor.BN,
@@ -255,19 +289,22 @@ export const deposit = async (
   } = {
     preInstructions: [],
     postInstructions: [],
-  }
+  },
+  mintTokenProgram: anchor.web3.PublicKey = SPL_TOKEN_PROGRAM_ID
 ) => {
   return createStakeBuilder(
     program,
     payer,
     owner,
     stakePoolKey,
+    mint,
     from,
     stakeMintAccount,
     amount,
     duration,
     receiptNonce,
-    rewardVaults
+    rewardVaults,
+    mintTokenProgram
   )
     .preInstructions(options.preInstructions)
     .postInstructions(options.postInstructions)
@@ -1,4 +1,4 @@
-import { IdlAccounts, web3 } from "@coral-xyz/anchor";
+import { BN, IdlAccounts, web3 } from "@coral-xyz/anchor";
 import { SplTokenStaking } from "./idl";
 
 export type StakePool = IdlAccounts<SplTokenStaking>["stakePool"];
@@ -7,3 +7,11 @@ export type StakeDepositReceipt =
 export type StakeDepositReceiptData = StakeDepositReceipt & {
   address: web3.PublicKey;
 };
+
+export type WeightCurvePoint = { duration: BN; weight: BN };
+export type WeightCurve =
+  | { linear: {} }
+  | { step: { tiers: WeightCurvePoint[] } }
+  | { power: { exponent: number } }
+  | { piecewise: { points: WeightCurvePoint[] } }
+  | { exponential: { doublings: number } };
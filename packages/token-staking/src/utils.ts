@@ -3,9 +3,12 @@ import { getAssociatedTokenAddressSync } from "@solana/spl-token";
 import _chunk from "lodash.chunk";
 import bs58 from "bs58";
 import {
+  MAX_REWARD_POOLS,
+  REWARD_POOL_EXTENSION_LEN,
   SCALE_FACTOR_BASE,
   SCALE_FACTOR_BASE_BN,
   STAKE_DEPOSIT_RECEIPT_DISCRIMINATOR,
+  STAKE_POOL_LEN,
   U64_MAX,
 } from "./constants";
 import { SplTokenStaking } from "./idl";
@@ -140,6 +143,35 @@ export const getNextUnusedStakeReceiptNonce = async (
   throw new Error("No more nonces available");
 };
 
+/**
+ * Fetch the RewardPoolExtension of each RewardPool, stored after the StakePool's account data.
+ * They are all zeroed when the StakePool has not been extended yet.
+ * @param program
+ * @param stakePoolKey
+ * @returns
+ */
+export const fetchRewardPoolExtensions = async (
+  program: anchor.Program<SplTokenStaking>,
+  stakePoolKey: anchor.web3.PublicKey
+) => {
+  const accountInfo = await program.provider.connection.getAccountInfo(
+    stakePoolKey
+  );
+  const start = 8 + STAKE_POOL_LEN;
+  const end = start + MAX_REWARD_POOLS * REWARD_POOL_EXTENSION_LEN;
+  const data =
+    accountInfo.data.length >= end ? accountInfo.data : Buffer.alloc(end);
+  return Array.from({ length: MAX_REWARD_POOLS }, (_, index) =>
+    program.coder.types.decode(
+      "RewardPoolExtension",
+      data.subarray(
+        start + index * REWARD_POOL_EXTENSION_LEN,
+        start + (index + 1) * REWARD_POOL_EXTENSION_LEN
+      )
+    )
+  );
+};
+
 /**
  * Filter StakePool's `rewardPools` for those that are initialized.
  * @param rewardPools
@@ -37,4 +37,58 @@ pub enum ErrorCode {
   
   #[msg("Deposits disabled by administrator")]
   DepositsDisabled, // TODO
+  #[msg("Amount must be greater than 0 and less than the deposited amount")]
+  InvalidWithdrawAmount, // 6014
+  #[msg("Lockup duration and effective stake can only be increased")]
+  InvalidLockupExtension, // 6015
+  #[msg("StakeDepositReceipts must be distinct")]
+  DuplicateStakeDepositReceipt, // 6016
+  #[msg("Split amount must be greater than 0 and less than the deposited amount")]
+  InvalidSplitAmount, // 6017
+  #[msg("RewardPool index is not occupied")]
+  RewardPoolIndexEmpty, // 6018
+  #[msg("RewardPool is retired")]
+  RewardPoolRetired, // 6019
+  #[msg("RewardPool is not retired or the claim window has not ended")]
+  RewardPoolNotSweepable, // 6020
+  #[msg("StakePool must be extended to store RewardPool state")]
+  StakePoolNotExtended, // 6021
+  #[msg("Emission must end in the future and after it starts")]
+  InvalidEmissionSchedule, // 6022
+  #[msg("Early withdraw penalty must not exceed 10000 bps")]
+  InvalidEarlyWithdrawPenalty, // 6023
+  #[msg("Treasury is invalid")]
+  InvalidTreasury, // 6024
+  #[msg("Weight curve points must be ordered and within the StakePool's durations and weights")]
+  InvalidWeightCurve, // 6025
+  #[msg("Max weight change would alter the stake mint decimals")]
+  InvalidMaxWeightDigitShift, // 6026
+  #[msg("Signer is not the pending authority")]
+  InvalidPendingAuthority, // 6027
+  #[msg("Flags contain unknown bits")]
+  InvalidFlags, // 6028
+  #[msg("Mint does not match the StakePool mint")]
+  InvalidMint, // 6029
+  #[msg("Token-2022 program is required for Token-2022 RewardPools and stake mints")]
+  MissingToken2022Program, // 6030
+  #[msg("RewardPool mask must only select occupied RewardPools")]
+  InvalidRewardPoolMask, // 6031
+  #[msg("Signer is not the claim delegate of the owner")]
+  InvalidClaimDelegate, // 6032
+  #[msg("Reward token account must be the owner's associated token account")]
+  InvalidOwnerRewardAccount, // 6033
+  #[msg("RewardPool has no effective stake to distribute rewards to")]
+  NoRewardCheckpoints, // 6034
+  #[msg("RewardCheckpoint has unclaimed rewards")]
+  RewardCheckpointNotClaimed, // 6035
+  #[msg("StakeDepositReceipt must leave its ExternalRewardPools first")]
+  StakeDepositReceiptHasRewardCheckpoints, // 6036
+  #[msg("RewardPool vault balance is below its last recorded amount")]
+  RewardPoolVaultBalanceDecreased, // 6037
+  #[msg("StakeDepositReceipt lockup has ended, extend the lockup to compound")]
+  StakeDepositReceiptUnlocked, // 6038
+  #[msg("Every RewardCheckpoint of the StakeDepositReceipt must be passed once")]
+  InvalidRewardCheckpoints, // 6039
+  #[msg("StakeDepositReceipt is locked until the vote it was counted for has ended")]
+  StakeDepositReceiptVoting, // 6040
 }
\ No newline at end of file
@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{StakePoolRole, MAX_REWARD_POOLS};
+
+#[event]
+pub struct RewardPoolFunded {
+    pub stake_pool: Pubkey,
+    pub reward_pool_index: u8,
+    /// Wallet that transferred the rewards
+    pub funder: Pubkey,
+    /// Amount received by the `reward_vault`, after any transfer fee
+    pub amount: u64,
+    /// Duration in seconds the RewardPool's undistributed rewards are streamed over, 0 if the
+    /// schedule was left unchanged
+    pub duration: u64,
+}
+
+#[event]
+pub struct DepositEvent {
+    pub stake_pool: Pubkey,
+    pub owner: Pubkey,
+    pub stake_deposit_receipt: Pubkey,
+    /// Amount of the StakePool's mint deposited
+    pub amount: u64,
+    /// Lockup duration in seconds, after clamping to the StakePool's `max_duration`
+    pub lockup_duration: u64,
+    pub effective_stake: u128,
+}
+
+#[event]
+pub struct WithdrawEvent {
+    pub stake_pool: Pubkey,
+    pub owner: Pubkey,
+    pub stake_deposit_receipt: Pubkey,
+    /// Amount of the StakePool's mint removed from the StakeDepositReceipt, including `penalty`
+    pub amount: u64,
+    /// Early withdraw penalty transferred to the StakePool's treasury
+    pub penalty: u64,
+    /// Effective stake removed from the StakeDepositReceipt
+    pub effective_stake: u128,
+    /// True when the StakeDepositReceipt was closed
+    pub closed: bool,
+}
+
+#[event]
+pub struct ClaimEvent {
+    pub stake_pool: Pubkey,
+    pub owner: Pubkey,
+    pub stake_deposit_receipt: Pubkey,
+    /// Amount claimed from each RewardPool, indexes align with the StakePool's `reward_pools`
+    pub amounts: [u64; MAX_REWARD_POOLS],
+}
+
+#[event]
+pub struct CompoundEvent {
+    pub stake_pool: Pubkey,
+    pub owner: Pubkey,
+    pub stake_deposit_receipt: Pubkey,
+    pub reward_pool_index: u8,
+    /// Amount of rewards added to the StakeDepositReceipt's deposit, after any transfer fee
+    pub amount: u64,
+    /// Effective stake added to the StakeDepositReceipt
+    pub effective_stake: u128,
+}
+
+#[event]
+pub struct RewardPoolAdded {
+    pub stake_pool: Pubkey,
+    pub reward_pool_index: u8,
+    pub reward_mint: Pubkey,
+    pub reward_vault: Pubkey,
+}
+
+#[event]
+pub struct FlagsChanged {
+    pub stake_pool: Pubkey,
+    /// Signer that changed the flags, either the authority or the pauser
+    pub authority: Pubkey,
+    pub old_flags: u8,
+    pub new_flags: u8,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub stake_pool: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct RewardsAccrued {
+    pub stake_pool: Pubkey,
+    /// Vault of the RewardPool, unique to the StakePool and reward mint
+    pub reward_vault: Pubkey,
+    pub reward_pool_index: u8,
+    /// Amount of rewards credited to current stakers
+    pub amount: u64,
+    /// `rewards_per_effective_stake` of the RewardPool after the accrual
+    pub rewards_per_effective_stake: u128,
+}
+
+#[event]
+pub struct ExtendLockupEvent {
+    pub stake_pool: Pubkey,
+    pub owner: Pubkey,
+    pub stake_deposit_receipt: Pubkey,
+    /// New lockup duration in seconds, starting at the time of the extension
+    pub lockup_duration: u64,
+    /// Effective stake of the StakeDepositReceipt after the extension
+    pub effective_stake: u128,
+}
+
+#[event]
+pub struct MergeReceiptsEvent {
+    pub stake_pool: Pubkey,
+    pub owner: Pubkey,
+    /// StakeDepositReceipt that absorbed the deposit of `source_stake_deposit_receipt`
+    pub stake_deposit_receipt: Pubkey,
+    /// StakeDepositReceipt that was closed
+    pub source_stake_deposit_receipt: Pubkey,
+    /// Effective stake of `stake_deposit_receipt` after the merge
+    pub effective_stake: u128,
+}
+
+#[event]
+pub struct SplitReceiptEvent {
+    pub stake_pool: Pubkey,
+    pub owner: Pubkey,
+    pub stake_deposit_receipt: Pubkey,
+    pub new_stake_deposit_receipt: Pubkey,
+    /// Amount of the deposit moved to `new_stake_deposit_receipt`
+    pub amount: u64,
+    /// Effective stake moved to `new_stake_deposit_receipt`
+    pub effective_stake: u128,
+}
+
+#[event]
+pub struct TransferReceiptEvent {
+    pub stake_pool: Pubkey,
+    pub old_owner: Pubkey,
+    pub new_owner: Pubkey,
+    /// StakeDepositReceipt of `old_owner` that was closed
+    pub stake_deposit_receipt: Pubkey,
+    pub new_stake_deposit_receipt: Pubkey,
+}
+
+#[event]
+pub struct RewardPoolRetired {
+    pub stake_pool: Pubkey,
+    pub reward_pool_index: u8,
+    pub retired_at: i64,
+}
+
+#[event]
+pub struct RewardPoolSwept {
+    pub stake_pool: Pubkey,
+    pub reward_pool_index: u8,
+    /// Token account that received the unclaimed rewards
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RewardPoolEmissionSet {
+    pub stake_pool: Pubkey,
+    pub reward_pool_index: u8,
+    pub rewards_per_second: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct PoolConfigUpdated {
+    pub stake_pool: Pubkey,
+    pub base_weight: u64,
+    pub max_weight: u64,
+    pub min_duration: u64,
+    pub max_duration: u64,
+}
+
+#[event]
+pub struct RoleSet {
+    pub stake_pool: Pubkey,
+    pub role: StakePoolRole,
+    /// New holder of the role, default if the role was revoked
+    pub key: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub stake_pool: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub stake_pool: Pubkey,
+    pub authority: Pubkey,
+    /// Pending authority whose transfer was cancelled
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct EarlyWithdrawPenaltySet {
+    pub stake_pool: Pubkey,
+    pub penalty_bps: u16,
+    pub treasury: Pubkey,
+}
+
+#[event]
+pub struct TokenMetaUpdated {
+    pub stake_pool: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+  errors::ErrorCode,
+  events::AuthorityTransferred,
+  state::StakePool,
+};
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+  /// Proposed authority of the StakePool
+  pub pending_authority: Signer<'info>,
+
+  /// StakePool that will have it's authority updated
+  #[account(
+    mut,
+    has_one = pending_authority @ ErrorCode::InvalidPendingAuthority,
+  )]
+  pub stake_pool: AccountLoader<'info, StakePool>,
+}
+
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+  let mut stake_pool = ctx.accounts.stake_pool.load_mut()?;
+  let old_authority = stake_pool.authority;
+  stake_pool.authority = ctx.accounts.pending_authority.key();
+  stake_pool.pending_authority = Pubkey::default();
+
+  emit!(AuthorityTransferred {
+    stake_pool: ctx.accounts.stake_pool.key(),
+    old_authority,
+    new_authority: stake_pool.authority,
+  });
+  Ok(())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::ErrorCode;
+use crate::state::{ExternalRewardPool, StakePool, u128};
+
+#[derive(Accounts)]
+#[instruction(id: u32)]
+pub struct AddExternalRewardPool<'info> {
+  /// Payer of rent
+  #[account(mut)]
+  pub payer: Signer<'info>,
+
+  /// Authority or reward manager of the StakePool
+  pub authority: Signer<'info>,
+
+  /// SPL Token or Token-2022 Mint of the token that will be distributed as rewards
+  pub reward_mint: InterfaceAccount<'info, Mint>,
+
+  /// StakePool the ExternalRewardPool is added to
+  #[account(
+    constraint = stake_pool.load()?.is_reward_manager(&authority.key()) @ ErrorCode::InvalidAuthority,
+  )]
+  pub stake_pool: AccountLoader<'info, StakePool>,
+
+  #[account(
+    init,
+    seeds = [stake_pool.key().as_ref(), &id.to_le_bytes(), b"rewardPool"],
+    bump,
+    payer = payer,
+    space = 8 + ExternalRewardPool::LEN,
+  )]
+  pub reward_pool: Account<'info, ExternalRewardPool>,
+
+  /// An SPL token Account for holding rewards to be claimed
+  #[account(
+    init,
+    seeds = [reward_pool.key().as_ref(), b"rewardVault"],
+    bump,
+    payer = payer,
+    token::mint = reward_mint,
+    token::authority = stake_pool,
+    token::token_program = token_program,
+  )]
+  pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+  /// Token program of the `reward_mint`, either SPL Token or Token-2022
+  pub token_program: Interface<'info, TokenInterface>,
+  pub rent: Sysvar<'info, Rent>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddExternalRewardPool>, id: u32) -> Result<()> {
+  let reward_pool = &mut ctx.accounts.reward_pool;
+  reward_pool.stake_pool = ctx.accounts.stake_pool.key();
+  reward_pool.reward_mint = ctx.accounts.reward_mint.key();
+  reward_pool.reward_vault = ctx.accounts.reward_vault.key();
+  reward_pool.token_program = ctx.accounts.token_program.key();
+  reward_pool.total_effective_stake = u128::default();
+  reward_pool.rewards_per_effective_stake = u128::default();
+  reward_pool.rewards_per_second = 0;
+  reward_pool.start_time = 0;
+  reward_pool.end_time = 0;
+  reward_pool.last_update_time = 0;
+  reward_pool.undistributed_amount = 0;
+  reward_pool.retired_at = 0;
+  reward_pool.id = id;
+  Ok(())
+}
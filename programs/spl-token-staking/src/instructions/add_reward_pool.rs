@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::state::{RewardPool, StakePool};
+use crate::state::{RewardPool, StakePool, StakePoolLoader};
 use crate::errors::ErrorCode;
+use crate::events::RewardPoolAdded;
 
 #[derive(Accounts)]
 #[instruction(index: u8)]
@@ -11,16 +12,16 @@ pub struct AddRewardPool<'info> {
   #[account(mut)]
   pub payer: Signer<'info>,
 
-  /// Authority of the StakePool
+  /// Authority or reward manager of the StakePool
   pub authority: Signer<'info>,
 
-  /// SPL Token Mint of the token that will be distributed as rewards
-  pub reward_mint: Account<'info, Mint>,
+  /// SPL Token or Token-2022 Mint of the token that will be distributed as rewards
+  pub reward_mint: InterfaceAccount<'info, Mint>,
 
   /// StakePool where the RewardPool will be added
   #[account(
     mut, 
-    has_one = authority @ ErrorCode::InvalidAuthority,
+    constraint = stake_pool.load()?.is_reward_manager(&authority.key()) @ ErrorCode::InvalidAuthority,
     constraint = stake_pool.load()?.reward_pools[usize::from(index)].reward_vault == Pubkey::default() 
       @ ErrorCode::RewardPoolIndexOccupied,
   )]
@@ -34,18 +35,34 @@ pub struct AddRewardPool<'info> {
     payer = payer,
     token::mint = reward_mint,
     token::authority = stake_pool,
+    token::token_program = token_program,
   )]
-  pub reward_vault: Account<'info, TokenAccount>,
+  pub reward_vault: InterfaceAccount<'info, TokenAccount>,
 
-  pub token_program: Program<'info, Token>,
+  /// Token program of the `reward_mint`, either SPL Token or Token-2022
+  pub token_program: Interface<'info, TokenInterface>,
   pub rent: Sysvar<'info, Rent>,
   pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<AddRewardPool>, index: u8) -> Result<()> {
-  let mut stake_pool = ctx.accounts.stake_pool.load_mut()?;
-  let reward_pool = RewardPool::new(&ctx.accounts.reward_vault.key());
-  stake_pool.reward_pools[usize::from(index)] = reward_pool;
+  ctx.accounts.stake_pool.extend(
+    &ctx.accounts.payer.to_account_info(),
+    &ctx.accounts.system_program.to_account_info(),
+  )?;
+  let mut stake_pool = ctx.accounts.stake_pool.load_mut_with_extensions()?;
+  // continue from the last value of a previously retired RewardPool in this slot
+  let extension = stake_pool.reward_pool_extension_mut(usize::from(index))?;
+  extension.token_program = ctx.accounts.token_program.key();
+  let base_rewards_per_effective_stake = extension.base_rewards_per_effective_stake;
+  stake_pool.reward_pools[usize::from(index)] =
+    RewardPool::new(&ctx.accounts.reward_vault.key(), base_rewards_per_effective_stake);
 
+  emit!(RewardPoolAdded {
+    stake_pool: ctx.accounts.stake_pool.key(),
+    reward_pool_index: index,
+    reward_mint: ctx.accounts.reward_mint.key(),
+    reward_vault: ctx.accounts.reward_vault.key(),
+  });
   Ok(())
 }
\ No newline at end of file
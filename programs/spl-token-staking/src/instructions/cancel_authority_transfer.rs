@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+  errors::ErrorCode,
+  events::AuthorityTransferCancelled,
+  state::StakePool,
+};
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+  /// Current authority of the StakePool
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority @ ErrorCode::InvalidAuthority,
+  )]
+  pub stake_pool: AccountLoader<'info, StakePool>,
+}
+
+pub fn handler(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+  let mut stake_pool = ctx.accounts.stake_pool.load_mut()?;
+  let pending_authority = stake_pool.pending_authority;
+  stake_pool.pending_authority = Pubkey::default();
+
+  emit!(AuthorityTransferCancelled {
+    stake_pool: ctx.accounts.stake_pool.key(),
+    authority: stake_pool.authority,
+    pending_authority,
+  });
+  Ok(())
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 
+use crate::state::StakePoolLoader;
 use super::claim_base::*;
 
 #[derive(Accounts)]
@@ -9,7 +10,7 @@ pub struct ClaimAll<'info> {
 
 pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ClaimAll<'info>>) -> Result<()> {
     {
-        let mut stake_pool = ctx.accounts.claim_base.stake_pool.load_mut()?;
+        let mut stake_pool = ctx.accounts.claim_base.stake_pool.load_mut_with_extensions()?;
         stake_pool.recalculate_rewards_per_effective_stake(&ctx.remaining_accounts, 2usize)?;
     }
 
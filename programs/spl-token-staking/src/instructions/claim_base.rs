@@ -1,10 +1,31 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, Transfer};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, Mint, TransferChecked};
 
 use crate::errors::ErrorCode;
-use crate::math::U256;
+use crate::events::ClaimEvent;
 use crate::stake_pool_signer_seeds;
-use crate::state::{StakeDepositReceipt, StakePool, MAX_REWARD_POOLS, SCALE_FACTOR_BASE_SQUARED};
+use crate::state::{
+    is_reward_pool_selected, StakeDepositReceipt, StakePool, StakePoolLoader, ALL_REWARD_POOLS,
+    MAX_REWARD_POOLS,
+};
+
+/// Token program of the `stake_mint`. A non-transferable `stake_mint` is a Token-2022 Mint, in
+/// which case the `token_2022_program` must be provided.
+pub fn get_stake_mint_token_program<'info>(
+    stake_mint: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    token_2022_program: &Option<Program<'info, Token2022>>,
+) -> Result<AccountInfo<'info>> {
+    if *stake_mint.owner != Token2022::id() {
+        return Ok(token_program.to_account_info());
+    }
+    match token_2022_program {
+        Some(token_2022_program) => Ok(token_2022_program.to_account_info()),
+        None => err!(ErrorCode::MissingToken2022Program),
+    }
+}
 
 #[derive(Accounts)]
 pub struct ClaimBase<'info> {
@@ -25,23 +46,100 @@ pub struct ClaimBase<'info> {
     pub stake_deposit_receipt: Account<'info, StakeDepositReceipt>,
 
     pub token_program: Program<'info, Token>,
+
+    /// Token program of Token-2022 RewardPools and a non-transferable `stake_mint`, only
+    /// required when using either
+    pub token_2022_program: Option<Program<'info, Token2022>>,
 }
 
 impl<'info> ClaimBase<'info> {
+    /// Token program of the StakePool's `stake_mint`
+    pub fn stake_mint_token_program(
+        &self,
+        stake_mint: &AccountInfo<'info>,
+    ) -> Result<AccountInfo<'info>> {
+        get_stake_mint_token_program(stake_mint, &self.token_program, &self.token_2022_program)
+    }
+}
+
+/// Settles and transfers the rewards of a StakeDepositReceipt to its owner. Implemented by
+/// [ClaimBase] for claims signed by the owner and by
+/// [CrankClaim](super::crank_claim::CrankClaim) for claims on the owner's behalf.
+pub trait ClaimRewards<'info> {
+    fn stake_pool(&self) -> &AccountLoader<'info, StakePool>;
+
+    /// Owner of the StakeDepositReceipt, receiving the rewards
+    fn owner_key(&self) -> Pubkey;
+
+    fn stake_deposit_receipt(&self) -> &Account<'info, StakeDepositReceipt>;
+
+    fn stake_deposit_receipt_mut(&mut self) -> &mut Account<'info, StakeDepositReceipt>;
+
+    fn token_program(&self) -> &Program<'info, Token>;
+
+    fn token_2022_program(&self) -> &Option<Program<'info, Token2022>>;
+
+    /// Additional validation of the token account receiving the rewards of a RewardPool. Any
+    /// token account is accepted by default, since the owner signs.
+    fn validate_owner_reward_account(
+        &self,
+        _reward_vault_info: &AccountInfo<'info>,
+        _owner_reward_account_info: &AccountInfo<'info>,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Assert `reward_pool_mask` selects at least one RewardPool and only occupied ones.
+    fn validate_reward_pool_mask(&self, reward_pool_mask: u16) -> Result<()> {
+        let stake_pool = self.stake_pool().load()?;
+        require!(
+            reward_pool_mask != 0 && reward_pool_mask >> MAX_REWARD_POOLS == 0,
+            ErrorCode::InvalidRewardPoolMask
+        );
+        for (index, reward_pool) in stake_pool.reward_pools.iter().enumerate() {
+            if is_reward_pool_selected(reward_pool_mask, index) {
+                require!(!reward_pool.is_empty(), ErrorCode::InvalidRewardPoolMask);
+            }
+        }
+        Ok(())
+    }
+
     /// Transfer tokens from a RewardPool to the StakeDepositReceipt owner that is claiming.
-    pub fn transfer_reward_from_pool_to_owner(
+    /// Token-2022 RewardPools transfer with `transfer_checked` and require the `reward_mint`.
+    fn transfer_reward_from_pool_to_owner(
         &self,
         reward_vault_info: AccountInfo<'info>,
         owner_reward_account_info: AccountInfo<'info>,
+        reward_mint_info: Option<&AccountInfo<'info>>,
         amount: u64,
     ) -> Result<()> {
-        let stake_pool = self.stake_pool.load()?;
+        let stake_pool = self.stake_pool().load()?;
+        if let Some(reward_mint_info) = reward_mint_info {
+            let token_2022_program = match self.token_2022_program() {
+                Some(token_2022_program) => token_2022_program,
+                None => return err!(ErrorCode::MissingToken2022Program),
+            };
+            let reward_mint: InterfaceAccount<'info, Mint> =
+                InterfaceAccount::try_from(reward_mint_info).map_err(|_| ErrorCode::InvalidMint)?;
+            let cpi_ctx = CpiContext {
+                program: token_2022_program.to_account_info(),
+                accounts: TransferChecked {
+                    from: reward_vault_info,
+                    mint: reward_mint_info.clone(),
+                    to: owner_reward_account_info,
+                    authority: self.stake_pool().to_account_info(),
+                },
+                remaining_accounts: Vec::new(),
+                signer_seeds: &[stake_pool_signer_seeds!(stake_pool)],
+            };
+            return token_interface::transfer_checked(cpi_ctx, amount, reward_mint.decimals);
+        }
         let cpi_ctx = CpiContext {
-            program: self.token_program.to_account_info(),
+            program: self.token_program().to_account_info(),
             accounts: Transfer {
                 from: reward_vault_info,
                 to: owner_reward_account_info,
-                authority: self.stake_pool.to_account_info(),
+                authority: self.stake_pool().to_account_info(),
             },
             remaining_accounts: Vec::new(),
             signer_seeds: &[stake_pool_signer_seeds!(stake_pool)],
@@ -51,15 +149,58 @@ impl<'info> ClaimBase<'info> {
 
     /// Iterated over reward pools to calculate amount claimable from each and
     /// transfer to the owner of the StakeDepositReceipt.
-    pub fn transfer_all_claimable_rewards(
+    fn transfer_all_claimable_rewards(
+        &self,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<[u64; MAX_REWARD_POOLS]> {
+        self.transfer_all_claimable_rewards_of_receipts(
+            &[self.stake_deposit_receipt()],
+            remaining_accounts,
+        )
+    }
+
+    /// Iterated over reward pools to calculate the amount claimable from each by all of the
+    /// given StakeDepositReceipts and transfer the sum to the owner. The receipts must all be
+    /// owned by the `owner` and belong to the `stake_pool`.
+    ///
+    /// `remaining_accounts` holds a (reward vault, owner token account) pair per RewardPool,
+    /// followed by the reward mint of each Token-2022 RewardPool in the same order.
+    fn transfer_all_claimable_rewards_of_receipts(
+        &self,
+        stake_deposit_receipts: &[&StakeDepositReceipt],
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<[u64; MAX_REWARD_POOLS]> {
+        self.transfer_selected_claimable_rewards_of_receipts(
+            stake_deposit_receipts,
+            remaining_accounts,
+            ALL_REWARD_POOLS,
+        )
+    }
+
+    /// Same as `transfer_all_claimable_rewards_of_receipts`, but only for the RewardPools selected
+    /// by `reward_pool_mask`. `remaining_accounts` only holds the pairs and reward mints of the
+    /// selected RewardPools.
+    fn transfer_selected_claimable_rewards_of_receipts(
         &self,
+        stake_deposit_receipts: &[&StakeDepositReceipt],
         remaining_accounts: &[AccountInfo<'info>],
+        reward_pool_mask: u16,
     ) -> Result<[u64; MAX_REWARD_POOLS]> {
-        let stake_pool = self.stake_pool.load()?;
+        let stake_pool = self.stake_pool().load_with_extensions()?;
         let mut remaining_accounts_index: usize = 0;
+        // reward mints of Token-2022 RewardPools follow the pairs of all selected RewardPools
+        let mut reward_mint_account_index = stake_pool
+            .reward_pools
+            .iter()
+            .enumerate()
+            .filter(|(index, reward_pool)| {
+                !reward_pool.is_empty() && is_reward_pool_selected(reward_pool_mask, *index)
+            })
+            .count()
+            * 2;
         let mut claimed_amounts = [0u64; MAX_REWARD_POOLS];
         for (index, reward_pool) in stake_pool.reward_pools.iter().enumerate() {
-            if reward_pool.is_empty() {
+            if reward_pool.is_empty() || !is_reward_pool_selected(reward_pool_mask, index) {
                 continue;
             }
             // indexes for the relevant remaining accounts
@@ -86,20 +227,28 @@ impl<'info> ClaimBase<'info> {
                 );
                 return err!(ErrorCode::InvalidRewardPoolVault);
             }
+            self.validate_owner_reward_account(reward_vault_info, owner_reward_account_info)?;
+
+            let reward_mint_info = if stake_pool.reward_pool_extension(index).is_token_2022() {
+                let reward_mint_info = remaining_accounts.get(reward_mint_account_index);
+                if reward_mint_info.is_none() {
+                    msg!(
+                        "Missing reward mint account. Failed at index {:?}",
+                        reward_mint_account_index
+                    );
+                    return err!(ErrorCode::InvalidRewardPoolVaultIndex);
+                }
+                reward_mint_account_index += 1;
+                reward_mint_info
+            } else {
+                None
+            };
 
-            let claimable_per_effective_stake = reward_pool
-                .rewards_per_effective_stake_u128()
-                .checked_sub(self.stake_deposit_receipt.claimed_amounts[index].as_u128())
-                .unwrap();
-            // Note: Cannot overflow, 2^128 * 2^128 < 2^256
-            let total_claimable = U256::from(claimable_per_effective_stake)
-                .checked_mul(U256::from(
-                    self.stake_deposit_receipt.effective_stake_u128(),
-                ))
-                .unwrap()
-                .checked_div(U256::from(SCALE_FACTOR_BASE_SQUARED))
-                .unwrap()
-                .as_u64();
+            let mut total_claimable = 0u64;
+            for stake_deposit_receipt in stake_deposit_receipts {
+                let claimable = stake_pool.get_receipt_claimable_amount(stake_deposit_receipt, index);
+                total_claimable = total_claimable.checked_add(claimable).unwrap();
+            }
             if total_claimable == 0 {
                 remaining_accounts_index += 1;
                 continue;
@@ -108,6 +257,7 @@ impl<'info> ClaimBase<'info> {
             self.transfer_reward_from_pool_to_owner(
                 reward_vault_info.to_account_info(),
                 owner_reward_account_info.to_account_info(),
+                reward_mint_info,
                 total_claimable,
             )?;
 
@@ -118,21 +268,70 @@ impl<'info> ClaimBase<'info> {
         Ok(claimed_amounts)
     }
 
-    /// Decrement `last_amount` for all RewardPools that had tokens transferred.
-    pub fn update_reward_pools_last_amount(
+    /// Decrement `last_amount` for all RewardPools that had tokens transferred and emit a
+    /// `ClaimEvent` when anything was claimed.
+    fn update_reward_pools_last_amount(
         &mut self,
         claimed_amounts: [u64; MAX_REWARD_POOLS],
     ) -> Result<()> {
-        let mut stake_pool = self.stake_pool.load_mut()?;
-        for (index, reward_pool) in stake_pool.reward_pools.iter_mut().enumerate() {
-            if reward_pool.is_empty() {
-                continue;
+        self.update_selected_reward_pools_last_amount(claimed_amounts, ALL_REWARD_POOLS)
+    }
+
+    /// Same as `update_reward_pools_last_amount`, but leaves the StakeDepositReceipt's claimed
+    /// amounts of RewardPools not selected by `reward_pool_mask` untouched.
+    fn update_selected_reward_pools_last_amount(
+        &mut self,
+        claimed_amounts: [u64; MAX_REWARD_POOLS],
+        reward_pool_mask: u16,
+    ) -> Result<()> {
+        let mut receipt_claimed_amounts = self.stake_deposit_receipt().claimed_amounts;
+        {
+            let mut stake_pool = self.stake_pool().load_mut()?;
+            for (index, reward_pool) in stake_pool.reward_pools.iter_mut().enumerate() {
+                if reward_pool.is_empty() || !is_reward_pool_selected(reward_pool_mask, index) {
+                    continue;
+                }
+                let claimed = claimed_amounts[index];
+                reward_pool.last_amount = reward_pool.last_amount.checked_sub(claimed).unwrap();
+                receipt_claimed_amounts[index] = reward_pool.rewards_per_effective_stake;
             }
-            let claimed = claimed_amounts[index];
-            reward_pool.last_amount = reward_pool.last_amount.checked_sub(claimed).unwrap();
-            self.stake_deposit_receipt.claimed_amounts[index] =
-                reward_pool.rewards_per_effective_stake;
+        }
+        self.stake_deposit_receipt_mut().claimed_amounts = receipt_claimed_amounts;
+
+        if claimed_amounts.iter().any(|amount| *amount > 0) {
+            emit!(ClaimEvent {
+                stake_pool: self.stake_pool().key(),
+                owner: self.owner_key(),
+                stake_deposit_receipt: self.stake_deposit_receipt().key(),
+                amounts: claimed_amounts,
+            });
         }
         Ok(())
     }
 }
+
+impl<'info> ClaimRewards<'info> for ClaimBase<'info> {
+    fn stake_pool(&self) -> &AccountLoader<'info, StakePool> {
+        &self.stake_pool
+    }
+
+    fn owner_key(&self) -> Pubkey {
+        self.owner.key()
+    }
+
+    fn stake_deposit_receipt(&self) -> &Account<'info, StakeDepositReceipt> {
+        &self.stake_deposit_receipt
+    }
+
+    fn stake_deposit_receipt_mut(&mut self) -> &mut Account<'info, StakeDepositReceipt> {
+        &mut self.stake_deposit_receipt
+    }
+
+    fn token_program(&self) -> &Program<'info, Token> {
+        &self.token_program
+    }
+
+    fn token_2022_program(&self) -> &Option<Program<'info, Token2022>> {
+        &self.token_2022_program
+    }
+}
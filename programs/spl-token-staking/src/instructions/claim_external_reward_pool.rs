@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+  self, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::errors::ErrorCode;
+use crate::stake_pool_signer_seeds;
+use crate::state::{ExternalRewardPool, RewardCheckpoint, StakePool};
+
+#[derive(Accounts)]
+pub struct ClaimExternalRewardPool<'info> {
+  /// Owner of the StakeDepositReceipt
+  pub owner: Signer<'info>,
+
+  /// StakePool owning the `reward_vault`
+  #[account(address = reward_pool.stake_pool @ ErrorCode::InvalidStakePool)]
+  pub stake_pool: AccountLoader<'info, StakePool>,
+
+  #[account(
+    mut,
+    has_one = reward_vault @ ErrorCode::InvalidRewardPoolVault,
+    has_one = reward_mint @ ErrorCode::InvalidMint,
+  )]
+  pub reward_pool: Account<'info, ExternalRewardPool>,
+
+  #[account(
+    mut,
+    has_one = owner @ ErrorCode::InvalidAuthority,
+    has_one = reward_pool,
+    has_one = stake_deposit_receipt,
+  )]
+  pub reward_checkpoint: Account<'info, RewardCheckpoint>,
+
+  /// CHECK: StakeDepositReceipt of the RewardCheckpoint, closed once it was withdrawn
+  pub stake_deposit_receipt: UncheckedAccount<'info>,
+
+  #[account(mut)]
+  pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+  pub reward_mint: InterfaceAccount<'info, Mint>,
+
+  /// Token account of the owner receiving the rewards
+  #[account(mut)]
+  pub owner_reward_account: InterfaceAccount<'info, TokenAccount>,
+
+  /// Token program of the `reward_mint`, either SPL Token or Token-2022
+  #[account(address = reward_pool.token_program)]
+  pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimExternalRewardPool<'info> {
+  pub fn transfer_from_reward_vault_to_owner(&self, amount: u64) -> Result<()> {
+    let stake_pool = self.stake_pool.load()?;
+    let cpi_ctx = CpiContext {
+      program: self.token_program.to_account_info(),
+      accounts: TransferChecked {
+        from: self.reward_vault.to_account_info(),
+        mint: self.reward_mint.to_account_info(),
+        to: self.owner_reward_account.to_account_info(),
+        authority: self.stake_pool.to_account_info(),
+      },
+      remaining_accounts: Vec::new(),
+      signer_seeds: &[stake_pool_signer_seeds!(stake_pool)],
+    };
+    token_interface::transfer_checked(cpi_ctx, amount, self.reward_mint.decimals)
+  }
+}
+
+pub fn handler(ctx: Context<ClaimExternalRewardPool>) -> Result<()> {
+  let effective_stake = ctx
+    .accounts
+    .reward_checkpoint
+    .get_receipt_effective_stake(&ctx.accounts.stake_deposit_receipt)?;
+  ctx.accounts.reward_checkpoint.sync(
+    &mut ctx.accounts.reward_pool,
+    effective_stake,
+    Clock::get()?.unix_timestamp,
+  );
+
+  let amount = ctx.accounts.reward_checkpoint.unclaimed;
+  if amount > 0 {
+    ctx.accounts.transfer_from_reward_vault_to_owner(amount)?;
+    ctx.accounts.reward_checkpoint.unclaimed = 0;
+  }
+  Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::state::StakePoolLoader;
+use super::claim_base::*;
+
+#[derive(Accounts)]
+pub struct ClaimSelected<'info> {
+    pub claim_base: ClaimBase<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimSelected<'info>>,
+    reward_pool_mask: u16,
+) -> Result<()> {
+    ctx.accounts
+        .claim_base
+        .validate_reward_pool_mask(reward_pool_mask)?;
+    {
+        let mut stake_pool = ctx.accounts.claim_base.stake_pool.load_mut_with_extensions()?;
+        stake_pool.recalculate_selected_rewards_per_effective_stake(
+            ctx.remaining_accounts,
+            2usize,
+            reward_pool_mask,
+        )?;
+    }
+
+    let claim_base = &ctx.accounts.claim_base;
+    let claimed_amounts = claim_base.transfer_selected_claimable_rewards_of_receipts(
+        &[&claim_base.stake_deposit_receipt],
+        ctx.remaining_accounts,
+        reward_pool_mask,
+    )?;
+
+    ctx.accounts
+        .claim_base
+        .update_selected_reward_pools_last_amount(claimed_amounts, reward_pool_mask)?;
+
+    Ok(())
+}
@@ -0,0 +1,206 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::errors::ErrorCode;
+use crate::events::CompoundEvent;
+use crate::stake_pool_signer_seeds;
+use crate::state::{u128, StakeDepositReceipt, StakePoolLoader, MAX_REWARD_POOLS};
+
+use super::claim_base::*;
+
+#[derive(Accounts)]
+pub struct Compound<'info> {
+    pub claim_base: ClaimBase<'info>,
+
+    /// Vault of the StakePool receiving the compounded rewards
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the StakePool's underlying token, which the RewardPool must distribute
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Vault of the RewardPool at `index`
+    #[account(mut)]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// stake_mint of StakePool that will be minted for the additional effective stake
+    #[account(mut)]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token account the StakePool token will be transfered to
+    #[account(mut)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program of the `mint`, either SPL Token or Token-2022
+    pub mint_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Compound<'info> {
+    /// Addiditional validations that rely on the accounts within `claim_base`.
+    pub fn validate_stake_pool_and_reward_pool(&self, index: usize) -> Result<()> {
+        let stake_pool = self.claim_base.stake_pool.load()?;
+        require!(
+            stake_pool.vault == self.vault.key(),
+            ErrorCode::InvalidStakePoolVault
+        );
+        require!(stake_pool.mint == self.mint.key(), ErrorCode::InvalidMint);
+        require!(
+            stake_pool.stake_mint == self.stake_mint.key(),
+            ErrorCode::InvalidStakeMint
+        );
+        require!(
+            self.destination.owner == self.claim_base.owner.key(),
+            ErrorCode::InvalidAuthority
+        );
+        require!(index < MAX_REWARD_POOLS, ErrorCode::InvalidRewardPoolVaultIndex);
+        let reward_pool = &stake_pool.reward_pools[index];
+        require!(!reward_pool.is_empty(), ErrorCode::RewardPoolIndexEmpty);
+        require!(
+            reward_pool.reward_vault == self.reward_vault.key(),
+            ErrorCode::InvalidRewardPoolVault
+        );
+        require!(
+            self.reward_vault.mint == stake_pool.mint,
+            ErrorCode::InvalidMint
+        );
+        require!(!stake_pool.deposits_disabled(), ErrorCode::DepositsDisabled);
+        Ok(())
+    }
+
+    /// Transfer rewards from the RewardPool's vault to the StakePool vault. Returns the amount
+    /// received by the vault, which is less than `amount` for mints with a transfer fee.
+    pub fn transfer_from_reward_vault_to_stake_vault(&mut self, amount: u64) -> Result<u64> {
+        let vault_amount_before = self.vault.amount;
+        {
+            let stake_pool = self.claim_base.stake_pool.load()?;
+            let signer_seeds: &[&[&[u8]]] = &[stake_pool_signer_seeds!(stake_pool)];
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.mint_token_program.to_account_info(),
+                TransferChecked {
+                    from: self.reward_vault.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: self.vault.to_account_info(),
+                    authority: self.claim_base.stake_pool.to_account_info(),
+                },
+                signer_seeds,
+            );
+            token_interface::transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+        }
+        self.vault.reload()?;
+        Ok(self.vault.amount.checked_sub(vault_amount_before).unwrap())
+    }
+
+    pub fn mint_staked_token_to_user(&self, effective_amount: u64) -> Result<()> {
+        let stake_pool = self.claim_base.stake_pool.load()?;
+        let signer_seeds: &[&[&[u8]]] = &[stake_pool_signer_seeds!(stake_pool)];
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.claim_base
+                .stake_mint_token_program(&self.stake_mint.to_account_info())?,
+            MintTo {
+                mint: self.stake_mint.to_account_info(),
+                to: self.destination.to_account_info(),
+                authority: self.claim_base.stake_pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        token_interface::mint_to(cpi_ctx, effective_amount)
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Compound<'info>>,
+    index: u8,
+) -> Result<()> {
+    let index = usize::from(index);
+    ctx.accounts.validate_stake_pool_and_reward_pool(index)?;
+    // The compounded amount receives the weight of the full lockup, so the lockup must not have
+    // ended. Receipts without a lockup are already at the minimum weight.
+    let stake_deposit_receipt = &ctx.accounts.claim_base.stake_deposit_receipt;
+    if stake_deposit_receipt.lockup_duration > 0
+        && Clock::get()?.unix_timestamp >= stake_deposit_receipt.get_unlock_timestamp()
+    {
+        return err!(ErrorCode::StakeDepositReceiptUnlocked);
+    }
+
+    let compound_amount: u64;
+    {
+        let mut stake_pool = ctx.accounts.claim_base.stake_pool.load_mut_with_extensions()?;
+        // Recalculate rewards so the pending rewards are settled at the current effective stake
+        stake_pool.recalculate_rewards_per_effective_stake(ctx.remaining_accounts, 2usize)?;
+
+        let stake_deposit_receipt = &mut ctx.accounts.claim_base.stake_deposit_receipt;
+        compound_amount = stake_pool.get_receipt_claimable_amount(stake_deposit_receipt, index);
+        let reward_pool = &mut stake_pool.reward_pools[index];
+        reward_pool.last_amount = reward_pool.last_amount.checked_sub(compound_amount).unwrap();
+        stake_deposit_receipt.claimed_amounts[index] = reward_pool.rewards_per_effective_stake;
+    } // release mutable borrow of stake_pool
+
+    // claim the rewards of all other RewardPools before the effective stake changes
+    let claimed_amounts = ctx
+        .accounts
+        .claim_base
+        .transfer_all_claimable_rewards(ctx.remaining_accounts)?;
+
+    ctx.accounts
+        .claim_base
+        .update_reward_pools_last_amount(claimed_amounts)?;
+
+    if compound_amount == 0 {
+        return Ok(());
+    }
+    let amount = ctx
+        .accounts
+        .transfer_from_reward_vault_to_stake_vault(compound_amount)?;
+
+    // The compounded amount is staked at the receipt's existing weight and lockup
+    let stake_deposit_receipt = &mut ctx.accounts.claim_base.stake_deposit_receipt;
+    let effective_stake_before = stake_deposit_receipt.effective_stake_u128();
+    let additional_effective_stake = stake_deposit_receipt.get_effective_stake_for_amount(amount);
+    let effective_stake_after = effective_stake_before
+        .checked_add(additional_effective_stake)
+        .unwrap();
+    stake_deposit_receipt.deposit_amount = stake_deposit_receipt
+        .deposit_amount
+        .checked_add(amount)
+        .unwrap();
+    stake_deposit_receipt.effective_stake = u128(effective_stake_after.to_le_bytes());
+
+    let max_weight: u64;
+    let deposits_ignores_lp: bool;
+    {
+        let mut stake_pool = ctx.accounts.claim_base.stake_pool.load_mut()?;
+        max_weight = stake_pool.max_weight;
+        deposits_ignores_lp = stake_pool.deposits_ignores_lp();
+        let total_staked = stake_pool
+            .total_weighted_stake_u128()
+            .checked_add(additional_effective_stake)
+            .unwrap();
+        stake_pool.total_weighted_stake = u128(total_staked.to_le_bytes());
+    }
+
+    if !deposits_ignores_lp {
+        // Mint the difference of the token representations, so the total minted over the life
+        // of the receipt always matches the amount burned at withdraw.
+        let mint_amount =
+            StakeDepositReceipt::get_token_amount_from_stake(effective_stake_after, max_weight)
+                .checked_sub(StakeDepositReceipt::get_token_amount_from_stake(
+                    effective_stake_before,
+                    max_weight,
+                ))
+                .unwrap();
+        ctx.accounts.mint_staked_token_to_user(mint_amount)?;
+    }
+
+    emit!(CompoundEvent {
+        stake_pool: ctx.accounts.claim_base.stake_pool.key(),
+        owner: ctx.accounts.claim_base.owner.key(),
+        stake_deposit_receipt: ctx.accounts.claim_base.stake_deposit_receipt.key(),
+        reward_pool_index: index as u8,
+        amount,
+        effective_stake: additional_effective_stake,
+    });
+    Ok(())
+}
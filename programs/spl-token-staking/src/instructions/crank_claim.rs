@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::state::{ClaimDelegate, StakeDepositReceipt, StakePool, StakePoolLoader};
+
+use super::claim_base::*;
+
+#[derive(Accounts)]
+pub struct CrankClaim<'info> {
+    /// Claims on behalf of the owner. Must be the owner's claim delegate, unless the owner
+    /// allows anyone to claim.
+    pub cranker: Signer<'info>,
+
+    /// CHECK: Owner of the StakeDepositReceipt, validated by the receipt and the ClaimDelegate
+    pub owner: UncheckedAccount<'info>,
+
+    // StakePool the StakeDepositReceipt belongs to
+    #[account(mut)]
+    pub stake_pool: AccountLoader<'info, StakePool>,
+
+    /// StakeDepositReceipt of the owner that will be used to claim respective rewards
+    #[account(
+      mut,
+      has_one = owner @ ErrorCode::InvalidOwner,
+      has_one = stake_pool @ ErrorCode::InvalidStakePool,
+    )]
+    pub stake_deposit_receipt: Account<'info, StakeDepositReceipt>,
+
+    /// Created by the owner to opt in to claims on their behalf
+    #[account(
+      seeds = [
+        owner.key().as_ref(),
+        stake_pool.key().as_ref(),
+        b"claimDelegate".as_ref(),
+      ],
+      bump,
+      constraint = claim_delegate.can_claim(&cranker.key()) @ ErrorCode::InvalidClaimDelegate,
+    )]
+    pub claim_delegate: Account<'info, ClaimDelegate>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// Token program of Token-2022 RewardPools, only required when claiming from them
+    pub token_2022_program: Option<Program<'info, Token2022>>,
+}
+
+impl<'info> ClaimRewards<'info> for CrankClaim<'info> {
+    fn stake_pool(&self) -> &AccountLoader<'info, StakePool> {
+        &self.stake_pool
+    }
+
+    fn owner_key(&self) -> Pubkey {
+        self.owner.key()
+    }
+
+    fn stake_deposit_receipt(&self) -> &Account<'info, StakeDepositReceipt> {
+        &self.stake_deposit_receipt
+    }
+
+    fn stake_deposit_receipt_mut(&mut self) -> &mut Account<'info, StakeDepositReceipt> {
+        &mut self.stake_deposit_receipt
+    }
+
+    fn token_program(&self) -> &Program<'info, Token> {
+        &self.token_program
+    }
+
+    fn token_2022_program(&self) -> &Option<Program<'info, Token2022>> {
+        &self.token_2022_program
+    }
+
+    /// The owner does not sign, so rewards may only be transferred to their associated token
+    /// account of the reward mint.
+    fn validate_owner_reward_account(
+        &self,
+        reward_vault_info: &AccountInfo<'info>,
+        owner_reward_account_info: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let reward_vault: InterfaceAccount<'info, TokenAccount> =
+            InterfaceAccount::try_from(reward_vault_info)
+                .map_err(|_| ErrorCode::InvalidRewardPoolVault)?;
+        let owner_reward_account = get_associated_token_address_with_program_id(
+            &self.owner.key(),
+            &reward_vault.mint,
+            reward_vault_info.owner,
+        );
+        require!(
+            owner_reward_account == owner_reward_account_info.key(),
+            ErrorCode::InvalidOwnerRewardAccount
+        );
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CrankClaim<'info>>,
+    reward_pool_mask: u16,
+) -> Result<()> {
+    ctx.accounts.validate_reward_pool_mask(reward_pool_mask)?;
+    {
+        let mut stake_pool = ctx.accounts.stake_pool.load_mut_with_extensions()?;
+        stake_pool.recalculate_selected_rewards_per_effective_stake(
+            ctx.remaining_accounts,
+            2usize,
+            reward_pool_mask,
+        )?;
+    }
+
+    let claimed_amounts = ctx.accounts.transfer_selected_claimable_rewards_of_receipts(
+        &[&ctx.accounts.stake_deposit_receipt],
+        ctx.remaining_accounts,
+        reward_pool_mask,
+    )?;
+
+    ctx.accounts
+        .update_selected_reward_pools_last_amount(claimed_amounts, reward_pool_mask)?;
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{MaxVoterWeightRecord, StakePool};
+
+#[derive(Accounts)]
+pub struct CreateMaxVoterWeightRecord<'info> {
+  /// Payer of the MaxVoterWeightRecord rent
+  #[account(mut)]
+  pub payer: Signer<'info>,
+
+  /// StakePool whose `stake_mint` is the Realm's governing token
+  pub stake_pool: AccountLoader<'info, StakePool>,
+
+  /// CHECK: Stored on the MaxVoterWeightRecord, validated by SPL Governance when the record is used
+  pub realm: UncheckedAccount<'info>,
+
+  #[account(
+    init,
+    payer = payer,
+    space = MaxVoterWeightRecord::LEN,
+    seeds = [
+      stake_pool.key().as_ref(),
+      realm.key().as_ref(),
+      b"maxVoterWeightRecord".as_ref(),
+    ],
+    bump,
+  )]
+  pub max_voter_weight_record: Account<'info, MaxVoterWeightRecord>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateMaxVoterWeightRecord>) -> Result<()> {
+  let stake_pool = ctx.accounts.stake_pool.load()?;
+  let max_voter_weight_record = &mut ctx.accounts.max_voter_weight_record;
+  max_voter_weight_record.realm = ctx.accounts.realm.key();
+  max_voter_weight_record.governing_token_mint = stake_pool.stake_mint;
+  // expired until updated
+  max_voter_weight_record.max_voter_weight_expiry = Some(0);
+  Ok(())
+}
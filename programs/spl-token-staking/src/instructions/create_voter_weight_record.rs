@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{StakePool, VoterWeightRecord};
+
+#[derive(Accounts)]
+pub struct CreateVoterWeightRecord<'info> {
+  /// Payer of the VoterWeightRecord rent
+  #[account(mut)]
+  pub payer: Signer<'info>,
+
+  /// CHECK: Only used as the owner of the VoterWeightRecord
+  pub governing_token_owner: UncheckedAccount<'info>,
+
+  /// StakePool whose `stake_mint` is the Realm's governing token
+  pub stake_pool: AccountLoader<'info, StakePool>,
+
+  /// CHECK: Stored on the VoterWeightRecord, validated by SPL Governance when the record is used
+  pub realm: UncheckedAccount<'info>,
+
+  #[account(
+    init,
+    payer = payer,
+    space = VoterWeightRecord::LEN,
+    seeds = [
+      stake_pool.key().as_ref(),
+      realm.key().as_ref(),
+      governing_token_owner.key().as_ref(),
+      b"voterWeightRecord".as_ref(),
+    ],
+    bump,
+  )]
+  pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateVoterWeightRecord>) -> Result<()> {
+  let stake_pool = ctx.accounts.stake_pool.load()?;
+  let voter_weight_record = &mut ctx.accounts.voter_weight_record;
+  voter_weight_record.realm = ctx.accounts.realm.key();
+  voter_weight_record.governing_token_mint = stake_pool.stake_mint;
+  voter_weight_record.governing_token_owner = ctx.accounts.governing_token_owner.key();
+  // expired until updated
+  voter_weight_record.voter_weight_expiry = Some(0);
+  Ok(())
+}
@@ -1,7 +1,9 @@
 use std::str::FromStr;
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount};
 
 use crate::{
     errors::ErrorCode,
@@ -9,6 +11,8 @@ use crate::{
     state::StakePool,
 };
 
+use super::claim_base::get_stake_mint_token_program;
+
 #[derive(Accounts)]
 pub struct DangerouslyMintStakeMint<'info> {
     /// Payer of rent
@@ -19,10 +23,10 @@ pub struct DangerouslyMintStakeMint<'info> {
   pub authority: Signer<'info>,
 
   #[account(mut)]
-  pub stake_mint: Account<'info, Mint>,
+  pub stake_mint: InterfaceAccount<'info, Mint>,
 
   #[account(mut)]
-  pub destination: Account<'info, TokenAccount>,
+  pub destination: InterfaceAccount<'info, TokenAccount>,
 
   /// StakePool of the `stake_mint` to be minted
   #[account(
@@ -33,6 +37,8 @@ pub struct DangerouslyMintStakeMint<'info> {
   pub stake_pool: AccountLoader<'info, StakePool>,
 
   pub token_program: Program<'info, Token>,
+  /// Token program of a non-transferable `stake_mint`, only required when minting one
+  pub token_2022_program: Option<Program<'info, Token2022>>,
 }
 
 impl<'info> DangerouslyMintStakeMint<'info> {
@@ -50,7 +56,11 @@ impl<'info> DangerouslyMintStakeMint<'info> {
         let stake_pool = self.stake_pool.load()?;
         let signer_seeds: &[&[&[u8]]] = &[stake_pool_signer_seeds!(stake_pool)];
         let cpi_ctx = CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
+            get_stake_mint_token_program(
+                &self.stake_mint.to_account_info(),
+                &self.token_program,
+                &self.token_2022_program,
+            )?,
             MintTo {
                 mint: self.stake_mint.to_account_info(),
                 to: self.destination.to_account_info(),
@@ -59,7 +69,7 @@ impl<'info> DangerouslyMintStakeMint<'info> {
             signer_seeds,
         );
 
-        token::mint_to(cpi_ctx, amount)
+        token_interface::mint_to(cpi_ctx, amount)
     }
 }
 
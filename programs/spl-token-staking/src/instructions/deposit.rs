@@ -1,10 +1,17 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{
+    self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+};
 
 use crate::errors::ErrorCode;
+use crate::events::DepositEvent;
 use crate::stake_pool_signer_seeds;
 use crate::state::u128;
-use crate::state::{StakeDepositReceipt, StakePool};
+use crate::state::{StakeDepositReceipt, StakePool, StakePoolLoader};
+
+use super::claim_base::get_stake_mint_token_program;
 
 #[derive(Accounts)]
 #[instruction(nonce: u32)]
@@ -20,27 +27,31 @@ pub struct Deposit<'info> {
 
     /// Token Account to transfer stake_mint from, to be deposited into the vault
     #[account(mut)]
-    pub from: Account<'info, TokenAccount>,
+    pub from: InterfaceAccount<'info, TokenAccount>,
 
     /// Vault of the StakePool token will be transfer to
     #[account(mut)]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the StakePool's underlying token
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(mut)]
-    pub stake_mint: Account<'info, Mint>,
+    pub stake_mint: InterfaceAccount<'info, Mint>,
 
     /// Token account the StakePool token will be transfered to
     #[account(
       mut,
       has_one = owner @ ErrorCode::InvalidAuthority
     )]
-    pub destination: Account<'info, TokenAccount>,
+    pub destination: InterfaceAccount<'info, TokenAccount>,
 
     /// StakePool owning the vault that will receive the deposit
     #[account(
       mut,
       has_one = vault @ ErrorCode::InvalidStakePoolVault,
       has_one = stake_mint @ ErrorCode::InvalidAuthority,
+      has_one = mint @ ErrorCode::InvalidMint,
     )]
     pub stake_pool: AccountLoader<'info, StakePool>,
 
@@ -59,29 +70,42 @@ pub struct Deposit<'info> {
     pub stake_deposit_receipt: Account<'info, StakeDepositReceipt>,
 
     pub token_program: Program<'info, Token>,
+    /// Token program of a non-transferable `stake_mint`, only required for such StakePools
+    pub token_2022_program: Option<Program<'info, Token2022>>,
+    /// Token program of the `mint`, either SPL Token or Token-2022
+    pub mint_token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> Deposit<'info> {
-    /// Transfer the stake_mint from the payer's address to the StakePool vault.
-    pub fn transfer_from_user_to_stake_vault(&self, amount: u64) -> Result<()> {
+    /// Transfer the stake_mint from the payer's address to the StakePool vault. Returns the
+    /// amount received by the vault, which is less than `amount` for mints with a transfer fee.
+    pub fn transfer_from_user_to_stake_vault(&mut self, amount: u64) -> Result<u64> {
+        let vault_amount_before = self.vault.amount;
         let cpi_ctx = CpiContext::new(
-            self.token_program.to_account_info(),
-            Transfer {
+            self.mint_token_program.to_account_info(),
+            TransferChecked {
                 from: self.from.to_account_info(),
+                mint: self.mint.to_account_info(),
                 to: self.vault.to_account_info(),
                 authority: self.payer.to_account_info(),
             },
         );
-        token::transfer(cpi_ctx, amount)
+        token_interface::transfer_checked(cpi_ctx, amount, self.mint.decimals)?;
+        self.vault.reload()?;
+        Ok(self.vault.amount.checked_sub(vault_amount_before).unwrap())
     }
 
     pub fn mint_staked_token_to_user(&self, effective_amount: u64) -> Result<()> {
         let stake_pool = self.stake_pool.load()?;
         let signer_seeds: &[&[&[u8]]] = &[stake_pool_signer_seeds!(stake_pool)];
         let cpi_ctx = CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
+            get_stake_mint_token_program(
+                &self.stake_mint.to_account_info(),
+                &self.token_program,
+                &self.token_2022_program,
+            )?,
             MintTo {
                 mint: self.stake_mint.to_account_info(),
                 to: self.destination.to_account_info(),
@@ -90,7 +114,7 @@ impl<'info> Deposit<'info> {
             signer_seeds,
         );
 
-        token::mint_to(cpi_ctx, effective_amount)
+        token_interface::mint_to(cpi_ctx, effective_amount)
     }
 }
 
@@ -100,10 +124,10 @@ pub fn handler<'info>(
     amount: u64,
     lockup_duration: u64,
 ) -> Result<()> {
-    ctx.accounts.transfer_from_user_to_stake_vault(amount)?;
+    let amount = ctx.accounts.transfer_from_user_to_stake_vault(amount)?;
 
     {
-        let mut stake_pool = ctx.accounts.stake_pool.load_mut()?;
+        let mut stake_pool = ctx.accounts.stake_pool.load_mut_with_extensions()?;
         if stake_pool.deposits_disabled() {
             return err!(ErrorCode::DepositsDisabled);
         }
@@ -149,5 +173,14 @@ pub fn handler<'info>(
             .mint_staked_token_to_user(effect_amount_staked_tokens)?;
     }
 
+    let stake_deposit_receipt = &ctx.accounts.stake_deposit_receipt;
+    emit!(DepositEvent {
+        stake_pool: ctx.accounts.stake_pool.key(),
+        owner: stake_deposit_receipt.owner,
+        stake_deposit_receipt: stake_deposit_receipt.key(),
+        amount,
+        lockup_duration: stake_deposit_receipt.lockup_duration,
+        effective_stake: stake_deposit_receipt.effective_stake_u128(),
+    });
     Ok(())
 }
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ErrorCode, state::PoolFlags};
+
+use super::set_flags::*;
+
+pub fn handler(ctx: Context<SetFlags>, mask: u8) -> Result<()> {
+  let mask = PoolFlags::from_bits(mask).ok_or(ErrorCode::InvalidFlags)?;
+  // operate on the raw flags, so bits unknown to PoolFlags are left untouched
+  let flags = ctx.accounts.stake_pool.load()?.flags | mask.bits();
+  ctx.accounts.update_flags(flags)
+}
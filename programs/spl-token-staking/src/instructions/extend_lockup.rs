@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::events::ExtendLockupEvent;
+use crate::stake_pool_signer_seeds;
+use crate::state::{u128, StakeDepositReceipt, StakePoolLoader};
+
+use super::claim_base::*;
+
+#[derive(Accounts)]
+pub struct ExtendLockup<'info> {
+    pub claim_base: ClaimBase<'info>,
+
+    /// stake_mint of StakePool that will be minted for the additional effective stake
+    #[account(mut)]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token account the additional StakePool token will be transfered to
+    #[account(mut)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+}
+
+impl<'info> ExtendLockup<'info> {
+    /// Addiditional validations that rely on the accounts within `claim_base`.
+    pub fn validate_stake_pool_and_owner(&self) -> Result<()> {
+        let stake_pool = self.claim_base.stake_pool.load()?;
+        require!(
+            stake_pool.stake_mint.key() == self.stake_mint.key(),
+            ErrorCode::InvalidStakeMint
+        );
+        require!(
+            self.destination.owner.key() == self.claim_base.owner.key(),
+            ErrorCode::InvalidAuthority
+        );
+        Ok(())
+    }
+
+    pub fn mint_staked_token_to_user(&self, effective_amount: u64) -> Result<()> {
+        let stake_pool = self.claim_base.stake_pool.load()?;
+        let signer_seeds: &[&[&[u8]]] = &[stake_pool_signer_seeds!(stake_pool)];
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.claim_base
+                .stake_mint_token_program(&self.stake_mint.to_account_info())?,
+            MintTo {
+                mint: self.stake_mint.to_account_info(),
+                to: self.destination.to_account_info(),
+                authority: self.claim_base.stake_pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        token_interface::mint_to(cpi_ctx, effective_amount)
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExtendLockup<'info>>,
+    new_lockup_duration: u64,
+) -> Result<()> {
+    ctx.accounts.validate_stake_pool_and_owner()?;
+
+    let lockup_duration: u64;
+    let weight: u64;
+    {
+        let mut stake_pool = ctx.accounts.claim_base.stake_pool.load_mut_with_extensions()?;
+        if stake_pool.deposits_disabled() {
+            return err!(ErrorCode::DepositsDisabled);
+        }
+        if new_lockup_duration < stake_pool.min_duration {
+            return err!(ErrorCode::DurationTooShort);
+        }
+        // clamp lockup duration to the max
+        lockup_duration = u64::min(new_lockup_duration, stake_pool.max_duration);
+        weight = stake_pool.get_stake_weight(lockup_duration);
+
+        // Recalculate rewards so the pending rewards are settled at the current effective stake
+        stake_pool.recalculate_rewards_per_effective_stake(ctx.remaining_accounts, 2usize)?;
+    } // release mutable borrow of stake_pool
+
+    let stake_deposit_receipt = &ctx.accounts.claim_base.stake_deposit_receipt;
+    if lockup_duration < stake_deposit_receipt.lockup_duration {
+        return err!(ErrorCode::InvalidLockupExtension);
+    }
+    let effective_stake_before = stake_deposit_receipt.effective_stake_u128();
+    let effective_stake_after = StakeDepositReceipt::get_effective_stake_amount(
+        weight,
+        stake_deposit_receipt.deposit_amount,
+    );
+    if effective_stake_after < effective_stake_before {
+        return err!(ErrorCode::InvalidLockupExtension);
+    }
+
+    // claim all unclaimed rewards before the effective stake changes
+    let claimed_amounts = ctx
+        .accounts
+        .claim_base
+        .transfer_all_claimable_rewards(ctx.remaining_accounts)?;
+
+    ctx.accounts
+        .claim_base
+        .update_reward_pools_last_amount(claimed_amounts)?;
+
+    // The lockup restarts from now, so the weight always reflects the time remaining locked.
+    let stake_deposit_receipt = &mut ctx.accounts.claim_base.stake_deposit_receipt;
+    stake_deposit_receipt.lockup_duration = lockup_duration;
+    stake_deposit_receipt.deposit_timestamp = Clock::get()?.unix_timestamp;
+    stake_deposit_receipt.effective_stake = u128(effective_stake_after.to_le_bytes());
+
+    let max_weight: u64;
+    let deposits_ignores_lp: bool;
+    {
+        let mut stake_pool = ctx.accounts.claim_base.stake_pool.load_mut()?;
+        max_weight = stake_pool.max_weight;
+        deposits_ignores_lp = stake_pool.deposits_ignores_lp();
+        let total_staked = stake_pool
+            .total_weighted_stake_u128()
+            .checked_add(
+                effective_stake_after
+                    .checked_sub(effective_stake_before)
+                    .unwrap(),
+            )
+            .unwrap();
+        stake_pool.total_weighted_stake = u128(total_staked.to_le_bytes());
+    }
+
+    if !deposits_ignores_lp {
+        // Mint the difference of the token representations, so the total minted over the life
+        // of the receipt always matches the amount burned at withdraw.
+        let mint_amount =
+            StakeDepositReceipt::get_token_amount_from_stake(effective_stake_after, max_weight)
+                .checked_sub(StakeDepositReceipt::get_token_amount_from_stake(
+                    effective_stake_before,
+                    max_weight,
+                ))
+                .unwrap();
+        ctx.accounts.mint_staked_token_to_user(mint_amount)?;
+    }
+
+    let stake_deposit_receipt = &ctx.accounts.claim_base.stake_deposit_receipt;
+    emit!(ExtendLockupEvent {
+        stake_pool: ctx.accounts.claim_base.stake_pool.key(),
+        owner: stake_deposit_receipt.owner,
+        stake_deposit_receipt: stake_deposit_receipt.key(),
+        lockup_duration,
+        effective_stake: effective_stake_after,
+    });
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+  self, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::errors::ErrorCode;
+use crate::state::ExternalRewardPool;
+
+#[derive(Accounts)]
+pub struct FundExternalRewardPool<'info> {
+  /// Wallet transferring the rewards
+  pub funder: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = reward_vault @ ErrorCode::InvalidRewardPoolVault,
+    constraint = !reward_pool.is_retired() @ ErrorCode::RewardPoolRetired,
+  )]
+  pub reward_pool: Account<'info, ExternalRewardPool>,
+
+  #[account(mut)]
+  pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+  /// Mint of the ExternalRewardPool's `reward_vault`
+  #[account(address = reward_pool.reward_mint @ ErrorCode::InvalidMint)]
+  pub reward_mint: InterfaceAccount<'info, Mint>,
+
+  /// Token account the rewards are transferred from
+  #[account(mut)]
+  pub from: InterfaceAccount<'info, TokenAccount>,
+
+  /// Token program of the `reward_mint`, either SPL Token or Token-2022
+  #[account(address = reward_pool.token_program)]
+  pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> FundExternalRewardPool<'info> {
+  /// Transfer rewards from the funder to the reward vault. Returns the amount received by the
+  /// vault, which is less than `amount` for mints with a transfer fee.
+  pub fn transfer_from_funder_to_reward_vault(&mut self, amount: u64) -> Result<u64> {
+    let vault_amount_before = self.reward_vault.amount;
+    let cpi_ctx = CpiContext::new(
+      self.token_program.to_account_info(),
+      TransferChecked {
+        from: self.from.to_account_info(),
+        mint: self.reward_mint.to_account_info(),
+        to: self.reward_vault.to_account_info(),
+        authority: self.funder.to_account_info(),
+      },
+    );
+    token_interface::transfer_checked(cpi_ctx, amount, self.reward_mint.decimals)?;
+    self.reward_vault.reload()?;
+    Ok(self.reward_vault.amount.checked_sub(vault_amount_before).unwrap())
+  }
+}
+
+pub fn handler(ctx: Context<FundExternalRewardPool>, amount: u64, duration: u64) -> Result<()> {
+  if duration == 0 {
+    return err!(ErrorCode::InvalidEmissionSchedule);
+  }
+  let current_timestamp = Clock::get()?.unix_timestamp;
+  // Distribute everything owed under the current schedule before replacing it
+  ctx.accounts.reward_pool.accrue(current_timestamp);
+
+  let amount = ctx.accounts.transfer_from_funder_to_reward_vault(amount)?;
+  // Rewards are streamed, so stake joining right before the fund can not take an instant share
+  ctx
+    .accounts
+    .reward_pool
+    .schedule_emission(amount, duration, current_timestamp);
+  Ok(())
+}
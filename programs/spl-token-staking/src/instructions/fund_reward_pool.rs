@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+  self, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::errors::ErrorCode;
+use crate::events::RewardPoolFunded;
+use crate::state::{StakePool, StakePoolLoader};
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct FundRewardPool<'info> {
+  /// Wallet transferring the rewards. Must be the StakePool authority or reward manager to set
+  /// a schedule.
+  #[account(mut)]
+  pub funder: Signer<'info>,
+
+  /// StakePool the RewardPool belongs to
+  #[account(
+    mut,
+    constraint = !stake_pool.load()?.reward_pools[usize::from(index)].is_empty()
+      @ ErrorCode::RewardPoolIndexEmpty,
+    constraint = !stake_pool.load()?.reward_pools[usize::from(index)].is_retired()
+      @ ErrorCode::RewardPoolRetired,
+  )]
+  pub stake_pool: AccountLoader<'info, StakePool>,
+
+  /// Vault of the RewardPool at `index`
+  #[account(
+    mut,
+    address = stake_pool.load()?.reward_pools[usize::from(index)].reward_vault
+      @ ErrorCode::InvalidRewardPoolVault,
+  )]
+  pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+  /// Mint of the RewardPool's `reward_vault`
+  #[account(address = reward_vault.mint @ ErrorCode::InvalidMint)]
+  pub reward_mint: InterfaceAccount<'info, Mint>,
+
+  /// Token account the rewards are transferred from
+  #[account(mut)]
+  pub from: InterfaceAccount<'info, TokenAccount>,
+
+  /// Token program of the `reward_mint`, either SPL Token or Token-2022
+  pub token_program: Interface<'info, TokenInterface>,
+  pub system_program: Program<'info, System>,
+}
+
+impl<'info> FundRewardPool<'info> {
+  /// Transfer rewards from the funder to the reward vault. Returns the amount received by the
+  /// vault, which is less than `amount` for mints with a transfer fee.
+  pub fn transfer_from_funder_to_reward_vault(&mut self, amount: u64) -> Result<u64> {
+    let vault_amount_before = self.reward_vault.amount;
+    let cpi_ctx = CpiContext::new(
+      self.token_program.to_account_info(),
+      TransferChecked {
+        from: self.from.to_account_info(),
+        mint: self.reward_mint.to_account_info(),
+        to: self.reward_vault.to_account_info(),
+        authority: self.funder.to_account_info(),
+      },
+    );
+    token_interface::transfer_checked(cpi_ctx, amount, self.reward_mint.decimals)?;
+    self.reward_vault.reload()?;
+    Ok(self.reward_vault.amount.checked_sub(vault_amount_before).unwrap())
+  }
+}
+
+pub fn handler<'info>(
+  ctx: Context<'_, '_, '_, 'info, FundRewardPool<'info>>,
+  index: u8,
+  amount: u64,
+  duration: u64,
+) -> Result<()> {
+  if duration > 0 {
+    if !ctx
+      .accounts
+      .stake_pool
+      .load()?
+      .is_reward_manager(&ctx.accounts.funder.key())
+    {
+      return err!(ErrorCode::InvalidAuthority);
+    }
+    // the schedule is stored in the RewardPool's extension, paid for by the funder
+    ctx.accounts.stake_pool.extend(
+      &ctx.accounts.funder.to_account_info(),
+      &ctx.accounts.system_program.to_account_info(),
+    )?;
+  }
+  {
+    let mut stake_pool = ctx.accounts.stake_pool.load_mut_with_extensions()?;
+    // Credit everything owed under the current schedule before the funds arrive
+    stake_pool.recalculate_rewards_per_effective_stake(ctx.remaining_accounts, 1usize)?;
+  }
+
+  let amount = ctx.accounts.transfer_from_funder_to_reward_vault(amount)?;
+
+  if duration > 0 {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let mut stake_pool = ctx.accounts.stake_pool.load_mut_with_extensions()?;
+    let reward_pool = &mut stake_pool.reward_pools[usize::from(index)];
+    reward_pool.last_amount = reward_pool.last_amount.checked_add(amount).unwrap();
+    // Stream all undistributed rewards, including this amount, evenly over the duration
+    stake_pool
+      .reward_pool_extension_mut(usize::from(index))?
+      .schedule_emission(amount, duration, current_timestamp);
+  }
+
+  emit!(RewardPoolFunded {
+    stake_pool: ctx.accounts.stake_pool.key(),
+    reward_pool_index: index,
+    funder: ctx.accounts.funder.key(),
+    amount,
+    duration,
+  });
+  Ok(())
+}
@@ -1,9 +1,14 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::spl_token_2022::{self, extension::ExtensionType};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, InitializeMint2, Mint, TokenAccount, TokenInterface};
 
 use crate::{
     errors::ErrorCode,
-    state::{get_digit_shift_by_max_scalar, SCALE_FACTOR_BASE, StakePool},
+    state::{get_digit_shift_by_max_scalar, SCALE_FACTOR_BASE, StakePool, WeightCurve, WeightCurveData},
 };
 
 #[derive(Accounts)]
@@ -12,6 +17,8 @@ use crate::{
   max_weight: u64,
   min_duration: u64,
   max_duration: u64,
+  weight_curve: WeightCurve,
+  non_transferable_stake_mint: bool,
 )]
 pub struct InitializeStakePool<'info> {
     /// Payer of rent
@@ -22,8 +29,8 @@ pub struct InitializeStakePool<'info> {
     /// CHECK: No check needed since this will be signer to `AddRewardPool`
     pub authority: UncheckedAccount<'info>,
 
-    /// SPL Token Mint of the underlying token to be deposited for staking
-    pub mint: Account<'info, Mint>,
+    /// SPL Token or Token-2022 Mint of the underlying token to be deposited for staking
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
       init,
@@ -39,16 +46,14 @@ pub struct InitializeStakePool<'info> {
     )]
     pub stake_pool: AccountLoader<'info, StakePool>,
 
-    /// An SPL token Mint for the effective stake weight token
+    /// An SPL token Mint for the effective stake weight token, created by the handler
+    /// CHECK: Address is checked by the seeds and the account is initialized as a Mint
     #[account(
-      init,
+      mut,
       seeds = [&stake_pool.key().to_bytes()[..], b"stakeMint"],
       bump,
-      payer = payer,
-      mint::decimals = mint.decimals.checked_sub(get_digit_shift_by_max_scalar(max_weight)).unwrap_or_default(),
-      mint::authority = stake_pool,
     )]
-    pub stake_mint: Account<'info, Mint>,
+    pub stake_mint: UncheckedAccount<'info>,
 
     /// An SPL token Account for staging A tokens
     #[account(
@@ -58,20 +63,130 @@ pub struct InitializeStakePool<'info> {
       payer = payer,
       token::mint = mint,
       token::authority = stake_pool,
+      token::token_program = mint_token_program,
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
 
+    /// Token program of the `stake_mint`
     pub token_program: Program<'info, Token>,
+    /// Token program of a non-transferable `stake_mint`, only required when creating one
+    pub token_2022_program: Option<Program<'info, Token2022>>,
+    /// Token program of the `mint`, either SPL Token or Token-2022
+    pub mint_token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
 }
 
+impl<'info> InitializeStakePool<'info> {
+    /// Create and initialize the `stake_mint` with the StakePool as mint authority. A
+    /// non-transferable `stake_mint` is a Token-2022 Mint with the NonTransferable extension, so
+    /// effective stake weight can not be moved away from the staker.
+    pub fn create_stake_mint(
+        &self,
+        decimals: u8,
+        non_transferable: bool,
+        bump: u8,
+    ) -> Result<()> {
+        let (token_program, extensions) = if non_transferable {
+            match &self.token_2022_program {
+                Some(token_2022_program) => (
+                    token_2022_program.to_account_info(),
+                    vec![ExtensionType::NonTransferable],
+                ),
+                None => return err!(ErrorCode::MissingToken2022Program),
+            }
+        } else {
+            (self.token_program.to_account_info(), vec![])
+        };
+        let space = ExtensionType::get_account_len::<spl_token_2022::state::Mint>(&extensions);
+
+        let stake_pool_key = self.stake_pool.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[stake_pool_key.as_ref(), b"stakeMint", &[bump]]];
+        let lamports = self.stake_mint.lamports();
+        if lamports == 0 {
+            system_program::create_account(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    CreateAccount {
+                        from: self.payer.to_account_info(),
+                        to: self.stake_mint.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                self.rent.minimum_balance(space),
+                u64::try_from(space).unwrap(),
+                token_program.key,
+            )?;
+        } else {
+            // create_account fails for an address that already holds lamports, which anyone could
+            // send to block the StakePool. Top up the rent, then allocate and assign instead.
+            let required_lamports = self.rent.minimum_balance(space).saturating_sub(lamports);
+            if required_lamports > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        Transfer {
+                            from: self.payer.to_account_info(),
+                            to: self.stake_mint.to_account_info(),
+                        },
+                    ),
+                    required_lamports,
+                )?;
+            }
+            system_program::allocate(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    Allocate {
+                        account_to_allocate: self.stake_mint.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                u64::try_from(space).unwrap(),
+            )?;
+            system_program::assign(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    Assign {
+                        account_to_assign: self.stake_mint.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                token_program.key,
+            )?;
+        }
+
+        if non_transferable {
+            invoke(
+                &spl_token_2022::instruction::initialize_non_transferable_mint(
+                    token_program.key,
+                    self.stake_mint.key,
+                )?,
+                &[self.stake_mint.to_account_info()],
+            )?;
+        }
+
+        token_interface::initialize_mint2(
+            CpiContext::new(
+                token_program,
+                InitializeMint2 {
+                    mint: self.stake_mint.to_account_info(),
+                },
+            ),
+            decimals,
+            &stake_pool_key,
+            None,
+        )
+    }
+}
+
 pub fn handler(
     ctx: Context<InitializeStakePool>,
     nonce: u8,
     max_weight: u64,
     min_duration: u64,
     max_duration: u64,
+    weight_curve: WeightCurve,
+    non_transferable_stake_mint: bool,
 ) -> Result<()> {
     if min_duration > max_duration {
         return Err(ErrorCode::InvalidStakePoolDuration.into());
@@ -79,6 +194,16 @@ pub fn handler(
     if SCALE_FACTOR_BASE > max_weight {
         return Err(ErrorCode::InvalidStakePoolWeight.into());
     }
+    weight_curve.validate(SCALE_FACTOR_BASE, max_weight, min_duration, max_duration)?;
+    ctx.accounts.create_stake_mint(
+        ctx.accounts
+            .mint
+            .decimals
+            .checked_sub(get_digit_shift_by_max_scalar(max_weight))
+            .unwrap_or_default(),
+        non_transferable_stake_mint,
+        *ctx.bumps.get("stake_mint").unwrap(),
+    )?;
     let mut stake_pool = ctx.accounts.stake_pool.load_init()?;
     stake_pool.creator = ctx.accounts.authority.key();
     stake_pool.authority = ctx.accounts.authority.key();
@@ -89,6 +214,7 @@ pub fn handler(
     stake_pool.max_weight = max_weight;
     stake_pool.min_duration = min_duration;
     stake_pool.max_duration = max_duration;
+    stake_pool.weight_curve = WeightCurveData::from(&weight_curve);
     stake_pool.nonce = nonce;
     stake_pool.bump_seed = *ctx.bumps.get("stake_pool").unwrap();
     Ok(())
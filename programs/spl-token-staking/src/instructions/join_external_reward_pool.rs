@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{ExternalRewardPool, RewardCheckpoint, StakeDepositReceipt, u128};
+
+#[derive(Accounts)]
+pub struct JoinExternalRewardPool<'info> {
+  /// Payer of rent
+  #[account(mut)]
+  pub payer: Signer<'info>,
+
+  /// Owner of the StakeDepositReceipt
+  pub owner: Signer<'info>,
+
+  #[account(
+    mut,
+    constraint = !reward_pool.is_retired() @ ErrorCode::RewardPoolRetired,
+  )]
+  pub reward_pool: Account<'info, ExternalRewardPool>,
+
+  /// StakeDepositReceipt joining, extended to count its RewardCheckpoints if needed
+  #[account(
+    mut,
+    has_one = owner @ ErrorCode::InvalidAuthority,
+    constraint = stake_deposit_receipt.stake_pool == reward_pool.stake_pool
+      @ ErrorCode::InvalidStakePool,
+  )]
+  pub stake_deposit_receipt: Account<'info, StakeDepositReceipt>,
+
+  #[account(
+    init,
+    seeds = [
+      reward_pool.key().as_ref(),
+      stake_deposit_receipt.key().as_ref(),
+      b"rewardCheckpoint",
+    ],
+    bump,
+    payer = payer,
+    space = 8 + RewardCheckpoint::LEN,
+  )]
+  pub reward_checkpoint: Account<'info, RewardCheckpoint>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<JoinExternalRewardPool>) -> Result<()> {
+  let reward_pool = &mut ctx.accounts.reward_pool;
+  let reward_checkpoint = &mut ctx.accounts.reward_checkpoint;
+  reward_checkpoint.owner = ctx.accounts.owner.key();
+  reward_checkpoint.reward_pool = reward_pool.key();
+  reward_checkpoint.stake_deposit_receipt = ctx.accounts.stake_deposit_receipt.key();
+  reward_checkpoint.effective_stake = u128::default();
+  // only rewards emitted from now on are earned
+  reward_pool.accrue(Clock::get()?.unix_timestamp);
+  reward_checkpoint.rewards_per_effective_stake = reward_pool.rewards_per_effective_stake;
+  reward_checkpoint.unclaimed = 0;
+  reward_checkpoint.receipt_closed = false;
+  reward_checkpoint.sync(
+    reward_pool,
+    ctx.accounts.stake_deposit_receipt.effective_stake_u128(),
+    Clock::get()?.unix_timestamp,
+  );
+
+  let stake_deposit_receipt = ctx.accounts.stake_deposit_receipt.to_account_info();
+  let count = StakeDepositReceipt::get_reward_checkpoint_count(&stake_deposit_receipt)?;
+  StakeDepositReceipt::set_reward_checkpoint_count(
+    &stake_deposit_receipt,
+    &ctx.accounts.payer.to_account_info(),
+    &ctx.accounts.system_program.to_account_info(),
+    count.checked_add(1).unwrap(),
+  )
+
+}
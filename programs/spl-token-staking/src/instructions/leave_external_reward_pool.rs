@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{ExternalRewardPool, RewardCheckpoint, StakeDepositReceipt};
+
+#[derive(Accounts)]
+pub struct LeaveExternalRewardPool<'info> {
+  /// Owner of the StakeDepositReceipt, receives the rent of the RewardCheckpoint
+  #[account(mut)]
+  pub owner: Signer<'info>,
+
+  #[account(mut)]
+  pub reward_pool: Account<'info, ExternalRewardPool>,
+
+  #[account(
+    mut,
+    close = owner,
+    has_one = owner @ ErrorCode::InvalidAuthority,
+    has_one = reward_pool,
+    has_one = stake_deposit_receipt,
+  )]
+  pub reward_checkpoint: Account<'info, RewardCheckpoint>,
+
+  /// CHECK: StakeDepositReceipt of the RewardCheckpoint, its count of RewardCheckpoints is
+  /// decreased unless it was withdrawn
+  #[account(mut)]
+  pub stake_deposit_receipt: UncheckedAccount<'info>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<LeaveExternalRewardPool>) -> Result<()> {
+  let current_timestamp = Clock::get()?.unix_timestamp;
+  let reward_checkpoint = &mut ctx.accounts.reward_checkpoint;
+  let reward_pool = &mut ctx.accounts.reward_pool;
+  // settle what is owed, then remove the checkpoint's effective stake from the RewardPool
+  let effective_stake =
+    reward_checkpoint.get_receipt_effective_stake(&ctx.accounts.stake_deposit_receipt)?;
+  reward_checkpoint.sync(reward_pool, effective_stake, current_timestamp);
+  // unclaimed rewards are forfeited once the RewardPool could have been swept
+  require!(
+    reward_checkpoint.unclaimed == 0 || reward_pool.is_sweepable(current_timestamp),
+    ErrorCode::RewardCheckpointNotClaimed
+  );
+  reward_checkpoint.sync(reward_pool, 0, current_timestamp);
+  if reward_checkpoint.receipt_closed {
+    // the withdrawn StakeDepositReceipt no longer counts its RewardCheckpoints
+    return Ok(());
+  }
+
+  let stake_deposit_receipt = ctx.accounts.stake_deposit_receipt.to_account_info();
+  let count = StakeDepositReceipt::get_reward_checkpoint_count(&stake_deposit_receipt)?;
+  StakeDepositReceipt::set_reward_checkpoint_count(
+    &stake_deposit_receipt,
+    &ctx.accounts.owner.to_account_info(),
+    &ctx.accounts.system_program.to_account_info(),
+    count.checked_sub(1).unwrap(),
+  )
+}
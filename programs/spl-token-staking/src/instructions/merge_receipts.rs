@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::events::MergeReceiptsEvent;
+use crate::stake_pool_signer_seeds;
+use crate::state::{u128, StakeDepositReceipt, StakePoolLoader};
+
+use super::claim_base::*;
+
+#[derive(Accounts)]
+pub struct MergeReceipts<'info> {
+    /// Contains the destination StakeDepositReceipt that the source will be merged into
+    pub claim_base: ClaimBase<'info>,
+
+    /// StakeDepositReceipt that will be merged into the destination and closed
+    #[account(mut)]
+    pub source_stake_deposit_receipt: Account<'info, StakeDepositReceipt>,
+
+    /// stake_mint of StakePool that will be minted to cover rounding of the merged stake
+    #[account(mut)]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token account the StakePool token will be transfered to
+    #[account(mut)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+}
+
+impl<'info> MergeReceipts<'info> {
+    /// Addiditional validations that rely on the accounts within `claim_base`.
+    pub fn validate_stake_pool_and_owner(&self) -> Result<()> {
+        let stake_pool = self.claim_base.stake_pool.load()?;
+        require!(
+            self.source_stake_deposit_receipt.key() != self.claim_base.stake_deposit_receipt.key(),
+            ErrorCode::DuplicateStakeDepositReceipt
+        );
+        require!(
+            self.source_stake_deposit_receipt.owner == self.claim_base.owner.key(),
+            ErrorCode::InvalidOwner
+        );
+        require!(
+            self.source_stake_deposit_receipt.stake_pool == self.claim_base.stake_pool.key(),
+            ErrorCode::InvalidStakePool
+        );
+        require!(
+            stake_pool.stake_mint.key() == self.stake_mint.key(),
+            ErrorCode::InvalidStakeMint
+        );
+        require!(
+            self.destination.owner.key() == self.claim_base.owner.key(),
+            ErrorCode::InvalidAuthority
+        );
+        Ok(())
+    }
+
+    pub fn mint_staked_token_to_user(&self, effective_amount: u64) -> Result<()> {
+        let stake_pool = self.claim_base.stake_pool.load()?;
+        let signer_seeds: &[&[&[u8]]] = &[stake_pool_signer_seeds!(stake_pool)];
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.claim_base
+                .stake_mint_token_program(&self.stake_mint.to_account_info())?,
+            MintTo {
+                mint: self.stake_mint.to_account_info(),
+                to: self.destination.to_account_info(),
+                authority: self.claim_base.stake_pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        token_interface::mint_to(cpi_ctx, effective_amount)
+    }
+
+    pub fn close_source_stake_deposit_receipt(&self) -> Result<()> {
+        self.source_stake_deposit_receipt
+            .close(self.claim_base.owner.to_account_info())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, MergeReceipts<'info>>) -> Result<()> {
+    ctx.accounts.validate_stake_pool_and_owner()?;
+    let source_stake_deposit_receipt_info =
+        ctx.accounts.source_stake_deposit_receipt.to_account_info();
+    StakeDepositReceipt::validate_no_reward_checkpoints(&source_stake_deposit_receipt_info)?;
+    // the destination may be locked, the merged stake then stays locked with it
+    StakeDepositReceipt::validate_not_voting(&source_stake_deposit_receipt_info)?;
+
+    let max_weight: u64;
+    let deposits_ignores_lp: bool;
+    {
+        let mut stake_pool = ctx.accounts.claim_base.stake_pool.load_mut_with_extensions()?;
+        max_weight = stake_pool.max_weight;
+        deposits_ignores_lp = stake_pool.deposits_ignores_lp();
+        stake_pool.recalculate_rewards_per_effective_stake(ctx.remaining_accounts, 2usize)?;
+    }
+
+    // claim all unclaimed rewards of both receipts, so the merged receipt starts from the
+    // latest `rewards_per_effective_stake`
+    let claimed_amounts = ctx
+        .accounts
+        .claim_base
+        .transfer_all_claimable_rewards_of_receipts(
+            &[
+                &ctx.accounts.claim_base.stake_deposit_receipt,
+                &ctx.accounts.source_stake_deposit_receipt,
+            ],
+            ctx.remaining_accounts,
+        )?;
+
+    ctx.accounts
+        .claim_base
+        .update_reward_pools_last_amount(claimed_amounts)?;
+
+    let source = &ctx.accounts.source_stake_deposit_receipt;
+    let stake_deposit_receipt = &mut ctx.accounts.claim_base.stake_deposit_receipt;
+    let destination_stake_tokens = StakeDepositReceipt::get_token_amount_from_stake(
+        stake_deposit_receipt.effective_stake_u128(),
+        max_weight,
+    );
+    let source_stake_tokens =
+        StakeDepositReceipt::get_token_amount_from_stake(source.effective_stake_u128(), max_weight);
+
+    // The merged receipt unlocks at the later of the two unlock times.
+    if source.get_unlock_timestamp() > stake_deposit_receipt.get_unlock_timestamp() {
+        stake_deposit_receipt.deposit_timestamp = source.deposit_timestamp;
+        stake_deposit_receipt.lockup_duration = source.lockup_duration;
+    }
+    stake_deposit_receipt.deposit_amount = stake_deposit_receipt
+        .deposit_amount
+        .checked_add(source.deposit_amount)
+        .unwrap();
+    let merged_effective_stake = stake_deposit_receipt
+        .effective_stake_u128()
+        .checked_add(source.effective_stake_u128())
+        .unwrap();
+    stake_deposit_receipt.effective_stake = u128(merged_effective_stake.to_le_bytes());
+
+    if !deposits_ignores_lp {
+        // The merged token representation may exceed the sum of both due to rounding. Mint the
+        // difference so a later withdraw can burn the merged amount.
+        let rounding_amount =
+            StakeDepositReceipt::get_token_amount_from_stake(merged_effective_stake, max_weight)
+                .checked_sub(destination_stake_tokens)
+                .unwrap()
+                .checked_sub(source_stake_tokens)
+                .unwrap();
+        if rounding_amount > 0 {
+            ctx.accounts.mint_staked_token_to_user(rounding_amount)?;
+        }
+    }
+
+    ctx.accounts.close_source_stake_deposit_receipt()?;
+
+    let stake_deposit_receipt = &ctx.accounts.claim_base.stake_deposit_receipt;
+    emit!(MergeReceiptsEvent {
+        stake_pool: ctx.accounts.claim_base.stake_pool.key(),
+        owner: stake_deposit_receipt.owner,
+        stake_deposit_receipt: stake_deposit_receipt.key(),
+        source_stake_deposit_receipt: ctx.accounts.source_stake_deposit_receipt.key(),
+        effective_stake: merged_effective_stake,
+    });
+    Ok(())
+}
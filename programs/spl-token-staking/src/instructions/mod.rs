@@ -1,21 +1,87 @@
+pub mod accept_authority;
+pub mod add_external_reward_pool;
 pub mod add_reward_pool;
+pub mod cancel_authority_transfer;
 pub mod claim_all;
 pub mod claim_base;
+pub mod claim_external_reward_pool;
+pub mod claim_selected;
+pub mod compound;
+pub mod crank_claim;
+pub mod create_max_voter_weight_record;
+pub mod create_voter_weight_record;
 pub mod dangerously_mint_stake_mint;
 pub mod deposit;
+pub mod disable_flags;
+pub mod enable_flags;
+pub mod extend_lockup;
+pub mod fund_external_reward_pool;
+pub mod fund_reward_pool;
 pub mod initialize_stake_pool;
+pub mod join_external_reward_pool;
+pub mod leave_external_reward_pool;
+pub mod merge_receipts;
+pub mod propose_authority;
+pub mod retire_external_reward_pool;
+pub mod retire_reward_pool;
+pub mod revoke_claim_delegate;
+pub mod set_claim_delegate;
+pub mod set_early_withdraw_penalty;
 pub mod set_flags;
+pub mod set_reward_pool_emission;
+pub mod set_role;
+pub mod split_receipt;
+pub mod sweep_external_reward_pool;
+pub mod sweep_reward_pool;
+pub mod sync_reward_checkpoint;
 pub mod transfer_authority;
+pub mod transfer_receipt;
+pub mod update_pool_config;
+pub mod update_max_voter_weight_record;
 pub mod update_token_meta;
+pub mod update_voter_weight_record;
 pub mod withdraw;
+pub mod withdraw_partial;
 
+pub use accept_authority::*;
+pub use add_external_reward_pool::*;
 pub use add_reward_pool::*;
+pub use cancel_authority_transfer::*;
 pub use claim_all::*;
 pub use claim_base::*;
+pub use claim_external_reward_pool::*;
+pub use claim_selected::*;
+pub use compound::*;
+pub use crank_claim::*;
+pub use create_max_voter_weight_record::*;
+pub use create_voter_weight_record::*;
 pub use dangerously_mint_stake_mint::*;
 pub use deposit::*;
+pub use extend_lockup::*;
+pub use fund_external_reward_pool::*;
+pub use fund_reward_pool::*;
 pub use initialize_stake_pool::*;
+pub use join_external_reward_pool::*;
+pub use leave_external_reward_pool::*;
+pub use merge_receipts::*;
+pub use propose_authority::*;
+pub use retire_external_reward_pool::*;
+pub use retire_reward_pool::*;
+pub use revoke_claim_delegate::*;
+pub use set_claim_delegate::*;
+pub use set_early_withdraw_penalty::*;
 pub use set_flags::*;
+pub use set_reward_pool_emission::*;
+pub use set_role::*;
+pub use split_receipt::*;
+pub use sweep_external_reward_pool::*;
+pub use sweep_reward_pool::*;
+pub use sync_reward_checkpoint::*;
 pub use transfer_authority::*;
+pub use transfer_receipt::*;
+pub use update_pool_config::*;
+pub use update_max_voter_weight_record::*;
 pub use update_token_meta::*;
+pub use update_voter_weight_record::*;
 pub use withdraw::*;
+pub use withdraw_partial::*;
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+  errors::ErrorCode,
+  events::AuthorityTransferProposed,
+  state::StakePool,
+};
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+  /// Current authority of the StakePool
+  pub authority: Signer<'info>,
+
+  /// CHECK: No check required, the new authority must sign `accept_authority`
+  pub new_authority: UncheckedAccount<'info>,
+
+  /// StakePool that will have it's authority transferred
+  #[account(
+    mut,
+    has_one = authority @ ErrorCode::InvalidAuthority,
+  )]
+  pub stake_pool: AccountLoader<'info, StakePool>,
+}
+
+pub fn handler(ctx: Context<ProposeAuthority>) -> Result<()> {
+  let mut stake_pool = ctx.accounts.stake_pool.load_mut()?;
+  stake_pool.pending_authority = ctx.accounts.new_authority.key();
+
+  emit!(AuthorityTransferProposed {
+    stake_pool: ctx.accounts.stake_pool.key(),
+    authority: stake_pool.authority,
+    pending_authority: stake_pool.pending_authority,
+  });
+  Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{ExternalRewardPool, StakePool};
+
+#[derive(Accounts)]
+pub struct RetireExternalRewardPool<'info> {
+  /// Authority of the StakePool
+  pub authority: Signer<'info>,
+
+  /// StakePool the ExternalRewardPool belongs to
+  #[account(has_one = authority @ ErrorCode::InvalidAuthority)]
+  pub stake_pool: AccountLoader<'info, StakePool>,
+
+  #[account(
+    mut,
+    has_one = stake_pool @ ErrorCode::InvalidStakePool,
+    constraint = !reward_pool.is_retired() @ ErrorCode::RewardPoolRetired,
+  )]
+  pub reward_pool: Account<'info, ExternalRewardPool>,
+}
+
+pub fn handler(ctx: Context<RetireExternalRewardPool>) -> Result<()> {
+  let current_timestamp = Clock::get()?.unix_timestamp;
+  let reward_pool = &mut ctx.accounts.reward_pool;
+  // distribute what was emitted until now, the rest is left to be swept
+  reward_pool.accrue(current_timestamp);
+  reward_pool.stop_emission();
+  reward_pool.retired_at = current_timestamp;
+  Ok(())
+}
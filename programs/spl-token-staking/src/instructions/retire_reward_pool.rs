@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::RewardPoolRetired;
+use crate::state::{StakePool, StakePoolLoader};
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct RetireRewardPool<'info> {
+  /// Authority of the StakePool
+  pub authority: Signer<'info>,
+
+  /// StakePool the RewardPool belongs to
+  #[account(
+    mut,
+    has_one = authority @ ErrorCode::InvalidAuthority,
+    constraint = !stake_pool.load()?.reward_pools[usize::from(index)].is_empty()
+      @ ErrorCode::RewardPoolIndexEmpty,
+    constraint = !stake_pool.load()?.reward_pools[usize::from(index)].is_retired()
+      @ ErrorCode::RewardPoolRetired,
+  )]
+  pub stake_pool: AccountLoader<'info, StakePool>,
+}
+
+pub fn handler<'info>(
+  ctx: Context<'_, '_, '_, 'info, RetireRewardPool<'info>>,
+  index: u8,
+) -> Result<()> {
+  let mut stake_pool = ctx.accounts.stake_pool.load_mut_with_extensions()?;
+  // Credit rewards that were transferred in prior to retirement to the current stakers
+  stake_pool.recalculate_rewards_per_effective_stake(ctx.remaining_accounts, 1usize)?;
+  let retired_at = Clock::get()?.unix_timestamp;
+  stake_pool.reward_pools[usize::from(index)].retired_at = retired_at;
+
+  emit!(RewardPoolRetired {
+    stake_pool: ctx.accounts.stake_pool.key(),
+    reward_pool_index: index,
+    retired_at,
+  });
+  Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ErrorCode, state::ClaimDelegate};
+
+#[derive(Accounts)]
+pub struct RevokeClaimDelegate<'info> {
+  /// Owner of the StakeDepositReceipts, receives the ClaimDelegate rent
+  #[account(mut)]
+  pub owner: Signer<'info>,
+
+  #[account(
+    mut,
+    close = owner,
+    has_one = owner @ ErrorCode::InvalidOwner,
+  )]
+  pub claim_delegate: Account<'info, ClaimDelegate>,
+}
+
+pub fn handler(_ctx: Context<RevokeClaimDelegate>) -> Result<()> {
+  Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ClaimDelegate, StakePool};
+
+#[derive(Accounts)]
+pub struct SetClaimDelegate<'info> {
+  /// Owner of the StakeDepositReceipts, pays for the ClaimDelegate
+  #[account(mut)]
+  pub owner: Signer<'info>,
+
+  pub stake_pool: AccountLoader<'info, StakePool>,
+
+  #[account(
+    init,
+    payer = owner,
+    space = 8 + ClaimDelegate::LEN,
+    seeds = [
+      owner.key().as_ref(),
+      stake_pool.key().as_ref(),
+      b"claimDelegate".as_ref(),
+    ],
+    bump,
+  )]
+  pub claim_delegate: Account<'info, ClaimDelegate>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetClaimDelegate>, delegate: Pubkey) -> Result<()> {
+  let claim_delegate = &mut ctx.accounts.claim_delegate;
+  claim_delegate.owner = ctx.accounts.owner.key();
+  claim_delegate.stake_pool = ctx.accounts.stake_pool.key();
+  claim_delegate.delegate = delegate;
+  Ok(())
+}
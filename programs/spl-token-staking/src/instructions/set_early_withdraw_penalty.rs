@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::events::EarlyWithdrawPenaltySet;
+use crate::state::{StakePool, BPS_DENOMINATOR};
+
+#[derive(Accounts)]
+pub struct SetEarlyWithdrawPenalty<'info> {
+  /// Authority of the StakePool
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority @ ErrorCode::InvalidAuthority,
+  )]
+  pub stake_pool: AccountLoader<'info, StakePool>,
+
+  /// Token Account of the StakePool's `mint` to receive early withdraw penalties
+  #[account(
+    constraint = treasury.mint == stake_pool.load()?.mint @ ErrorCode::InvalidTreasury,
+  )]
+  pub treasury: InterfaceAccount<'info, TokenAccount>,
+}
+
+pub fn handler(ctx: Context<SetEarlyWithdrawPenalty>, penalty_bps: u16) -> Result<()> {
+  if u64::from(penalty_bps) > BPS_DENOMINATOR {
+    return err!(ErrorCode::InvalidEarlyWithdrawPenalty);
+  }
+  let mut stake_pool = ctx.accounts.stake_pool.load_mut()?;
+  stake_pool.early_withdraw_penalty_bps = penalty_bps;
+  stake_pool.treasury = ctx.accounts.treasury.key();
+
+  emit!(EarlyWithdrawPenaltySet {
+    stake_pool: ctx.accounts.stake_pool.key(),
+    penalty_bps,
+    treasury: stake_pool.treasury,
+  });
+  Ok(())
+}
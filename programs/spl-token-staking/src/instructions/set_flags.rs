@@ -2,25 +2,42 @@ use anchor_lang::prelude::*;
 
 use crate::{
   errors::ErrorCode,
-  state::StakePool,
+  events::FlagsChanged,
+  state::{PoolFlags, StakePool},
 };
 
 #[derive(Accounts)]
 pub struct SetFlags<'info> {
-  /// Current authority of the StakePool
+  /// Current authority of the StakePool, or its pauser when only changing `PAUSER_FLAGS`
   #[account(mut)]
   pub authority: Signer<'info>,
 
 
-  #[account(
-    mut, 
-    has_one = authority @ ErrorCode::InvalidAuthority,
-  )]
+  #[account(mut)]
   pub stake_pool: AccountLoader<'info, StakePool>,
 }
 
+impl<'info> SetFlags<'info> {
+  /// Replace the StakePool's flags, if the signer is allowed to make the change.
+  pub fn update_flags(&self, flags: u8) -> Result<()> {
+    let mut stake_pool = self.stake_pool.load_mut()?;
+    if !stake_pool.can_set_flags(&self.authority.key(), flags) {
+      return err!(ErrorCode::InvalidAuthority);
+    }
+    let old_flags = stake_pool.flags;
+    stake_pool.flags = flags;
+
+    emit!(FlagsChanged {
+      stake_pool: self.stake_pool.key(),
+      authority: self.authority.key(),
+      old_flags,
+      new_flags: stake_pool.flags,
+    });
+    Ok(())
+  }
+}
+
 pub fn handler(ctx: Context<SetFlags>, flags: u8) -> Result<()> {
-  let mut stake_pool = ctx.accounts.stake_pool.load_mut()?;
-  stake_pool.flags = flags;
-  Ok(())
+  let flags = PoolFlags::from_bits(flags).ok_or(ErrorCode::InvalidFlags)?;
+  ctx.accounts.update_flags(flags.bits())
 }
\ No newline at end of file
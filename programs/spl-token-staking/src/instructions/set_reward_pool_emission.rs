@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::RewardPoolEmissionSet;
+use crate::state::{StakePool, StakePoolLoader};
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct SetRewardPoolEmission<'info> {
+  /// Authority or reward manager of the StakePool
+  pub authority: Signer<'info>,
+
+  /// Payer of the rent when the StakePool is extended to store the emission schedule
+  #[account(mut)]
+  pub payer: Signer<'info>,
+
+  /// StakePool the RewardPool belongs to
+  #[account(
+    mut,
+    constraint = stake_pool.load()?.is_reward_manager(&authority.key()) @ ErrorCode::InvalidAuthority,
+    constraint = !stake_pool.load()?.reward_pools[usize::from(index)].is_empty()
+      @ ErrorCode::RewardPoolIndexEmpty,
+    constraint = !stake_pool.load()?.reward_pools[usize::from(index)].is_retired()
+      @ ErrorCode::RewardPoolRetired,
+  )]
+  pub stake_pool: AccountLoader<'info, StakePool>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(
+  ctx: Context<'_, '_, '_, 'info, SetRewardPoolEmission<'info>>,
+  index: u8,
+  rewards_per_second: u64,
+  start_time: i64,
+  end_time: i64,
+) -> Result<()> {
+  let current_timestamp = Clock::get()?.unix_timestamp;
+  if end_time <= start_time || end_time <= current_timestamp {
+    return err!(ErrorCode::InvalidEmissionSchedule);
+  }
+
+  ctx.accounts.stake_pool.extend(
+    &ctx.accounts.payer.to_account_info(),
+    &ctx.accounts.system_program.to_account_info(),
+  )?;
+  let mut stake_pool = ctx.accounts.stake_pool.load_mut_with_extensions()?;
+  // Credit everything emitted under the previous schedule before replacing it
+  stake_pool.recalculate_rewards_per_effective_stake(ctx.remaining_accounts, 1usize)?;
+  let extension = stake_pool.reward_pool_extension_mut(usize::from(index))?;
+  extension.rewards_per_second = rewards_per_second;
+  extension.start_time = start_time;
+  extension.end_time = end_time;
+  extension.last_update_time = current_timestamp;
+
+  emit!(RewardPoolEmissionSet {
+    stake_pool: ctx.accounts.stake_pool.key(),
+    reward_pool_index: index,
+    rewards_per_second,
+    start_time,
+    end_time,
+  });
+  Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+  errors::ErrorCode,
+  events::RoleSet,
+  state::{StakePool, StakePoolRole},
+};
+
+#[derive(Accounts)]
+pub struct SetRole<'info> {
+  /// Current authority of the StakePool
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority @ ErrorCode::InvalidAuthority,
+  )]
+  pub stake_pool: AccountLoader<'info, StakePool>,
+}
+
+pub fn handler(ctx: Context<SetRole>, role: StakePoolRole, key: Pubkey) -> Result<()> {
+  let mut stake_pool = ctx.accounts.stake_pool.load_mut()?;
+  match role {
+    StakePoolRole::Pauser => stake_pool.pauser = key,
+    StakePoolRole::RewardManager => stake_pool.reward_manager = key,
+    StakePoolRole::MetadataAdmin => stake_pool.metadata_admin = key,
+  }
+
+  emit!(RoleSet {
+    stake_pool: ctx.accounts.stake_pool.key(),
+    role,
+    key,
+  });
+  Ok(())
+}
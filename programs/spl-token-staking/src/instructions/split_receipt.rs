@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::events::SplitReceiptEvent;
+use crate::state::{u128, RewardCheckpoint, StakeDepositReceipt, StakePoolLoader};
+
+use super::claim_base::*;
+
+#[derive(Accounts)]
+#[instruction(amount: u64, new_nonce: u32)]
+pub struct SplitReceipt<'info> {
+    /// Contains the StakeDepositReceipt that will be split
+    pub claim_base: ClaimBase<'info>,
+
+    /// Payer of rent for the new StakeDepositReceipt
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// StakeDepositReceipt that will receive the split portion of the stake
+    #[account(
+      init,
+      seeds = [
+        claim_base.owner.key().as_ref(),
+        claim_base.stake_pool.key().as_ref(),
+        &new_nonce.to_le_bytes(),
+        b"stakeDepositReceipt",
+      ],
+      bump,
+      payer = payer,
+      space = 8 + StakeDepositReceipt::LEN,
+    )]
+    pub new_stake_deposit_receipt: Account<'info, StakeDepositReceipt>,
+
+    /// stake_mint of StakePool that will be burned to cover rounding of the split stake
+    #[account(mut)]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token Account holding weighted stake representation token to burn
+    #[account(mut)]
+    pub from: InterfaceAccount<'info, TokenAccount>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SplitReceipt<'info> {
+    /// Additional validations that rely on the accounts within `claim_base`.
+    pub fn validate_stake_mint(&self) -> Result<()> {
+        let stake_pool = self.claim_base.stake_pool.load()?;
+        require!(
+            stake_pool.stake_mint.key() == self.stake_mint.key(),
+            ErrorCode::InvalidStakeMint
+        );
+        Ok(())
+    }
+
+    pub fn burn_stake_weight_tokens_from_owner(&self, amount: u64) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            self.claim_base
+                .stake_mint_token_program(&self.stake_mint.to_account_info())?,
+            Burn {
+                mint: self.stake_mint.to_account_info(),
+                from: self.from.to_account_info(),
+                authority: self.claim_base.owner.to_account_info(),
+            },
+        );
+        token_interface::burn(cpi_ctx, amount)
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, SplitReceipt<'info>>,
+    amount: u64,
+    _new_nonce: u32,
+) -> Result<()> {
+    if amount == 0 || amount >= ctx.accounts.claim_base.stake_deposit_receipt.deposit_amount {
+        return err!(ErrorCode::InvalidSplitAmount);
+    }
+    ctx.accounts.validate_stake_mint()?;
+
+    let stake_deposit_receipt = &ctx.accounts.claim_base.stake_deposit_receipt;
+    StakeDepositReceipt::validate_not_voting(&stake_deposit_receipt.to_account_info())?;
+    let effective_stake_moved = stake_deposit_receipt.get_effective_stake_for_amount(amount);
+    let effective_stake_remaining = stake_deposit_receipt
+        .effective_stake_u128()
+        .checked_sub(effective_stake_moved)
+        .unwrap();
+    // the new receipt has no RewardCheckpoints, the existing ones keep the remaining stake
+    let remaining_accounts = RewardCheckpoint::sync_all(
+        &stake_deposit_receipt.to_account_info(),
+        ctx.remaining_accounts,
+        effective_stake_remaining,
+        false,
+    )?;
+
+    let max_weight: u64;
+    let withdraw_ignores_lp: bool;
+    {
+        let mut stake_pool = ctx.accounts.claim_base.stake_pool.load_mut_with_extensions()?;
+        max_weight = stake_pool.max_weight;
+        withdraw_ignores_lp = stake_pool.withdraw_ignores_lp();
+        stake_pool.recalculate_rewards_per_effective_stake(remaining_accounts, 2usize)?;
+    }
+
+    // claim all unclaimed rewards, so both receipts start from the latest `rewards_per_effective_stake`
+    let claimed_amounts = ctx
+        .accounts
+        .claim_base
+        .transfer_all_claimable_rewards(remaining_accounts)?;
+
+    ctx.accounts
+        .claim_base
+        .update_reward_pools_last_amount(claimed_amounts)?;
+
+    let stake_deposit_receipt = &mut ctx.accounts.claim_base.stake_deposit_receipt;
+    stake_deposit_receipt.deposit_amount = stake_deposit_receipt
+        .deposit_amount
+        .checked_sub(amount)
+        .unwrap();
+    stake_deposit_receipt.effective_stake = u128(effective_stake_remaining.to_le_bytes());
+
+    // The new receipt keeps the same lockup end, so the split cannot be used to unlock early.
+    let new_stake_deposit_receipt = &mut ctx.accounts.new_stake_deposit_receipt;
+    new_stake_deposit_receipt.owner = stake_deposit_receipt.owner;
+    new_stake_deposit_receipt.payer = ctx.accounts.payer.key();
+    new_stake_deposit_receipt.stake_pool = stake_deposit_receipt.stake_pool;
+    new_stake_deposit_receipt.lockup_duration = stake_deposit_receipt.lockup_duration;
+    new_stake_deposit_receipt.deposit_timestamp = stake_deposit_receipt.deposit_timestamp;
+    new_stake_deposit_receipt.deposit_amount = amount;
+    new_stake_deposit_receipt.effective_stake = u128(effective_stake_moved.to_le_bytes());
+    new_stake_deposit_receipt.claimed_amounts = stake_deposit_receipt.claimed_amounts;
+
+    if !withdraw_ignores_lp {
+        // The token representations of both receipts may sum to less than the original due to
+        // rounding. Burn the difference so withdrawing both burns exactly what was minted. Not
+        // needed when withdraws do not burn the stake_mint.
+        let rounding_amount = StakeDepositReceipt::get_token_amount_from_stake(
+            effective_stake_remaining.checked_add(effective_stake_moved).unwrap(),
+            max_weight,
+        )
+        .checked_sub(StakeDepositReceipt::get_token_amount_from_stake(
+            effective_stake_remaining,
+            max_weight,
+        ))
+        .unwrap()
+        .checked_sub(StakeDepositReceipt::get_token_amount_from_stake(
+            effective_stake_moved,
+            max_weight,
+        ))
+        .unwrap();
+        if rounding_amount > 0 {
+            ctx.accounts.burn_stake_weight_tokens_from_owner(rounding_amount)?;
+        }
+    }
+
+    let new_stake_deposit_receipt = &ctx.accounts.new_stake_deposit_receipt;
+    emit!(SplitReceiptEvent {
+        stake_pool: ctx.accounts.claim_base.stake_pool.key(),
+        owner: new_stake_deposit_receipt.owner,
+        stake_deposit_receipt: ctx.accounts.claim_base.stake_deposit_receipt.key(),
+        new_stake_deposit_receipt: new_stake_deposit_receipt.key(),
+        amount,
+        effective_stake: effective_stake_moved,
+    });
+    Ok(())
+}
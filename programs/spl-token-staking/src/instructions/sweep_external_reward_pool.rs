@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+  self, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::errors::ErrorCode;
+use crate::stake_pool_signer_seeds;
+use crate::state::{ExternalRewardPool, StakePool};
+
+#[derive(Accounts)]
+pub struct SweepExternalRewardPool<'info> {
+  /// Authority of the StakePool
+  pub authority: Signer<'info>,
+
+  /// StakePool the ExternalRewardPool belongs to, owner of the `reward_vault`
+  #[account(has_one = authority @ ErrorCode::InvalidAuthority)]
+  pub stake_pool: AccountLoader<'info, StakePool>,
+
+  #[account(
+    has_one = stake_pool @ ErrorCode::InvalidStakePool,
+    has_one = reward_vault @ ErrorCode::InvalidRewardPoolVault,
+  )]
+  pub reward_pool: Account<'info, ExternalRewardPool>,
+
+  #[account(mut)]
+  pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+  /// Mint of the ExternalRewardPool's `reward_vault`
+  #[account(address = reward_pool.reward_mint @ ErrorCode::InvalidMint)]
+  pub reward_mint: InterfaceAccount<'info, Mint>,
+
+  /// Token account the remaining rewards will be transferred to
+  #[account(mut)]
+  pub destination: InterfaceAccount<'info, TokenAccount>,
+
+  /// Token program of the `reward_mint`, either SPL Token or Token-2022
+  #[account(address = reward_pool.token_program)]
+  pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SweepExternalRewardPool<'info> {
+  /// Transfer the entire remaining balance of the reward vault to the destination.
+  pub fn transfer_remaining_rewards_to_destination(&self) -> Result<()> {
+    let stake_pool = self.stake_pool.load()?;
+    let signer_seeds: &[&[&[u8]]] = &[stake_pool_signer_seeds!(stake_pool)];
+    let cpi_ctx = CpiContext::new_with_signer(
+      self.token_program.to_account_info(),
+      TransferChecked {
+        from: self.reward_vault.to_account_info(),
+        mint: self.reward_mint.to_account_info(),
+        to: self.destination.to_account_info(),
+        authority: self.stake_pool.to_account_info(),
+      },
+      signer_seeds,
+    );
+    token_interface::transfer_checked(
+      cpi_ctx,
+      self.reward_vault.amount,
+      self.reward_mint.decimals,
+    )
+  }
+}
+
+pub fn handler(ctx: Context<SweepExternalRewardPool>) -> Result<()> {
+  if !ctx.accounts.reward_pool.is_sweepable(Clock::get()?.unix_timestamp) {
+    return err!(ErrorCode::RewardPoolNotSweepable);
+  }
+  if ctx.accounts.reward_vault.amount > 0 {
+    ctx.accounts.transfer_remaining_rewards_to_destination()?;
+  }
+  Ok(())
+}
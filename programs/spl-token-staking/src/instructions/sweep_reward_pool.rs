@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+  self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::errors::ErrorCode;
+use crate::events::RewardPoolSwept;
+use crate::stake_pool_signer_seeds;
+use crate::state::{RewardPool, RewardPoolExtension, StakePool, StakePoolLoader};
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct SweepRewardPool<'info> {
+  /// Authority of the StakePool, receives the rent of the closed `reward_vault` and pays for
+  /// extending the StakePool
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  /// StakePool the RewardPool belongs to
+  #[account(
+    mut,
+    has_one = authority @ ErrorCode::InvalidAuthority,
+  )]
+  pub stake_pool: AccountLoader<'info, StakePool>,
+
+  /// Vault of the retired RewardPool, closed after sweeping
+  #[account(
+    mut,
+    address = stake_pool.load()?.reward_pools[usize::from(index)].reward_vault
+      @ ErrorCode::InvalidRewardPoolVault,
+  )]
+  pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+  /// Mint of the RewardPool's `reward_vault`
+  #[account(address = reward_vault.mint @ ErrorCode::InvalidMint)]
+  pub reward_mint: InterfaceAccount<'info, Mint>,
+
+  /// Token account the remaining rewards will be transferred to
+  #[account(mut)]
+  pub destination: InterfaceAccount<'info, TokenAccount>,
+
+  /// Token program of the `reward_mint`, either SPL Token or Token-2022
+  pub token_program: Interface<'info, TokenInterface>,
+  pub system_program: Program<'info, System>,
+}
+
+impl<'info> SweepRewardPool<'info> {
+  /// Transfer the entire remaining balance of the reward vault to the destination.
+  pub fn transfer_remaining_rewards_to_destination(&self) -> Result<()> {
+    let stake_pool = self.stake_pool.load()?;
+    let signer_seeds: &[&[&[u8]]] = &[stake_pool_signer_seeds!(stake_pool)];
+    let cpi_ctx = CpiContext::new_with_signer(
+      self.token_program.to_account_info(),
+      TransferChecked {
+        from: self.reward_vault.to_account_info(),
+        mint: self.reward_mint.to_account_info(),
+        to: self.destination.to_account_info(),
+        authority: self.stake_pool.to_account_info(),
+      },
+      signer_seeds,
+    );
+    token_interface::transfer_checked(
+      cpi_ctx,
+      self.reward_vault.amount,
+      self.reward_mint.decimals,
+    )
+  }
+
+  /// Close the empty reward vault, so a RewardPool for the same mint can be added again.
+  pub fn close_reward_vault(&self) -> Result<()> {
+    let stake_pool = self.stake_pool.load()?;
+    let signer_seeds: &[&[&[u8]]] = &[stake_pool_signer_seeds!(stake_pool)];
+    let cpi_ctx = CpiContext::new_with_signer(
+      self.token_program.to_account_info(),
+      CloseAccount {
+        account: self.reward_vault.to_account_info(),
+        destination: self.authority.to_account_info(),
+        authority: self.stake_pool.to_account_info(),
+      },
+      signer_seeds,
+    );
+    token_interface::close_account(cpi_ctx)
+  }
+}
+
+pub fn handler(ctx: Context<SweepRewardPool>, reward_pool_index: u8) -> Result<()> {
+  let index = reward_pool_index;
+  {
+    let stake_pool = ctx.accounts.stake_pool.load()?;
+    if !stake_pool.reward_pools[usize::from(index)].is_sweepable(Clock::get()?.unix_timestamp) {
+      return err!(ErrorCode::RewardPoolNotSweepable);
+    }
+  }
+
+  let amount = ctx.accounts.reward_vault.amount;
+  if amount > 0 {
+    ctx.accounts.transfer_remaining_rewards_to_destination()?;
+  }
+  ctx.accounts.close_reward_vault()?;
+
+  ctx.accounts.stake_pool.extend(
+    &ctx.accounts.authority.to_account_info(),
+    &ctx.accounts.system_program.to_account_info(),
+  )?;
+  let mut stake_pool = ctx.accounts.stake_pool.load_mut_with_extensions()?;
+  let index = usize::from(index);
+  // Any later RewardPool in this slot continues from the final `rewards_per_effective_stake`,
+  // so unclaimed amounts of this RewardPool can never be claimed from it.
+  let rewards_per_effective_stake = stake_pool.reward_pools[index].rewards_per_effective_stake;
+  *stake_pool.reward_pool_extension_mut(index)? =
+    RewardPoolExtension::new(rewards_per_effective_stake);
+  stake_pool.reward_pools[index] = RewardPool::default();
+
+  emit!(RewardPoolSwept {
+    stake_pool: ctx.accounts.stake_pool.key(),
+    reward_pool_index,
+    destination: ctx.accounts.destination.key(),
+    amount,
+  });
+  Ok(())
+}
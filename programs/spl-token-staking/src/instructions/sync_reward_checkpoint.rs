@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ExternalRewardPool, RewardCheckpoint};
+
+#[derive(Accounts)]
+pub struct SyncRewardCheckpoint<'info> {
+  #[account(mut)]
+  pub reward_pool: Account<'info, ExternalRewardPool>,
+
+  #[account(
+    mut,
+    has_one = reward_pool,
+    has_one = stake_deposit_receipt,
+  )]
+  pub reward_checkpoint: Account<'info, RewardCheckpoint>,
+
+  /// CHECK: StakeDepositReceipt of the RewardCheckpoint, closed once it was withdrawn
+  pub stake_deposit_receipt: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<SyncRewardCheckpoint>) -> Result<()> {
+  let effective_stake = ctx
+    .accounts
+    .reward_checkpoint
+    .get_receipt_effective_stake(&ctx.accounts.stake_deposit_receipt)?;
+  ctx.accounts.reward_checkpoint.sync(
+    &mut ctx.accounts.reward_pool,
+    effective_stake,
+    Clock::get()?.unix_timestamp,
+  );
+  Ok(())
+}
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::{
   errors::ErrorCode,
+  events::AuthorityTransferred,
   state::StakePool,
 };
 
@@ -24,6 +25,14 @@ pub struct TransferAuthority<'info> {
 
 pub fn handler(ctx: Context<TransferAuthority>) -> Result<()> {
   let mut stake_pool = ctx.accounts.stake_pool.load_mut()?;
+  let old_authority = stake_pool.authority;
   stake_pool.authority = ctx.accounts.new_authority.key();
+  stake_pool.pending_authority = Pubkey::default();
+
+  emit!(AuthorityTransferred {
+    stake_pool: ctx.accounts.stake_pool.key(),
+    old_authority,
+    new_authority: stake_pool.authority,
+  });
   Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, Burn, Mint, MintTo, TokenAccount, TransferChecked};
+
+use crate::errors::ErrorCode;
+use crate::events::TransferReceiptEvent;
+use crate::stake_pool_signer_seeds;
+use crate::state::{StakeDepositReceipt, StakePoolLoader};
+
+use super::claim_base::*;
+
+#[derive(Accounts)]
+#[instruction(new_nonce: u32)]
+pub struct TransferReceipt<'info> {
+    /// Contains the StakeDepositReceipt that will be transferred and closed
+    pub claim_base: ClaimBase<'info>,
+
+    /// Payer of rent for the new StakeDepositReceipt
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Owner of the new StakeDepositReceipt.
+    /// CHECK: No check needed since this account will own the StakeReceipt.
+    pub new_owner: UncheckedAccount<'info>,
+
+    /// StakeDepositReceipt of the new owner that the position is moved to
+    #[account(
+      init,
+      seeds = [
+        new_owner.key().as_ref(),
+        claim_base.stake_pool.key().as_ref(),
+        &new_nonce.to_le_bytes(),
+        b"stakeDepositReceipt",
+      ],
+      bump,
+      payer = payer,
+      space = 8 + StakeDepositReceipt::LEN,
+    )]
+    pub new_stake_deposit_receipt: Account<'info, StakeDepositReceipt>,
+
+    /// stake_mint of the StakePool
+    #[account(mut)]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token Account holding weighted stake representation token to transfer
+    #[account(mut)]
+    pub from: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token account of the new owner the weighted stake representation token will be transferred to
+    #[account(mut)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TransferReceipt<'info> {
+    /// Addiditional validations that rely on the accounts within `claim_base`.
+    pub fn validate_stake_mint_accounts(&self) -> Result<()> {
+        let stake_pool = self.claim_base.stake_pool.load()?;
+        require!(
+            self.stake_mint.key() == stake_pool.stake_mint,
+            ErrorCode::InvalidStakeMint
+        );
+        require!(
+            self.from.owner.key() == self.claim_base.owner.key(),
+            ErrorCode::InvalidAuthority
+        );
+        require!(
+            self.destination.owner.key() == self.new_owner.key(),
+            ErrorCode::InvalidOwner
+        );
+        Ok(())
+    }
+
+    /// Transfer the weighted stake representation token to the new owner, so they are able to
+    /// withdraw. A non-transferable `stake_mint` is burned from the owner and minted to the new
+    /// owner instead.
+    pub fn transfer_stake_weight_tokens_to_new_owner(&self, amount: u64) -> Result<()> {
+        let token_program = self
+            .claim_base
+            .stake_mint_token_program(&self.stake_mint.to_account_info())?;
+        if token_program.key() != Token2022::id() {
+            let cpi_ctx = CpiContext::new(
+                token_program,
+                TransferChecked {
+                    from: self.from.to_account_info(),
+                    mint: self.stake_mint.to_account_info(),
+                    to: self.destination.to_account_info(),
+                    authority: self.claim_base.owner.to_account_info(),
+                },
+            );
+            return token_interface::transfer_checked(cpi_ctx, amount, self.stake_mint.decimals);
+        }
+
+        let cpi_ctx = CpiContext::new(
+            token_program.clone(),
+            Burn {
+                mint: self.stake_mint.to_account_info(),
+                from: self.from.to_account_info(),
+                authority: self.claim_base.owner.to_account_info(),
+            },
+        );
+        token_interface::burn(cpi_ctx, amount)?;
+
+        let stake_pool = self.claim_base.stake_pool.load()?;
+        let signer_seeds: &[&[&[u8]]] = &[stake_pool_signer_seeds!(stake_pool)];
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program,
+            MintTo {
+                mint: self.stake_mint.to_account_info(),
+                to: self.destination.to_account_info(),
+                authority: self.claim_base.stake_pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token_interface::mint_to(cpi_ctx, amount)
+    }
+
+    pub fn close_stake_deposit_receipt(&self) -> Result<()> {
+        self.claim_base
+            .stake_deposit_receipt
+            .close(self.claim_base.owner.to_account_info())
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, TransferReceipt<'info>>,
+    _new_nonce: u32,
+) -> Result<()> {
+    ctx.accounts.validate_stake_mint_accounts()?;
+    let stake_deposit_receipt_info = ctx
+        .accounts
+        .claim_base
+        .stake_deposit_receipt
+        .to_account_info();
+    StakeDepositReceipt::validate_no_reward_checkpoints(&stake_deposit_receipt_info)?;
+    StakeDepositReceipt::validate_not_voting(&stake_deposit_receipt_info)?;
+
+    let max_weight: u64;
+    let withdraw_ignores_lp: bool;
+    {
+        let mut stake_pool = ctx.accounts.claim_base.stake_pool.load_mut_with_extensions()?;
+        max_weight = stake_pool.max_weight;
+        withdraw_ignores_lp = stake_pool.withdraw_ignores_lp();
+        stake_pool.recalculate_rewards_per_effective_stake(ctx.remaining_accounts, 2usize)?;
+    }
+
+    // claim all unclaimed rewards to the current owner before the position changes hands
+    let claimed_amounts = ctx
+        .accounts
+        .claim_base
+        .transfer_all_claimable_rewards(ctx.remaining_accounts)?;
+
+    ctx.accounts
+        .claim_base
+        .update_reward_pools_last_amount(claimed_amounts)?;
+
+    let stake_deposit_receipt = &ctx.accounts.claim_base.stake_deposit_receipt;
+    let new_stake_deposit_receipt = &mut ctx.accounts.new_stake_deposit_receipt;
+    new_stake_deposit_receipt.owner = ctx.accounts.new_owner.key();
+    new_stake_deposit_receipt.payer = ctx.accounts.payer.key();
+    new_stake_deposit_receipt.stake_pool = stake_deposit_receipt.stake_pool;
+    new_stake_deposit_receipt.lockup_duration = stake_deposit_receipt.lockup_duration;
+    new_stake_deposit_receipt.deposit_timestamp = stake_deposit_receipt.deposit_timestamp;
+    new_stake_deposit_receipt.deposit_amount = stake_deposit_receipt.deposit_amount;
+    new_stake_deposit_receipt.effective_stake = stake_deposit_receipt.effective_stake;
+    new_stake_deposit_receipt.claimed_amounts = stake_deposit_receipt.claimed_amounts;
+
+    if !withdraw_ignores_lp {
+        let effective_stake_token_amount = StakeDepositReceipt::get_token_amount_from_stake(
+            stake_deposit_receipt.effective_stake_u128(),
+            max_weight,
+        );
+        ctx.accounts
+            .transfer_stake_weight_tokens_to_new_owner(effective_stake_token_amount)?;
+    }
+
+    ctx.accounts.close_stake_deposit_receipt()?;
+
+    emit!(TransferReceiptEvent {
+        stake_pool: ctx.accounts.claim_base.stake_pool.key(),
+        old_owner: ctx.accounts.claim_base.owner.key(),
+        new_owner: ctx.accounts.new_owner.key(),
+        stake_deposit_receipt: ctx.accounts.claim_base.stake_deposit_receipt.key(),
+        new_stake_deposit_receipt: ctx.accounts.new_stake_deposit_receipt.key(),
+    });
+    Ok(())
+}
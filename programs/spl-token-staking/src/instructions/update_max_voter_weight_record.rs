@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+  errors::ErrorCode,
+  state::{MaxVoterWeightRecord, StakeDepositReceipt, StakePool},
+};
+
+#[derive(Accounts)]
+pub struct UpdateMaxVoterWeightRecord<'info> {
+  pub stake_pool: AccountLoader<'info, StakePool>,
+
+  #[account(
+    mut,
+    constraint = max_voter_weight_record.governing_token_mint == stake_pool.load()?.stake_mint
+      @ ErrorCode::InvalidStakeMint,
+  )]
+  pub max_voter_weight_record: Account<'info, MaxVoterWeightRecord>,
+}
+
+pub fn handler(ctx: Context<UpdateMaxVoterWeightRecord>) -> Result<()> {
+  let stake_pool = ctx.accounts.stake_pool.load()?;
+  let max_voter_weight_record = &mut ctx.accounts.max_voter_weight_record;
+  max_voter_weight_record.max_voter_weight = StakeDepositReceipt::get_token_amount_from_stake(
+    stake_pool.total_weighted_stake_u128(),
+    stake_pool.max_weight,
+  );
+  max_voter_weight_record.max_voter_weight_expiry = Some(Clock::get()?.slot);
+  Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::PoolConfigUpdated;
+use crate::state::{get_digit_shift_by_max_scalar, StakePool, WeightCurve, SCALE_FACTOR_BASE};
+
+#[derive(Accounts)]
+pub struct UpdatePoolConfig<'info> {
+  /// Authority of the StakePool
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority @ ErrorCode::InvalidAuthority,
+  )]
+  pub stake_pool: AccountLoader<'info, StakePool>,
+}
+
+pub fn handler(
+  ctx: Context<UpdatePoolConfig>,
+  base_weight: u64,
+  max_weight: u64,
+  min_duration: u64,
+  max_duration: u64,
+) -> Result<()> {
+  if min_duration > max_duration {
+    return err!(ErrorCode::InvalidStakePoolDuration);
+  }
+  if base_weight == 0 || base_weight > max_weight || max_weight < SCALE_FACTOR_BASE {
+    return err!(ErrorCode::InvalidStakePoolWeight);
+  }
+  let mut stake_pool = ctx.accounts.stake_pool.load_mut()?;
+  // The stake_mint decimals and existing token representations of effective stake depend on
+  // the digit shift of `max_weight`
+  if get_digit_shift_by_max_scalar(max_weight) != get_digit_shift_by_max_scalar(stake_pool.max_weight) {
+    return err!(ErrorCode::InvalidMaxWeightDigitShift);
+  }
+  WeightCurve::from(&stake_pool.weight_curve).validate(
+    base_weight,
+    max_weight,
+    min_duration,
+    max_duration,
+  )?;
+
+  stake_pool.base_weight = base_weight;
+  stake_pool.max_weight = max_weight;
+  stake_pool.min_duration = min_duration;
+  stake_pool.max_duration = max_duration;
+
+  emit!(PoolConfigUpdated {
+    stake_pool: ctx.accounts.stake_pool.key(),
+    base_weight,
+    max_weight,
+    min_duration,
+    max_duration,
+  });
+  Ok(())
+}
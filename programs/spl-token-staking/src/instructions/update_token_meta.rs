@@ -1,4 +1,6 @@
-use crate::{errors::ErrorCode, stake_pool_signer_seeds, state::StakePool, ID};
+use crate::{
+    errors::ErrorCode, events::TokenMetaUpdated, stake_pool_signer_seeds, state::StakePool, ID,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::metadata::{
     create_metadata_accounts_v3, update_metadata_accounts_v2, CreateMetadataAccountsV3,
@@ -18,8 +20,8 @@ pub struct UpdateTokenMeta<'info> {
       owner = ID,
       // Validates the StakePool's stake mint matches the mint to have updated metadata
       has_one = stake_mint,
-      // Validate the stake pool authority is the signer
-      has_one = authority @ErrorCode::InvalidAuthority
+      // Validate the stake pool authority or metadata admin is the signer
+      constraint = stake_pool.load()?.is_metadata_admin(&authority.key()) @ErrorCode::InvalidAuthority
     )]
     pub stake_pool: AccountLoader<'info, StakePool>,
 
@@ -44,9 +46,9 @@ pub fn handler(
     let stake_pool = ctx.accounts.stake_pool.load()?;
 
     let data = DataV2 {
-        name,
-        symbol,
-        uri,
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
         seller_fee_basis_points: 0,
         creators: None,
         collection: None,
@@ -84,5 +86,12 @@ pub fn handler(
         update_metadata_accounts_v2(ctx, None, Some(data), None, Some(true))?;
     }
 
+    emit!(TokenMetaUpdated {
+        stake_pool: ctx.accounts.stake_pool.key(),
+        name,
+        symbol,
+        uri,
+    });
+
     Ok(())
 }
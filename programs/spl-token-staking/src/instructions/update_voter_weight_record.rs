@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+  errors::ErrorCode,
+  state::{StakeDepositReceipt, StakePool, VoterWeightRecord, VOTE_LOCK_DURATION},
+};
+
+#[derive(Accounts)]
+pub struct UpdateVoterWeightRecord<'info> {
+  /// Owner of the StakeDepositReceipts, pays the rent of storing their vote lock
+  #[account(mut)]
+  pub governing_token_owner: Signer<'info>,
+
+  pub stake_pool: AccountLoader<'info, StakePool>,
+
+  #[account(
+    mut,
+    has_one = governing_token_owner @ ErrorCode::InvalidOwner,
+    constraint = voter_weight_record.governing_token_mint == stake_pool.load()?.stake_mint
+      @ ErrorCode::InvalidStakeMint,
+  )]
+  pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+  pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateVoterWeightRecord<'info> {
+  /// Sum of the effective stake of the given StakeDepositReceipts. Each receipt must be distinct,
+  /// belong to the `stake_pool` and be owned by the `governing_token_owner`.
+  ///
+  /// The receipts are locked for `VOTE_LOCK_DURATION`, so their stake can not be withdrawn or
+  /// moved to another owner to vote again on the same proposal.
+  pub fn lock_and_get_total_effective_stake(
+    &self,
+    stake_deposit_receipt_infos: &[AccountInfo<'info>],
+  ) -> Result<u128> {
+    let voting_until = Clock::get()?
+      .unix_timestamp
+      .checked_add(VOTE_LOCK_DURATION)
+      .unwrap();
+    let mut total_effective_stake: u128 = 0;
+    for (index, stake_deposit_receipt_info) in stake_deposit_receipt_infos.iter().enumerate() {
+      require!(
+        stake_deposit_receipt_infos[..index]
+          .iter()
+          .all(|info| info.key() != stake_deposit_receipt_info.key()),
+        ErrorCode::DuplicateStakeDepositReceipt
+      );
+      let stake_deposit_receipt: Account<StakeDepositReceipt> =
+        Account::try_from(stake_deposit_receipt_info)?;
+      require!(
+        stake_deposit_receipt.stake_pool == self.stake_pool.key(),
+        ErrorCode::InvalidStakePool
+      );
+      require!(
+        stake_deposit_receipt.owner == self.voter_weight_record.governing_token_owner,
+        ErrorCode::InvalidOwner
+      );
+      total_effective_stake = total_effective_stake
+        .checked_add(stake_deposit_receipt.effective_stake_u128())
+        .unwrap();
+      StakeDepositReceipt::set_voting_until(
+        stake_deposit_receipt_info,
+        &self.governing_token_owner.to_account_info(),
+        &self.system_program.to_account_info(),
+        voting_until,
+      )?;
+    }
+    Ok(total_effective_stake)
+  }
+}
+
+pub fn handler<'info>(
+  ctx: Context<'_, '_, '_, 'info, UpdateVoterWeightRecord<'info>>,
+) -> Result<()> {
+  let total_effective_stake = ctx
+    .accounts
+    .lock_and_get_total_effective_stake(ctx.remaining_accounts)?;
+  let max_weight = ctx.accounts.stake_pool.load()?.max_weight;
+
+  let voter_weight_record = &mut ctx.accounts.voter_weight_record;
+  voter_weight_record.voter_weight =
+    StakeDepositReceipt::get_token_amount_from_stake(total_effective_stake, max_weight);
+  voter_weight_record.voter_weight_expiry = Some(Clock::get()?.slot);
+  voter_weight_record.weight_action = None;
+  voter_weight_record.weight_action_target = None;
+  Ok(())
+}
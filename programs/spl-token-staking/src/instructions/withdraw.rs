@@ -1,10 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Burn, Mint, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
 
-use crate::{errors::ErrorCode, stake_pool_signer_seeds, state::StakeDepositReceipt};
+use crate::{
+    errors::ErrorCode, events::WithdrawEvent, stake_pool_signer_seeds, state::StakeDepositReceipt,
+};
 
 use super::claim_base::*;
-use crate::state::u128;
+use crate::state::{u128, RewardCheckpoint, StakePoolLoader};
 
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
@@ -12,73 +14,150 @@ pub struct Withdraw<'info> {
 
     /// Vault of the StakePool token will be transferred from
     #[account(mut)]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the StakePool's underlying token
+    pub mint: InterfaceAccount<'info, Mint>,
 
     /// stake_mint of StakePool that will be burned
     #[account(mut)]
-    pub stake_mint: Account<'info, Mint>,
+    pub stake_mint: InterfaceAccount<'info, Mint>,
 
     /// Token Account holding weighted stake representation token to burn
     #[account(mut)]
-    pub from: Account<'info, TokenAccount>,
+    pub from: InterfaceAccount<'info, TokenAccount>,
 
     /// Token account to transfer the previously staked token to
     #[account(mut)]
-    pub destination: Account<'info, TokenAccount>,
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// Treasury of the StakePool, only required to withdraw before the lockup ends
+    #[account(mut)]
+    pub treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program of the `mint`, either SPL Token or Token-2022
+    pub mint_token_program: Interface<'info, TokenInterface>,
 }
 
-impl<'info> Withdraw<'info> {
+/// Returns staked tokens to the owner of a StakeDepositReceipt. Implemented by [Withdraw] and
+/// [WithdrawPartial](super::withdraw_partial::WithdrawPartial), which share these accounts.
+pub trait WithdrawStake<'info> {
+    fn claim_base(&self) -> &ClaimBase<'info>;
+
+    /// Vault of the StakePool token will be transferred from
+    fn vault(&self) -> &InterfaceAccount<'info, TokenAccount>;
+
+    /// Mint of the StakePool's underlying token
+    fn mint(&self) -> &InterfaceAccount<'info, Mint>;
+
+    /// stake_mint of StakePool that will be burned
+    fn stake_mint(&self) -> &InterfaceAccount<'info, Mint>;
+
+    /// Token Account holding weighted stake representation token to burn
+    fn from(&self) -> &InterfaceAccount<'info, TokenAccount>;
+
+    /// Token account to transfer the previously staked token to
+    fn destination(&self) -> &InterfaceAccount<'info, TokenAccount>;
+
+    /// Token program of the `mint`
+    fn mint_token_program(&self) -> &Interface<'info, TokenInterface>;
+
     /// Addiditional validations that rely on the accounts within `claim_base`.
-    pub fn validate_stake_pool_and_owner(&self) -> Result<()> {
-        let stake_pool = self.claim_base.stake_pool.load()?;
+    fn validate_stake_pool_and_owner(&self) -> Result<()> {
+        let stake_pool = self.claim_base().stake_pool.load()?;
         require!(
-            stake_pool.vault.key() == self.vault.key(),
+            stake_pool.vault.key() == self.vault().key(),
             ErrorCode::InvalidStakePoolVault
         );
         require!(
-            stake_pool.stake_mint.key() == self.stake_mint.key(),
+            stake_pool.stake_mint.key() == self.stake_mint().key(),
             ErrorCode::InvalidStakeMint
         );
+        require!(stake_pool.mint.key() == self.mint().key(), ErrorCode::InvalidMint);
         require!(
-            self.from.owner.key() == self.claim_base.owner.key(),
+            self.from().owner.key() == self.claim_base().owner.key(),
             ErrorCode::InvalidAuthority
         );
         Ok(())
     }
-    /// Transfer the owner's previously staked tokens back.
-    pub fn transfer_staked_tokens_to_owner(&self) -> Result<()> {
-        let stake_pool = self.claim_base.stake_pool.load()?;
+
+    /// Transfer `amount` of the owner's previously staked tokens back.
+    fn transfer_staked_tokens_to_owner(&self, amount: u64) -> Result<()> {
+        self.transfer_from_vault(self.destination().to_account_info(), amount)
+    }
+
+    /// Transfer `amount` from the vault to `to`, signed by the StakePool.
+    fn transfer_from_vault(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        let claim_base = self.claim_base();
+        let stake_pool = claim_base.stake_pool.load()?;
         let signer_seeds: &[&[&[u8]]] = &[stake_pool_signer_seeds!(stake_pool)];
         let cpi_ctx = CpiContext::new_with_signer(
-            self.claim_base.token_program.to_account_info(),
-            Transfer {
-                from: self.vault.to_account_info(),
-                to: self.destination.to_account_info(),
-                authority: self.claim_base.stake_pool.to_account_info(),
+            self.mint_token_program().to_account_info(),
+            TransferChecked {
+                from: self.vault().to_account_info(),
+                mint: self.mint().to_account_info(),
+                to,
+                authority: claim_base.stake_pool.to_account_info(),
             },
             signer_seeds,
         );
-        token::transfer(
-            cpi_ctx,
-            self.claim_base.stake_deposit_receipt.deposit_amount,
-        )
+        token_interface::transfer_checked(cpi_ctx, amount, self.mint().decimals)
     }
 
-    pub fn burn_stake_weight_tokens_from_owner(&self) -> Result<()> {
-        let stake_pool = self.claim_base.stake_pool.load()?;
+    fn burn_stake_weight_tokens_from_owner(&self, amount: u64) -> Result<()> {
+        let claim_base = self.claim_base();
         let cpi_ctx = CpiContext::new(
-            self.claim_base.token_program.to_account_info(),
+            claim_base.stake_mint_token_program(&self.stake_mint().to_account_info())?,
             Burn {
-                mint: self.stake_mint.to_account_info(),
-                from: self.from.to_account_info(),
-                authority: self.claim_base.owner.to_account_info(),
+                mint: self.stake_mint().to_account_info(),
+                from: self.from().to_account_info(),
+                authority: claim_base.owner.to_account_info(),
             },
         );
-        let effective_stake_token_amount = StakeDepositReceipt::get_token_amount_from_stake(
-            self.claim_base.stake_deposit_receipt.effective_stake_u128(),
-            stake_pool.max_weight,
-        );
-        token::burn(cpi_ctx, effective_stake_token_amount)
+        token_interface::burn(cpi_ctx, amount)
+    }
+}
+
+impl<'info> WithdrawStake<'info> for Withdraw<'info> {
+    fn claim_base(&self) -> &ClaimBase<'info> {
+        &self.claim_base
+    }
+
+    fn vault(&self) -> &InterfaceAccount<'info, TokenAccount> {
+        &self.vault
+    }
+
+    fn mint(&self) -> &InterfaceAccount<'info, Mint> {
+        &self.mint
+    }
+
+    fn stake_mint(&self) -> &InterfaceAccount<'info, Mint> {
+        &self.stake_mint
+    }
+
+    fn from(&self) -> &InterfaceAccount<'info, TokenAccount> {
+        &self.from
+    }
+
+    fn destination(&self) -> &InterfaceAccount<'info, TokenAccount> {
+        &self.destination
+    }
+
+    fn mint_token_program(&self) -> &Interface<'info, TokenInterface> {
+        &self.mint_token_program
+    }
+}
+
+impl<'info> Withdraw<'info> {
+    /// Transfer the early withdraw `penalty` from the vault to the StakePool's treasury.
+    pub fn transfer_penalty_to_treasury(&self, penalty: u64) -> Result<()> {
+        let treasury = match &self.treasury {
+            Some(treasury) if treasury.key() == self.claim_base.stake_pool.load()?.treasury => {
+                treasury
+            }
+            _ => return err!(ErrorCode::InvalidTreasury),
+        };
+        self.transfer_from_vault(treasury.to_account_info(), penalty)
     }
 
     pub fn close_stake_deposit_receipt(&self) -> Result<()> {
@@ -90,16 +169,37 @@ impl<'info> Withdraw<'info> {
 
 pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>) -> Result<()> {
     ctx.accounts.validate_stake_pool_and_owner()?;
+    StakeDepositReceipt::validate_not_voting(
+        &ctx.accounts.claim_base.stake_deposit_receipt.to_account_info(),
+    )?;
+    // settle the RewardCheckpoints of the receipt, they keep their unclaimed rewards after it closes
+    let remaining_accounts = RewardCheckpoint::sync_all(
+        &ctx.accounts.claim_base.stake_deposit_receipt.to_account_info(),
+        ctx.remaining_accounts,
+        0,
+        true,
+    )?;
 
     let escape_hatch_enabled: bool;
+    let early_withdraw_enabled: bool;
     let withdraw_ignores_lp: bool;
+    let mut penalty = 0u64;
+    let deposit_amount = ctx.accounts.claim_base.stake_deposit_receipt.deposit_amount;
     {
-        let mut stake_pool = ctx.accounts.claim_base.stake_pool.load_mut()?;
+        let mut stake_pool = ctx.accounts.claim_base.stake_pool.load_mut_with_extensions()?;
         escape_hatch_enabled = stake_pool.escape_hatch_enabled();
+        early_withdraw_enabled = stake_pool.early_withdraw_enabled();
         withdraw_ignores_lp = stake_pool.withdraw_ignores_lp();
+        if !escape_hatch_enabled && early_withdraw_enabled {
+            penalty = stake_pool.get_early_withdraw_penalty(
+                &ctx.accounts.claim_base.stake_deposit_receipt,
+                deposit_amount,
+                Clock::get()?.unix_timestamp,
+            );
+        }
 
         // Recalculate rewards for stake prior, so withdrawing user can receive all rewards
-        stake_pool.recalculate_rewards_per_effective_stake(&ctx.remaining_accounts, 2usize)?;
+        stake_pool.recalculate_rewards_per_effective_stake(remaining_accounts, 2usize)?;
         // Decrement total weighted stake for future deposit reward ownership to be calculated correctly
         let total_staked = stake_pool
             .total_weighted_stake_u128()
@@ -113,27 +213,50 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>) -> Resul
         stake_pool.total_weighted_stake = u128(total_staked.to_le_bytes());
     } // release mutable borrow of stake_pool
 
-    if !escape_hatch_enabled {
+    if !escape_hatch_enabled && !early_withdraw_enabled {
         ctx.accounts
             .claim_base
             .stake_deposit_receipt
             .validate_unlocked()?;
     }
 
-    ctx.accounts.transfer_staked_tokens_to_owner()?;
+    if penalty > 0 {
+        ctx.accounts.transfer_penalty_to_treasury(penalty)?;
+    }
+    ctx.accounts
+        .transfer_staked_tokens_to_owner(deposit_amount.checked_sub(penalty).unwrap())?;
     if !withdraw_ignores_lp {
-        ctx.accounts.burn_stake_weight_tokens_from_owner()?;
+        let effective_stake_token_amount = StakeDepositReceipt::get_token_amount_from_stake(
+            ctx.accounts
+                .claim_base
+                .stake_deposit_receipt
+                .effective_stake_u128(),
+            ctx.accounts.claim_base.stake_pool.load()?.max_weight,
+        );
+        ctx.accounts
+            .burn_stake_weight_tokens_from_owner(effective_stake_token_amount)?;
     }
     // claim all unclaimed rewards
     let claimed_amounts = ctx
         .accounts
         .claim_base
-        .transfer_all_claimable_rewards(&ctx.remaining_accounts)?;
+        .transfer_all_claimable_rewards(remaining_accounts)?;
 
     ctx.accounts
         .claim_base
         .update_reward_pools_last_amount(claimed_amounts)?;
 
+    let stake_deposit_receipt = &ctx.accounts.claim_base.stake_deposit_receipt;
+    emit!(WithdrawEvent {
+        stake_pool: stake_deposit_receipt.stake_pool,
+        owner: stake_deposit_receipt.owner,
+        stake_deposit_receipt: stake_deposit_receipt.key(),
+        amount: deposit_amount,
+        penalty,
+        effective_stake: stake_deposit_receipt.effective_stake_u128(),
+        closed: true,
+    });
+
     ctx.accounts.close_stake_deposit_receipt()?;
     Ok(())
 }
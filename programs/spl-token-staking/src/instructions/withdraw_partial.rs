@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{errors::ErrorCode, events::WithdrawEvent, state::StakeDepositReceipt};
+
+use super::claim_base::*;
+use super::withdraw::WithdrawStake;
+use crate::state::{u128, RewardCheckpoint, StakePoolLoader};
+
+/// Same accounts as `Withdraw`, without the `treasury` since the receipt must be unlocked.
+#[derive(Accounts)]
+pub struct WithdrawPartial<'info> {
+    pub claim_base: ClaimBase<'info>,
+
+    /// Vault of the StakePool token will be transferred from
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the StakePool's underlying token
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// stake_mint of StakePool that will be burned
+    #[account(mut)]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token Account holding weighted stake representation token to burn
+    #[account(mut)]
+    pub from: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token account to transfer the previously staked token to
+    #[account(mut)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program of the `mint`, either SPL Token or Token-2022
+    pub mint_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawStake<'info> for WithdrawPartial<'info> {
+    fn claim_base(&self) -> &ClaimBase<'info> {
+        &self.claim_base
+    }
+
+    fn vault(&self) -> &InterfaceAccount<'info, TokenAccount> {
+        &self.vault
+    }
+
+    fn mint(&self) -> &InterfaceAccount<'info, Mint> {
+        &self.mint
+    }
+
+    fn stake_mint(&self) -> &InterfaceAccount<'info, Mint> {
+        &self.stake_mint
+    }
+
+    fn from(&self) -> &InterfaceAccount<'info, TokenAccount> {
+        &self.from
+    }
+
+    fn destination(&self) -> &InterfaceAccount<'info, TokenAccount> {
+        &self.destination
+    }
+
+    fn mint_token_program(&self) -> &Interface<'info, TokenInterface> {
+        &self.mint_token_program
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawPartial<'info>>,
+    amount: u64,
+) -> Result<()> {
+    ctx.accounts.validate_stake_pool_and_owner()?;
+    if amount == 0 || amount >= ctx.accounts.claim_base.stake_deposit_receipt.deposit_amount {
+        return err!(ErrorCode::InvalidWithdrawAmount);
+    }
+
+    let stake_deposit_receipt = &ctx.accounts.claim_base.stake_deposit_receipt;
+    StakeDepositReceipt::validate_not_voting(&stake_deposit_receipt.to_account_info())?;
+    let effective_stake_before = stake_deposit_receipt.effective_stake_u128();
+    let effective_stake_removed = stake_deposit_receipt.get_effective_stake_for_amount(amount);
+    let effective_stake_after = effective_stake_before
+        .checked_sub(effective_stake_removed)
+        .unwrap();
+    // the RewardCheckpoints of the receipt only keep earning on the remaining effective stake
+    let remaining_accounts = RewardCheckpoint::sync_all(
+        &stake_deposit_receipt.to_account_info(),
+        ctx.remaining_accounts,
+        effective_stake_after,
+        false,
+    )?;
+
+    let escape_hatch_enabled: bool;
+    let withdraw_ignores_lp: bool;
+    let max_weight: u64;
+    {
+        let mut stake_pool = ctx.accounts.claim_base.stake_pool.load_mut_with_extensions()?;
+        escape_hatch_enabled = stake_pool.escape_hatch_enabled();
+        withdraw_ignores_lp = stake_pool.withdraw_ignores_lp();
+        max_weight = stake_pool.max_weight;
+
+        // Recalculate rewards for stake prior, so withdrawing user can receive all rewards
+        stake_pool.recalculate_rewards_per_effective_stake(remaining_accounts, 2usize)?;
+    } // release mutable borrow of stake_pool
+
+    if !escape_hatch_enabled {
+        ctx.accounts
+            .claim_base
+            .stake_deposit_receipt
+            .validate_unlocked()?;
+    }
+
+    // claim all unclaimed rewards while the receipt still holds its full effective stake
+    let claimed_amounts = ctx
+        .accounts
+        .claim_base
+        .transfer_all_claimable_rewards(remaining_accounts)?;
+
+    ctx.accounts
+        .claim_base
+        .update_reward_pools_last_amount(claimed_amounts)?;
+
+    let stake_deposit_receipt = &mut ctx.accounts.claim_base.stake_deposit_receipt;
+    stake_deposit_receipt.deposit_amount = stake_deposit_receipt
+        .deposit_amount
+        .checked_sub(amount)
+        .unwrap();
+    stake_deposit_receipt.effective_stake = u128(effective_stake_after.to_le_bytes());
+
+    {
+        let mut stake_pool = ctx.accounts.claim_base.stake_pool.load_mut()?;
+        // Decrement total weighted stake for future deposit reward ownership to be calculated correctly
+        let total_staked = stake_pool
+            .total_weighted_stake_u128()
+            .checked_sub(effective_stake_removed)
+            .unwrap();
+        stake_pool.total_weighted_stake = u128(total_staked.to_le_bytes());
+    }
+
+    ctx.accounts.transfer_staked_tokens_to_owner(amount)?;
+    if !withdraw_ignores_lp {
+        // Burn the difference of the token representations, so the total burned over the life
+        // of the receipt always matches the amount minted at deposit.
+        let burn_amount =
+            StakeDepositReceipt::get_token_amount_from_stake(effective_stake_before, max_weight)
+                .checked_sub(StakeDepositReceipt::get_token_amount_from_stake(
+                    effective_stake_after,
+                    max_weight,
+                ))
+                .unwrap();
+        ctx.accounts
+            .burn_stake_weight_tokens_from_owner(burn_amount)?;
+    }
+
+    let stake_deposit_receipt = &ctx.accounts.claim_base.stake_deposit_receipt;
+    emit!(WithdrawEvent {
+        stake_pool: stake_deposit_receipt.stake_pool,
+        owner: stake_deposit_receipt.owner,
+        stake_deposit_receipt: stake_deposit_receipt.key(),
+        amount,
+        penalty: 0,
+        effective_stake: effective_stake_removed,
+        closed: false,
+    });
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod macros;
 pub mod math;
@@ -8,6 +9,7 @@ pub mod security;
 pub mod state;
 
 use crate::instructions::*;
+use crate::state::{StakePoolRole, WeightCurve};
 
 declare_id!("STAKEGztX7S1MUHxcQHieZhELCntb9Ys9BgUbeEtMu1");
 
@@ -17,23 +19,63 @@ pub mod spl_token_staking {
 
     /// Create a [StakePool](state::StakePool) and initialize the Mint that will
     /// represent effective stake weight.
+    ///
+    /// `weight_curve` determines how the lockup duration maps to a weight, see
+    /// [WeightCurve](state::WeightCurve).
+    ///
+    /// When `non_transferable_stake_mint` is set, the `stake_mint` is created as a Token-2022
+    /// Mint with the NonTransferable extension, so voting weight stays with the staker. Such
+    /// StakePools require the `token_2022_program` in every instruction minting, burning or
+    /// moving the `stake_mint`.
     pub fn initialize_stake_pool(
         ctx: Context<InitializeStakePool>,
         nonce: u8,
         max_weight: u64,
         min_duration: u64,
         max_duration: u64,
+        weight_curve: WeightCurve,
+        non_transferable_stake_mint: bool,
     ) -> Result<()> {
-        initialize_stake_pool::handler(ctx, nonce, max_weight, min_duration, max_duration)
+        initialize_stake_pool::handler(
+            ctx,
+            nonce,
+            max_weight,
+            min_duration,
+            max_duration,
+            weight_curve,
+            non_transferable_stake_mint,
+        )
     }
 
     /// DANGEROUSLY Update `authority` of [StakePool](state::StakePool) to `new_authority`.
     /// This is useful for quickly setting up a StakePool and then transfering it to a
-    /// form of governance.
+    /// form of governance. Prefer `propose_authority` and `accept_authority` otherwise.
     pub fn transfer_authority(ctx: Context<TransferAuthority>) -> Result<()> {
         transfer_authority::handler(ctx)
     }
 
+    /// Propose `new_authority` as the `authority` of [StakePool](state::StakePool). The transfer
+    /// only completes once `new_authority` signs `accept_authority`, so a mistyped key can not
+    /// take over the StakePool. Replaces any previously proposed authority.
+    ///
+    /// Can only be invoked by the StakePool's authority.
+    pub fn propose_authority(ctx: Context<ProposeAuthority>) -> Result<()> {
+        propose_authority::handler(ctx)
+    }
+
+    /// Complete a transfer started by `propose_authority`, making the signing
+    /// `pending_authority` the `authority` of [StakePool](state::StakePool).
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        accept_authority::handler(ctx)
+    }
+
+    /// Clear the `pending_authority` of [StakePool](state::StakePool).
+    ///
+    /// Can only be invoked by the StakePool's authority.
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        cancel_authority_transfer::handler(ctx)
+    }
+
     /// DANGEROUSLY mint "stake_mint" of [StakePool](state::StakePool) to a destination TokenAccount.
     pub fn dangerously_mint_stake_mint(ctx: Context<DangerouslyMintStakeMint>, amount: u64) -> Result<()> {
         dangerously_mint_stake_mint::handler(ctx, amount)
@@ -41,22 +83,146 @@ pub mod spl_token_staking {
 
     /// Add a [RewardPool](state::RewardPool) to an existing [StakePool](state::StakePool).
     ///
-    /// Can only be invoked by the StakePool's authority.
+    /// Can only be invoked by the StakePool's authority or reward manager.
     pub fn add_reward_pool(ctx: Context<AddRewardPool>, index: u8) -> Result<()> {
         add_reward_pool::handler(ctx, index)
     }
 
-    /// Update various stakepool settings. Simply sum the settings together and pass as a single
-    /// number. Allowed settings:
-    /// * ESCAPE_HATCH_ENABLED -  1
-    /// * PLACEHOLDER_A - 2
-    /// * PLACEHOLDER_B - 4
+    /// Retire the [RewardPool](state::RewardPool) at `index`. The RewardPool stops accruing rewards
+    /// and stakers have `RETIRED_REWARD_POOL_CLAIM_WINDOW` to claim already accrued rewards, after
+    /// which the authority may invoke `sweep_reward_pool`.
+    ///
+    /// Remaining accounts are required: pass the `reward_vault` of each reward pool. These must be
+    /// passed in the same order as `StakePool.reward_pools`
+    ///
+    /// Can only be invoked by the StakePool's authority.
+    pub fn retire_reward_pool<'info>(
+        ctx: Context<'_, '_, '_, 'info, RetireRewardPool<'info>>,
+        index: u8,
+    ) -> Result<()> {
+        retire_reward_pool::handler(ctx, index)
+    }
+
+    /// Transfer the remaining balance of a retired [RewardPool](state::RewardPool) to `destination`
+    /// once the claim window has ended, close its vault and free the slot at `index` for reuse.
+    ///
+    /// Can only be invoked by the StakePool's authority.
+    pub fn sweep_reward_pool(ctx: Context<SweepRewardPool>, index: u8) -> Result<()> {
+        sweep_reward_pool::handler(ctx, index)
+    }
+
+    /// Stream the rewards of the [RewardPool](state::RewardPool) at `index` at `rewards_per_second`
+    /// between `start_time` and `end_time` instead of crediting `reward_vault` balance increases
+    /// to stakers immediately. Tokens received by the `reward_vault` are held as undistributed and
+    /// emitted over time, so a deposit made right before the vault is topped up does not receive
+    /// a share of the whole top up. Rewards emitted under the previous schedule are credited first.
+    ///
+    /// Remaining accounts are required: pass the `reward_vault` of each reward pool. These must be
+    /// passed in the same order as `StakePool.reward_pools`
+    ///
+    /// Can only be invoked by the StakePool's authority or reward manager.
+    pub fn set_reward_pool_emission<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetRewardPoolEmission<'info>>,
+        index: u8,
+        rewards_per_second: u64,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<()> {
+        set_reward_pool_emission::handler(ctx, index, rewards_per_second, start_time, end_time)
+    }
+
+    /// Transfer `amount` of rewards from the funder into the `reward_vault` of the
+    /// [RewardPool](state::RewardPool) at `index`, emitting a `RewardPoolFunded` event. Retired
+    /// RewardPools can not be funded.
+    ///
+    /// When `duration` is greater than 0, all undistributed rewards of the RewardPool, including
+    /// `amount`, are streamed evenly from now over `duration` seconds. Only the StakePool's
+    /// authority or reward manager may set a schedule. With a `duration` of 0 the current schedule is unchanged
+    /// and the rewards are accounted for like any other transfer to the `reward_vault`.
+    ///
+    /// Remaining accounts are required: pass the `reward_vault` of each reward pool. These must be
+    /// passed in the same order as `StakePool.reward_pools`
+    pub fn fund_reward_pool<'info>(
+        ctx: Context<'_, '_, '_, 'info, FundRewardPool<'info>>,
+        index: u8,
+        amount: u64,
+        duration: u64,
+    ) -> Result<()> {
+        fund_reward_pool::handler(ctx, index, amount, duration)
+    }
+
+    /// Allow withdrawing locked StakeDepositReceipts for a penalty of up to `penalty_bps` of the
+    /// deposit, scaled by the fraction of the lockup remaining. Penalties are transferred to
+    /// `treasury`, which may be the `reward_vault` of a RewardPool distributing the staked mint
+    /// to return penalties to the remaining stakers. A `penalty_bps` of 0 disables early withdraws.
+    ///
+    /// Can only be invoked by the StakePool's authority.
+    pub fn set_early_withdraw_penalty(
+        ctx: Context<SetEarlyWithdrawPenalty>,
+        penalty_bps: u16,
+    ) -> Result<()> {
+        set_early_withdraw_penalty::handler(ctx, penalty_bps)
+    }
+
+    /// Update the lockup and weight configuration of a [StakePool](state::StakePool). Only applies
+    /// to future deposits, existing StakeDepositReceipts keep their effective stake.
+    ///
+    /// `max_weight` may only change within the same digit shift, since the `stake_mint`
+    /// decimals are derived from it. The StakePool's weight curve must remain valid.
     ///
     /// Can only be invoked by the StakePool's authority.
+    pub fn update_pool_config(
+        ctx: Context<UpdatePoolConfig>,
+        base_weight: u64,
+        max_weight: u64,
+        min_duration: u64,
+        max_duration: u64,
+    ) -> Result<()> {
+        update_pool_config::handler(ctx, base_weight, max_weight, min_duration, max_duration)
+    }
+
+    /// Assign `key` to a `role` of the [StakePool](state::StakePool), allowing it to perform a
+    /// subset of the authority's actions. Pass Pubkey default to unassign the role.
+    /// * `Pauser` - may toggle `ESCAPE_HATCH_ENABLED` and `DISABLE_DEPOSITS`
+    /// * `RewardManager` - may add RewardPools, set their emission and fund them with a schedule
+    /// * `MetadataAdmin` - may update the `stake_mint` metadata
+    ///
+    /// Can only be invoked by the StakePool's authority.
+    pub fn set_role(ctx: Context<SetRole>, role: StakePoolRole, key: Pubkey) -> Result<()> {
+        set_role::handler(ctx, role, key)
+    }
+
+    /// Update various stakepool settings. Simply sum the settings together and pass as a single
+    /// number, which replaces all current settings. Allowed settings:
+    /// * ESCAPE_HATCH_ENABLED - 1
+    /// * DISABLE_DEPOSITS - 4
+    /// * DEPOSIT_IGNORES_LP - 8
+    /// * WITHDRAW_IGNORES_LP - 16
+    ///
+    /// Can only be invoked by the StakePool's authority, or its pauser when only changing
+    /// ESCAPE_HATCH_ENABLED or DISABLE_DEPOSITS.
     pub fn set_flags(ctx: Context<SetFlags>, flags: u8) -> Result<()> {
         set_flags::handler(ctx, flags)
     }
 
+    /// Enable the settings in `mask`, leaving all other settings unchanged. Allowed settings are
+    /// the same as `set_flags`.
+    ///
+    /// Can only be invoked by the StakePool's authority, or its pauser when only changing
+    /// ESCAPE_HATCH_ENABLED or DISABLE_DEPOSITS.
+    pub fn enable_flags(ctx: Context<SetFlags>, mask: u8) -> Result<()> {
+        enable_flags::handler(ctx, mask)
+    }
+
+    /// Disable the settings in `mask`, leaving all other settings unchanged. Allowed settings are
+    /// the same as `set_flags`.
+    ///
+    /// Can only be invoked by the StakePool's authority, or its pauser when only changing
+    /// ESCAPE_HATCH_ENABLED or DISABLE_DEPOSITS.
+    pub fn disable_flags(ctx: Context<SetFlags>, mask: u8) -> Result<()> {
+        disable_flags::handler(ctx, mask)
+    }
+
     /// Deposit (aka Stake) a wallet's tokens to the specified [StakePool](state::StakePool).
     /// Depending on the `lockup_duration` and the StakePool's weighting configuration, the
     /// wallet initiating the deposit will receive tokens representing their effective stake
@@ -90,6 +256,48 @@ pub mod spl_token_staking {
         claim_all::handler(ctx)
     }
 
+    /// Claim unclaimed rewards from the RewardPools selected by `reward_pool_mask` for a specific
+    /// StakeDepositReceipt, where bit `n` of the mask selects the RewardPool at index `n`. Claimed
+    /// amounts of the other RewardPools are left untouched, so they remain claimable later.
+    ///
+    /// Remaining accounts are required: pass the `reward_vault` and owner's reward token account
+    /// of each selected RewardPool in pairs, in the same order as `StakePool.reward_pools`.
+    pub fn claim_selected<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimSelected<'info>>,
+        reward_pool_mask: u16,
+    ) -> Result<()> {
+        claim_selected::handler(ctx, reward_pool_mask)
+    }
+
+    /// Allow `delegate` to claim the rewards of the owner's StakeDepositReceipts in the
+    /// [StakePool](state::StakePool) with `crank_claim`. Pass Pubkey default to allow anyone,
+    /// e.g. an automated distribution crank. Use `revoke_claim_delegate` before changing the
+    /// delegate.
+    pub fn set_claim_delegate(ctx: Context<SetClaimDelegate>, delegate: Pubkey) -> Result<()> {
+        set_claim_delegate::handler(ctx, delegate)
+    }
+
+    /// Close the owner's [ClaimDelegate](state::ClaimDelegate), disabling `crank_claim` for
+    /// their StakeDepositReceipts.
+    pub fn revoke_claim_delegate(ctx: Context<RevokeClaimDelegate>) -> Result<()> {
+        revoke_claim_delegate::handler(ctx)
+    }
+
+    /// Claim unclaimed rewards of the RewardPools selected by `reward_pool_mask` for a specific
+    /// StakeDepositReceipt on behalf of its owner, exactly like `claim_selected`. The owner must
+    /// have opted in with `set_claim_delegate` and the signer must be their delegate, unless
+    /// anyone is allowed. Rewards may only be transferred to the owner's associated token
+    /// accounts.
+    ///
+    /// Remaining accounts are required: pass the `reward_vault` and owner's associated token
+    /// account of each selected RewardPool in pairs, exactly the same as `claim_selected`.
+    pub fn crank_claim<'info>(
+        ctx: Context<'_, '_, '_, 'info, CrankClaim<'info>>,
+        reward_pool_mask: u16,
+    ) -> Result<()> {
+        crank_claim::handler(ctx, reward_pool_mask)
+    }
+
     /// Withdraw (aka Unstake) a wallet's tokens for a specific StakeDepositReceipt. The StakePool's
     /// total weighted stake will be decreased by the effective stake amount of the StakeDepositReceipt
     /// and the original amount deposited will be transferred out of the vault.
@@ -101,16 +309,233 @@ pub mod spl_token_staking {
     ///
     /// StakeDepositReceipt account is closed after this instruction.
     ///
+    /// When the StakePool has an early withdraw penalty, a locked StakeDepositReceipt may be
+    /// withdrawn by passing the StakePool's `treasury`, which receives the penalty.
+    ///
     /// Remaining accounts are required: pass the `reward_vault` of each reward pool. These must be
     /// passed in the same order as `StakePool.reward_pools`. The owner (the token account which
     /// gains the withdrawn funds) must also be passed be, in pairs like so:
     /// * `<reward_vault[0]><owner[0]>`
     /// * `<reward_vault[1]><owner[1]>
     /// * ...etc
+    ///
+    /// The StakeDepositReceipt's RewardCheckpoints follow as the last remaining accounts, an
+    /// `<external_reward_pool><reward_checkpoint>` pair for each. They keep their unclaimed rewards
+    /// after the receipt is closed.
     pub fn withdraw<'info>(ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>) -> Result<()> {
         withdraw::handler(ctx)
     }
 
+    /// Withdraw (aka Unstake) part of a StakeDepositReceipt's tokens. The receipt remains open with
+    /// its `deposit_amount` reduced by `amount` and its effective stake reduced proportionally. The
+    /// StakePool's total weighted stake is decreased by the removed effective stake and the matching
+    /// amount of `stake_mint` is burned.
+    ///
+    /// All rewards will be claimed prior to reducing the stake, so the StakeDepositReceipt's claimable
+    /// amount is 0 after invoking the instruction. `amount` must be less than the deposited amount,
+    /// use `withdraw` to exit the position entirely.
+    ///
+    /// Takes the accounts of `withdraw` without the `treasury`, as the StakeDepositReceipt must be
+    /// unlocked.
+    ///
+    /// Remaining accounts are required: pass the `reward_vault` and owner's reward token account of
+    /// each reward pool in pairs, followed by the RewardCheckpoints, exactly the same as `withdraw`.
+    pub fn withdraw_partial<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawPartial<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        withdraw_partial::handler(ctx, amount)
+    }
+
+    /// Extend the lockup of an existing StakeDepositReceipt without withdrawing. The lockup
+    /// restarts at the current time with `new_lockup_duration`, which must not be shorter than the
+    /// receipt's current `lockup_duration`. The effective stake is recalculated with the new weight,
+    /// the StakePool's total weighted stake is increased by the difference and the additional
+    /// `stake_mint` is minted to the owner.
+    ///
+    /// All rewards will be claimed prior to updating the effective stake, so the StakeDepositReceipt's
+    /// claimable amount is 0 after invoking the instruction.
+    ///
+    /// Remaining accounts are required: pass the `reward_vault` and owner's reward token account of
+    /// each reward pool in pairs, exactly the same as `claim_all`.
+    pub fn extend_lockup<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExtendLockup<'info>>,
+        new_lockup_duration: u64,
+    ) -> Result<()> {
+        extend_lockup::handler(ctx, new_lockup_duration)
+    }
+
+    /// Restake the claimable rewards of the RewardPool at `index` into the StakeDepositReceipt,
+    /// for RewardPools distributing the StakePool's `mint`. The rewards are moved to the StakePool
+    /// vault and added to the receipt's deposit at its existing weight and lockup. The StakePool's
+    /// total weighted stake is increased and the additional `stake_mint` is minted to the owner.
+    /// Receipts with a lockup must still be locked, use `extend_lockup` to relock them first.
+    ///
+    /// Rewards of all other RewardPools will be claimed prior to increasing the effective stake.
+    ///
+    /// Remaining accounts are required: pass the `reward_vault` and owner's reward token account of
+    /// each reward pool in pairs, exactly the same as `claim_all`.
+    pub fn compound<'info>(
+        ctx: Context<'_, '_, '_, 'info, Compound<'info>>,
+        index: u8,
+    ) -> Result<()> {
+        compound::handler(ctx, index)
+    }
+
+    /// Merge a source StakeDepositReceipt into a destination StakeDepositReceipt of the same owner
+    /// and StakePool. The deposited amounts and effective stakes are summed and the merged receipt
+    /// unlocks at the later of the two unlock times. The StakePool's total weighted stake is unchanged.
+    ///
+    /// All rewards of both receipts will be claimed prior to merging. The source StakeDepositReceipt
+    /// account is closed after this instruction.
+    ///
+    /// Remaining accounts are required: pass the `reward_vault` and owner's reward token account of
+    /// each reward pool in pairs, exactly the same as `claim_all`.
+    pub fn merge_receipts<'info>(
+        ctx: Context<'_, '_, '_, 'info, MergeReceipts<'info>>,
+    ) -> Result<()> {
+        merge_receipts::handler(ctx)
+    }
+
+    /// Split `amount` of a StakeDepositReceipt's deposit, and the proportional effective stake, into
+    /// a new StakeDepositReceipt of the same owner derived from `new_nonce`. The new receipt has the
+    /// same lockup end as the original. The StakePool's total weighted stake is unchanged.
+    ///
+    /// All rewards will be claimed prior to splitting, so both receipts start with a claimable
+    /// amount of 0.
+    ///
+    /// Remaining accounts are required: pass the `reward_vault` and owner's reward token account of
+    /// each reward pool in pairs, exactly the same as `claim_all`, followed by the RewardCheckpoints
+    /// of the StakeDepositReceipt like `withdraw`.
+    pub fn split_receipt<'info>(
+        ctx: Context<'_, '_, '_, 'info, SplitReceipt<'info>>,
+        amount: u64,
+        new_nonce: u32,
+    ) -> Result<()> {
+        split_receipt::handler(ctx, amount, new_nonce)
+    }
+
+    /// Transfer a StakeDepositReceipt to `new_owner`. Since the owner is part of the receipt's
+    /// address, the position is moved to a new StakeDepositReceipt derived from `new_owner` and
+    /// `new_nonce`, keeping the deposit, effective stake and lockup. The effective stake amount of
+    /// `stake_mint` is transferred to the new owner, so they are able to withdraw.
+    ///
+    /// All rewards will be claimed to the current owner prior to the transfer. The original
+    /// StakeDepositReceipt account is closed after this instruction.
+    ///
+    /// Remaining accounts are required: pass the `reward_vault` and owner's reward token account of
+    /// each reward pool in pairs, exactly the same as `claim_all`.
+    pub fn transfer_receipt<'info>(
+        ctx: Context<'_, '_, '_, 'info, TransferReceipt<'info>>,
+        new_nonce: u32,
+    ) -> Result<()> {
+        transfer_receipt::handler(ctx, new_nonce)
+    }
+
+    /// Create the SPL Governance [VoterWeightRecord](state::VoterWeightRecord) of
+    /// `governing_token_owner` for `realm`, allowing this program to be used as the voter weight
+    /// addin of a Realm whose governing token is the StakePool's `stake_mint`. Members then vote
+    /// with their locked stake directly, without depositing `stake_mint` into the Realm.
+    pub fn create_voter_weight_record(ctx: Context<CreateVoterWeightRecord>) -> Result<()> {
+        create_voter_weight_record::handler(ctx)
+    }
+
+    /// Create the SPL Governance [MaxVoterWeightRecord](state::MaxVoterWeightRecord) of `realm`
+    /// for the StakePool's `stake_mint`.
+    pub fn create_max_voter_weight_record(ctx: Context<CreateMaxVoterWeightRecord>) -> Result<()> {
+        create_max_voter_weight_record::handler(ctx)
+    }
+
+    /// Set the `voter_weight` of a [VoterWeightRecord](state::VoterWeightRecord) to the
+    /// effective stake of the owner's StakeDepositReceipts, in `stake_mint` tokens. The weight
+    /// expires after the current slot, so this must be invoked in the same transaction as the
+    /// governance instruction relying on it.
+    ///
+    /// Must be signed by the `governing_token_owner`. The counted StakeDepositReceipts are locked
+    /// for `VOTE_LOCK_DURATION` seconds, during which they can not be withdrawn, split,
+    /// transferred or merged into another receipt, so their stake can not vote twice.
+    ///
+    /// Remaining accounts: pass each StakeDepositReceipt of the `governing_token_owner` in the
+    /// StakePool that should count towards the weight, writable. Receipts must be distinct.
+    pub fn update_voter_weight_record<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdateVoterWeightRecord<'info>>,
+    ) -> Result<()> {
+        update_voter_weight_record::handler(ctx)
+    }
+
+    /// Set the `max_voter_weight` of a [MaxVoterWeightRecord](state::MaxVoterWeightRecord) to
+    /// the StakePool's total weighted stake, in `stake_mint` tokens. Expires after the current
+    /// slot like `update_voter_weight_record`.
+    pub fn update_max_voter_weight_record(ctx: Context<UpdateMaxVoterWeightRecord>) -> Result<()> {
+        update_max_voter_weight_record::handler(ctx)
+    }
+
+    /// Add an [ExternalRewardPool](state::ExternalRewardPool) with its own account and reward
+    /// vault, derived from the StakePool and `id`. Unlike the RewardPools embedded in the
+    /// StakePool, a StakePool may have any number of them.
+    ///
+    /// Can only be invoked by the StakePool's authority or reward manager.
+    pub fn add_external_reward_pool(ctx: Context<AddExternalRewardPool>, id: u32) -> Result<()> {
+        add_external_reward_pool::handler(ctx, id)
+    }
+
+    /// Transfer `amount` of rewards into an ExternalRewardPool and stream them, together with
+    /// any undistributed rewards, evenly over `duration` seconds to the StakeDepositReceipts that
+    /// joined it, pro rata to their effective stake.
+    pub fn fund_external_reward_pool(
+        ctx: Context<FundExternalRewardPool>,
+        amount: u64,
+        duration: u64,
+    ) -> Result<()> {
+        fund_external_reward_pool::handler(ctx, amount, duration)
+    }
+
+    /// Opt a StakeDepositReceipt into an ExternalRewardPool by creating its
+    /// [RewardCheckpoint](state::RewardCheckpoint). Only rewards emitted afterwards are earned.
+    /// The StakeDepositReceipt can not be transferred or merged into another until it has left
+    /// all ExternalRewardPools, and must pass its RewardCheckpoints when withdrawing or splitting.
+    pub fn join_external_reward_pool(ctx: Context<JoinExternalRewardPool>) -> Result<()> {
+        join_external_reward_pool::handler(ctx)
+    }
+
+    /// Settle the rewards of a RewardCheckpoint and update it to the current effective stake of
+    /// its StakeDepositReceipt. Permissionless.
+    pub fn sync_reward_checkpoint(ctx: Context<SyncRewardCheckpoint>) -> Result<()> {
+        sync_reward_checkpoint::handler(ctx)
+    }
+
+    /// Sync a RewardCheckpoint and transfer all of its unclaimed rewards to the owner.
+    pub fn claim_external_reward_pool(ctx: Context<ClaimExternalRewardPool>) -> Result<()> {
+        claim_external_reward_pool::handler(ctx)
+    }
+
+    /// Close a RewardCheckpoint, removing its effective stake from the ExternalRewardPool. All
+    /// rewards must have been claimed first, unless the ExternalRewardPool is sweepable. Works
+    /// after the StakeDepositReceipt was withdrawn.
+    pub fn leave_external_reward_pool(ctx: Context<LeaveExternalRewardPool>) -> Result<()> {
+        leave_external_reward_pool::handler(ctx)
+    }
+
+    /// Retire an ExternalRewardPool, so it can no longer be joined or funded and stops streaming.
+    /// Rewards not emitted yet are left to be swept. RewardCheckpoints
+    /// may claim for another `RETIRED_REWARD_POOL_CLAIM_WINDOW` seconds.
+    ///
+    /// Can only be invoked by the StakePool's authority.
+    pub fn retire_external_reward_pool(ctx: Context<RetireExternalRewardPool>) -> Result<()> {
+        retire_external_reward_pool::handler(ctx)
+    }
+
+    /// Transfer the remaining rewards of an ExternalRewardPool retired for longer than the claim
+    /// window to `destination`.
+    ///
+    /// Can only be invoked by the StakePool's authority.
+    pub fn sweep_external_reward_pool(ctx: Context<SweepExternalRewardPool>) -> Result<()> {
+        sweep_external_reward_pool::handler(ctx)
+    }
+
+    /// Create or update the metadata of the `stake_mint`.
+    ///
+    /// Can only be invoked by the StakePool's authority or metadata admin.
     pub fn update_token_meta(
         ctx: Context<UpdateTokenMeta>,
         name: String,
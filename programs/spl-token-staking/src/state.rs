@@ -1,10 +1,18 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_lang::Discriminator;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::TokenAccount;
+use bitflags::bitflags;
 use bytemuck::{Pod, Zeroable};
 use core::primitive;
+use std::cell::{Ref, RefMut};
 use jet_proc_macros::assert_size;
 
-use crate::{errors::ErrorCode, math::U192};
+use crate::{
+    errors::ErrorCode,
+    events::RewardsAccrued,
+    math::{U192, U256},
+};
 
 // REVIEW: What's the theoretical limit of Reward pools? What's the limiting factor (e.g. CU)?
 //  Wondering because a single StakePool could only ever provide 5 different assets as rewards.
@@ -16,17 +24,54 @@ use crate::{errors::ErrorCode, math::U192};
 ///  * Withdraw requires 8 + 2 x num_reward_pools accounts and no arguments.
 ///  * (256 accounts per LUT - 8) / 2 = 124 reward pool max from account limits
 pub const MAX_REWARD_POOLS: usize = 10;
+/// Bitmask selecting every RewardPool, see [is_reward_pool_selected]
+pub const ALL_REWARD_POOLS: u16 = (1 << MAX_REWARD_POOLS) - 1;
 pub const SCALE_FACTOR_BASE: u64 = 1_000_000_000;
 pub const SCALE_FACTOR_BASE_SQUARED: u64 = 1_000_000_000_000_000_000;
 pub const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+/// Denominator of values in basis points
+pub const BPS_DENOMINATOR: u64 = 10_000;
+/// Time in seconds stakers have to claim the rewards of a retired RewardPool before the
+/// authority can sweep the remaining balance.
+pub const RETIRED_REWARD_POOL_CLAIM_WINDOW: i64 = 30 * SECONDS_PER_DAY as i64;
+/// Seconds a StakeDepositReceipt counted towards a VoterWeightRecord stays locked, so its stake can
+/// not be moved to another owner and vote again on the same proposal. Longer than the voting time
+/// of Realm proposals.
+pub const VOTE_LOCK_DURATION: i64 = 7 * SECONDS_PER_DAY as i64;
+
+bitflags! {
+    /// Settings of a StakePool, stored as `StakePool.flags`
+    pub struct PoolFlags: u8 {
+        /// Allows withdraws regardless of lockup time
+        const ESCAPE_HATCH_ENABLED = 1;
+        /// Causes the deposit ix to always fail
+        const DISABLE_DEPOSITS = 4;
+        /// The deposit ix will not mint any lp token
+        const DEPOSIT_IGNORES_LP = 8;
+        /// The withdraw ix will not burn any lp token
+        const WITHDRAW_IGNORES_LP = 16;
+    }
+}
 
 // SETTINGS
 // Note: These are summed and passed to `flags`
-pub const ESCAPE_HATCH_ENABLED: u8 = 1;
+pub const ESCAPE_HATCH_ENABLED: u8 = PoolFlags::ESCAPE_HATCH_ENABLED.bits();
 
-pub const DISABLE_DEPOSITS: u8 = 4;
-pub const DEPOSIT_IGNORES_LP: u8 = 8;
-pub const WITHDRAW_IGNORES_LP: u8 = 16;
+pub const DISABLE_DEPOSITS: u8 = PoolFlags::DISABLE_DEPOSITS.bits();
+pub const DEPOSIT_IGNORES_LP: u8 = PoolFlags::DEPOSIT_IGNORES_LP.bits();
+pub const WITHDRAW_IGNORES_LP: u8 = PoolFlags::WITHDRAW_IGNORES_LP.bits();
+/// Flags the `pauser` role is allowed to change
+pub const PAUSER_FLAGS: PoolFlags = PoolFlags::from_bits_truncate(
+    PoolFlags::ESCAPE_HATCH_ENABLED.bits() | PoolFlags::DISABLE_DEPOSITS.bits(),
+);
+
+/// Admin roles of a StakePool that may be assigned by the `authority`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorDeserialize, AnchorSerialize)]
+pub enum StakePoolRole {
+    Pauser,
+    RewardManager,
+    MetadataAdmin,
+}
 
 #[allow(non_camel_case_types)]
 /// Definitely not your primitive u128...but Anchor thinks it is...
@@ -41,6 +86,35 @@ impl u128 {
     }
 }
 
+/// Whether the RewardPool at `index` is selected by `reward_pool_mask`, where bit `n` of the mask
+/// selects the RewardPool at index `n`.
+pub fn is_reward_pool_selected(reward_pool_mask: u16, index: usize) -> bool {
+    reward_pool_mask & (1 << index) != 0
+}
+
+/// 2 to the power of `exponent`, both in terms of 1 / SCALE_FACTOR_BASE. The fractional part is
+/// calculated with the Taylor series of e^(f * ln(2)), which converges quickly as f < 1.
+fn exp2(exponent: U192) -> U192 {
+    let scale = U192::from(SCALE_FACTOR_BASE);
+    let integer_part = exponent.checked_div(scale).unwrap().as_u32();
+    let x = (exponent % scale)
+        .checked_mul(U192::from(LN_2))
+        .unwrap()
+        .checked_div(scale)
+        .unwrap();
+    let mut term = scale;
+    let mut res = scale;
+    for n in 1..12u64 {
+        term = term
+            .checked_mul(x)
+            .unwrap()
+            .checked_div(scale.checked_mul(U192::from(n)).unwrap())
+            .unwrap();
+        res = res.checked_add(term).unwrap();
+    }
+    res << integer_part
+}
+
 /// Get the number of digits to shift (aka precision loss) due to potential
 /// overflow of all tokens being staked for the max stake weight.
 pub fn get_digit_shift_by_max_scalar(max_weight: u64) -> u8 {
@@ -72,7 +146,9 @@ pub struct RewardPool {
     pub rewards_per_effective_stake: u128,
     /** latest amount of tokens in the vault */
     pub last_amount: u64,
-    _padding0: [u8; 8],
+    /** Timestamp in seconds of when the RewardPool was retired, 0 while the RewardPool is active.
+    Retired RewardPools no longer accrue rewards. */
+    pub retired_at: i64,
 }
 
 impl RewardPool {
@@ -80,18 +156,608 @@ impl RewardPool {
         self.reward_vault == Pubkey::default()
     }
 
-    pub fn new(reward_vault: &Pubkey) -> Self {
+    pub fn is_retired(&self) -> bool {
+        self.retired_at != 0
+    }
+
+    /// Create a RewardPool whose `rewards_per_effective_stake` continues from the given value,
+    /// so claimed amounts of a previous RewardPool in the same slot are never below it.
+    pub fn new(reward_vault: &Pubkey, rewards_per_effective_stake: u128) -> Self {
         let mut res = Self::default();
         res.reward_vault = *reward_vault;
+        res.rewards_per_effective_stake = rewards_per_effective_stake;
         res
     }
 
+    /// True when the RewardPool is retired and the claim window has ended.
+    pub fn is_sweepable(&self, current_timestamp: i64) -> bool {
+        self.is_retired()
+            && current_timestamp
+                >= self
+                    .retired_at
+                    .checked_add(RETIRED_REWARD_POOL_CLAIM_WINDOW)
+                    .unwrap()
+    }
+
     /// Extract the underlying u128 value of `rewards_per_effective_stake`
     pub fn rewards_per_effective_stake_u128(&self) -> primitive::u128 {
         self.rewards_per_effective_stake.as_u128()
     }
 }
 
+/// Additional per RewardPool state that does not fit within the `RewardPool` layout.
+#[assert_size(144)]
+#[derive(Clone, Copy, AnchorDeserialize, AnchorSerialize, Pod, Zeroable)]
+#[repr(C)]
+pub struct RewardPoolExtension {
+    /** `rewards_per_effective_stake` of the RewardPool when it was added to the slot. Any
+    claimed amount lower than this belongs to a previous RewardPool of the same slot and must be
+    treated as this value. */
+    pub base_rewards_per_effective_stake: u128,
+    /** Amount of reward tokens emitted per second between `start_time` and `end_time` */
+    pub rewards_per_second: u64,
+    /** Timestamp in seconds of when the emission starts */
+    pub start_time: i64,
+    /** Timestamp in seconds of when the emission ends. 0 when the RewardPool is not streaming,
+    in which case any `reward_vault` balance increase is immediately credited to stakers. */
+    pub end_time: i64,
+    /** Timestamp in seconds of the last time emissions were credited */
+    pub last_update_time: i64,
+    /** Amount of tokens in the `reward_vault` that have not been emitted yet. Tokens left when
+    the emission ends are carried into the next schedule. */
+    pub undistributed_amount: u64,
+    /** Token program of the `reward_vault`. Default for SPL Token, RewardPools added before
+    Token-2022 support leave this unset. */
+    pub token_program: Pubkey,
+    _reserved0: [u8; 32],
+    _reserved1: [u8; 24],
+}
+
+impl RewardPoolExtension {
+    /// Create a RewardPoolExtension that is not streaming, with the given base
+    /// `rewards_per_effective_stake` for the slot.
+    pub fn new(base_rewards_per_effective_stake: u128) -> Self {
+        let mut res = Self::zeroed();
+        res.base_rewards_per_effective_stake = base_rewards_per_effective_stake;
+        res
+    }
+
+    /// Extract the underlying u128 value of `base_rewards_per_effective_stake`
+    pub fn base_rewards_per_effective_stake_u128(&self) -> primitive::u128 {
+        self.base_rewards_per_effective_stake.as_u128()
+    }
+
+    /// True if the `reward_vault` is owned by the Token-2022 program
+    pub fn is_token_2022(&self) -> bool {
+        self.token_program == spl_token_2022::ID
+    }
+
+    /// True if the RewardPool emits rewards over time rather than on balance change
+    pub fn is_streaming(&self) -> bool {
+        self.end_time != 0
+    }
+
+    /// Amount of tokens emitted since `last_update_time`, bounded by the emission window and
+    /// the `undistributed_amount`. Nothing is emitted past `end_time`, tokens exceeding the
+    /// schedule remain undistributed.
+    pub fn get_emission_amount(&self, current_timestamp: i64) -> u64 {
+        get_scheduled_emission(
+            self.rewards_per_second,
+            self.start_time,
+            self.end_time,
+            self.last_update_time,
+            current_timestamp,
+        )
+        .min(self.undistributed_amount)
+    }
+
+    /// Schedule `balance_diff` newly received tokens for emission and return the amount of
+    /// tokens emitted since the last update. Streaming stops once the emission has ended, so
+    /// later balance increases are credited immediately. Any `undistributed_amount` left is
+    /// carried into the next schedule.
+    pub fn stream_rewards(&mut self, balance_diff: u64, current_timestamp: i64) -> u64 {
+        self.undistributed_amount = self.undistributed_amount.checked_add(balance_diff).unwrap();
+        let emitted = self.get_emission_amount(current_timestamp);
+        self.undistributed_amount = self.undistributed_amount.checked_sub(emitted).unwrap();
+        self.last_update_time = current_timestamp;
+        if current_timestamp >= self.end_time {
+            self.rewards_per_second = 0;
+            self.start_time = 0;
+            self.end_time = 0;
+        }
+        emitted
+    }
+
+    /// Stream `amount` newly funded tokens, together with any `undistributed_amount` carried
+    /// over, evenly over `duration` seconds starting at `current_timestamp`. Rounding
+    /// `rewards_per_second` up ensures everything is emitted by `end_time`.
+    pub fn schedule_emission(&mut self, amount: u64, duration: u64, current_timestamp: i64) {
+        self.undistributed_amount = self.undistributed_amount.checked_add(amount).unwrap();
+        self.rewards_per_second = get_rewards_per_second(self.undistributed_amount, duration);
+        self.start_time = current_timestamp;
+        self.end_time = current_timestamp
+            .checked_add(i64::try_from(duration).unwrap())
+            .unwrap();
+        self.last_update_time = current_timestamp;
+    }
+}
+
+/// `rewards_per_second` emitting `amount` over `duration` seconds, rounded up so everything is
+/// emitted by the end of the emission.
+fn get_rewards_per_second(amount: u64, duration: u64) -> u64 {
+    let rewards_per_second = amount.checked_div(duration).unwrap();
+    if rewards_per_second.checked_mul(duration).unwrap() < amount {
+        rewards_per_second + 1
+    } else {
+        rewards_per_second
+    }
+}
+
+/// Amount of tokens emitted at `rewards_per_second` between `last_update_time` and
+/// `current_timestamp`, within the emission window from `start_time` to `end_time`.
+fn get_scheduled_emission(
+    rewards_per_second: u64,
+    start_time: i64,
+    end_time: i64,
+    last_update_time: i64,
+    current_timestamp: i64,
+) -> u64 {
+    let from = i64::max(last_update_time, start_time);
+    let to = i64::min(current_timestamp, end_time);
+    if to <= from {
+        return 0;
+    }
+    let emitted = primitive::u128::from(rewards_per_second)
+        .checked_mul(primitive::u128::from(to.abs_diff(from)))
+        .unwrap();
+    u64::try_from(emitted).unwrap_or(u64::MAX)
+}
+
+/// Size of the RewardPoolExtensions following a StakePool in its account
+pub const REWARD_POOL_EXTENSIONS_LEN: usize =
+    std::mem::size_of::<[RewardPoolExtension; MAX_REWARD_POOLS]>();
+
+/// Loads a StakePool together with the [RewardPoolExtension]s stored after it. StakePools whose
+/// extensions were never written read them as default, which is the state of every RewardPool
+/// added before extensions existed.
+pub trait StakePoolLoader<'info> {
+    fn load_with_extensions(&self) -> Result<StakePoolRef<'_>>;
+
+    fn load_mut_with_extensions(&self) -> Result<StakePoolRefMut<'_>>;
+
+    /// Allocate the RewardPoolExtensions, if not already done, with `payer` funding the rent.
+    fn extend(&self, payer: &AccountInfo<'info>, system_program: &AccountInfo<'info>)
+        -> Result<()>;
+}
+
+impl<'info> StakePoolLoader<'info> for AccountLoader<'info, StakePool> {
+    fn load_with_extensions(&self) -> Result<StakePoolRef<'_>> {
+        // validates the discriminator
+        drop(self.load()?);
+        let data = self.as_ref().try_borrow_data()?;
+        let (stake_pool, extensions) = Ref::map_split(data, |data| {
+            let (stake_pool, extensions) = data[8..].split_at(StakePool::LEN);
+            (bytemuck::from_bytes::<StakePool>(stake_pool), extensions)
+        });
+        let reward_pool_extensions = if extensions.len() >= REWARD_POOL_EXTENSIONS_LEN {
+            Some(Ref::map(extensions, |extensions| {
+                bytemuck::from_bytes(&extensions[..REWARD_POOL_EXTENSIONS_LEN])
+            }))
+        } else {
+            None
+        };
+        Ok(StakePoolRef {
+            stake_pool,
+            reward_pool_extensions,
+        })
+    }
+
+    fn load_mut_with_extensions(&self) -> Result<StakePoolRefMut<'_>> {
+        // validates the discriminator and that the account is writable
+        drop(self.load_mut()?);
+        let data = self.as_ref().try_borrow_mut_data()?;
+        let (stake_pool, extensions) = RefMut::map_split(data, |data| {
+            let (stake_pool, extensions) = data[8..].split_at_mut(StakePool::LEN);
+            (bytemuck::from_bytes_mut::<StakePool>(stake_pool), extensions)
+        });
+        let reward_pool_extensions = if extensions.len() >= REWARD_POOL_EXTENSIONS_LEN {
+            Some(RefMut::map(extensions, |extensions| {
+                bytemuck::from_bytes_mut(&mut extensions[..REWARD_POOL_EXTENSIONS_LEN])
+            }))
+        } else {
+            None
+        };
+        Ok(StakePoolRefMut {
+            key: self.key(),
+            stake_pool,
+            reward_pool_extensions,
+        })
+    }
+
+    fn extend(
+        &self,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let stake_pool = self.as_ref();
+        let len = 8 + StakePool::LEN + REWARD_POOL_EXTENSIONS_LEN;
+        if stake_pool.data_len() >= len {
+            return Ok(());
+        }
+        let rent = Rent::get()?.minimum_balance(len);
+        if rent > stake_pool.lamports() {
+            let cpi_ctx = CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: stake_pool.clone(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, rent - stake_pool.lamports())?;
+        }
+        stake_pool.realloc(len, true)?;
+        Ok(())
+    }
+}
+
+/// A loaded StakePool and its RewardPoolExtensions, see [StakePoolLoader].
+pub struct StakePoolRef<'a> {
+    stake_pool: Ref<'a, StakePool>,
+    reward_pool_extensions: Option<Ref<'a, [RewardPoolExtension; MAX_REWARD_POOLS]>>,
+}
+
+impl<'a> std::ops::Deref for StakePoolRef<'a> {
+    type Target = StakePool;
+
+    fn deref(&self) -> &StakePool {
+        &self.stake_pool
+    }
+}
+
+impl<'a> StakePoolRef<'a> {
+    /// Extension of the RewardPool at `index`, default if never written
+    pub fn reward_pool_extension(&self, index: usize) -> RewardPoolExtension {
+        self.reward_pool_extensions
+            .as_ref()
+            .map_or_else(RewardPoolExtension::zeroed, |extensions| extensions[index])
+    }
+
+    pub fn get_receipt_claimable_amount(
+        &self,
+        stake_deposit_receipt: &StakeDepositReceipt,
+        index: usize,
+    ) -> u64 {
+        self.stake_pool.get_receipt_claimable_amount(
+            &self.reward_pool_extension(index),
+            stake_deposit_receipt,
+            index,
+        )
+    }
+}
+
+/// A mutably loaded StakePool and its RewardPoolExtensions, see [StakePoolLoader].
+pub struct StakePoolRefMut<'a> {
+    /// Address of the StakePool, included in emitted events
+    key: Pubkey,
+    stake_pool: RefMut<'a, StakePool>,
+    reward_pool_extensions: Option<RefMut<'a, [RewardPoolExtension; MAX_REWARD_POOLS]>>,
+}
+
+impl<'a> std::ops::Deref for StakePoolRefMut<'a> {
+    type Target = StakePool;
+
+    fn deref(&self) -> &StakePool {
+        &self.stake_pool
+    }
+}
+
+impl<'a> std::ops::DerefMut for StakePoolRefMut<'a> {
+    fn deref_mut(&mut self) -> &mut StakePool {
+        &mut self.stake_pool
+    }
+}
+
+impl<'a> StakePoolRefMut<'a> {
+    /// Extension of the RewardPool at `index`, default if never written
+    pub fn reward_pool_extension(&self, index: usize) -> RewardPoolExtension {
+        self.reward_pool_extensions
+            .as_ref()
+            .map_or_else(RewardPoolExtension::zeroed, |extensions| extensions[index])
+    }
+
+    /// Writable extension of the RewardPool at `index`. The StakePool must have been extended
+    /// with [StakePoolLoader::extend] prior to loading.
+    pub fn reward_pool_extension_mut(&mut self, index: usize) -> Result<&mut RewardPoolExtension> {
+        match self.reward_pool_extensions.as_mut() {
+            Some(extensions) => Ok(&mut extensions[index]),
+            None => err!(ErrorCode::StakePoolNotExtended),
+        }
+    }
+
+    pub fn get_receipt_claimable_amount(
+        &self,
+        stake_deposit_receipt: &StakeDepositReceipt,
+        index: usize,
+    ) -> u64 {
+        self.stake_pool.get_receipt_claimable_amount(
+            &self.reward_pool_extension(index),
+            stake_deposit_receipt,
+            index,
+        )
+    }
+
+    /// Update amount of reward each effective stake should receive based on current deposits.
+    /// Iterates over reward pools:
+    ///   - check for changes in Token Account balance
+    ///   - update `rewards_per_effective_stake` based on balance change, or for streaming
+    ///     RewardPools, based on the amount emitted since the last update
+    /// <br>
+    /// * `remaining_accounts` - The remaining_accounts passed into the instruction
+    /// * `reward_vault_account_offset` - The number of accounts to move the cursor/index each
+    /// iteration
+    pub fn recalculate_rewards_per_effective_stake<'info>(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        reward_vault_account_offset: usize,
+    ) -> Result<()> {
+        self.recalculate_selected_rewards_per_effective_stake(
+            remaining_accounts,
+            reward_vault_account_offset,
+            ALL_REWARD_POOLS,
+        )
+    }
+
+    /// Same as `recalculate_rewards_per_effective_stake`, but only for the RewardPools selected
+    /// by `reward_pool_mask`. `remaining_accounts` only holds the accounts of those RewardPools.
+    ///
+    /// RewardPools accrue independently of each other, so a subset may be updated as long as the
+    /// `total_weighted_stake` is unchanged. Instructions changing it must update all RewardPools.
+    pub fn recalculate_selected_rewards_per_effective_stake<'info>(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        reward_vault_account_offset: usize,
+        reward_pool_mask: u16,
+    ) -> Result<()> {
+        let total_weighted_stake = self.total_weighted_stake_u128();
+        if total_weighted_stake == 0 {
+            // do nothing if total stake is 0. This will allow the first
+            // depositor to collect all of the rewards accumulated thus far,
+            // including emissions of streaming RewardPools.
+            return Ok(());
+        }
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let mut remaining_accounts_index: usize = 0;
+        for index in 0..MAX_REWARD_POOLS {
+            let reward_pool = &self.reward_pools[index];
+            // skip when reward pool is not initialized or not selected
+            if reward_pool.is_empty() || !is_reward_pool_selected(reward_pool_mask, index) {
+                continue;
+            }
+
+            if remaining_accounts_index >= remaining_accounts.len() {
+                msg!(
+                    "Missing at least one reward vault account. Failed at index {:?}",
+                    remaining_accounts_index
+                );
+                return err!(ErrorCode::InvalidRewardPoolVaultIndex);
+            }
+            let account_info = &remaining_accounts[remaining_accounts_index];
+
+            // assert that the remaining account indexes and reward pool
+            // indexes line up.
+            if reward_pool.reward_vault != account_info.key() {
+                msg!(
+                    "expected pool: {:?} but got {:?}",
+                    reward_pool.reward_vault,
+                    account_info.key()
+                );
+                return err!(ErrorCode::InvalidRewardPoolVault);
+            }
+
+            let token_account: InterfaceAccount<'info, TokenAccount> =
+                InterfaceAccount::try_from(account_info)
+                    .map_err(|_| ErrorCode::InvalidRewardPoolVault)?;
+            remaining_accounts_index += reward_vault_account_offset;
+
+            let mut extension = self.reward_pool_extension(index);
+            let was_streaming = extension.is_streaming();
+            let accrued = self.stake_pool.accrue_reward_pool(
+                &mut extension,
+                index,
+                token_account.amount,
+                current_timestamp,
+            )?;
+            if was_streaming {
+                // only streaming RewardPools change their extension, which implies it is stored
+                *self.reward_pool_extension_mut(index)? = extension;
+            }
+            if accrued > 0 {
+                let reward_pool = &self.reward_pools[index];
+                emit!(RewardsAccrued {
+                    stake_pool: self.key,
+                    reward_vault: reward_pool.reward_vault,
+                    reward_pool_index: index as u8,
+                    amount: accrued,
+                    rewards_per_effective_stake: reward_pool.rewards_per_effective_stake_u128(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Maximum number of points of a step or piecewise WeightCurve
+pub const MAX_WEIGHT_CURVE_POINTS: usize = 5;
+/// Maximum number of doublings of an exponential WeightCurve
+pub const MAX_WEIGHT_CURVE_DOUBLINGS: u8 = 32;
+/// ln(2) in terms of 1 / SCALE_FACTOR_BASE
+const LN_2: u64 = 693_147_181;
+
+/// A lockup duration and the weight received for it. In terms of seconds and
+/// 1 / SCALE_FACTOR_BASE.
+#[derive(
+    Clone, Copy, Default, Debug, PartialEq, Eq, AnchorDeserialize, AnchorSerialize, Pod, Zeroable,
+)]
+#[repr(C)]
+pub struct WeightCurvePoint {
+    pub duration: u64,
+    pub weight: u64,
+}
+
+/// Function mapping a lockup duration to a stake weight, chosen when initializing a StakePool.
+/// All weights must be within `base_weight` and `max_weight`.
+#[derive(Clone, Debug, PartialEq, Eq, AnchorDeserialize, AnchorSerialize)]
+pub enum WeightCurve {
+    /// Linear interpolation from `base_weight` at `min_duration` to `max_weight` at
+    /// `max_duration`
+    Linear,
+    /// Weight of the last tier whose duration is reached, `base_weight` below the first tier.
+    /// Durations must be strictly increasing and weights must not decrease.
+    Step { tiers: Vec<WeightCurvePoint> },
+    /// Like `Linear`, but the position between `min_duration` and `max_duration` is raised to
+    /// `exponent`, e.g. 2 for a quadratic curve rewarding the longest lockups the most
+    Power { exponent: u8 },
+    /// Linear interpolation between consecutive points, starting from `base_weight` at
+    /// `min_duration` and capped at the weight of the last point. Durations must be strictly
+    /// increasing and weights must not decrease.
+    Piecewise { points: Vec<WeightCurvePoint> },
+    /// Exponential growth from `base_weight` at `min_duration` to `max_weight` at
+    /// `max_duration`. The weight above `base_weight` doubles `doublings` times over the
+    /// durations, e.g. 4 gives the last eighth of the lockup as much weight as the first half.
+    Exponential { doublings: u8 },
+}
+
+impl WeightCurve {
+    /// Throw an error if the curve is not valid for the StakePool's weights and durations
+    pub fn validate(
+        &self,
+        base_weight: u64,
+        max_weight: u64,
+        min_duration: u64,
+        max_duration: u64,
+    ) -> Result<()> {
+        let points = match self {
+            WeightCurve::Linear => return Ok(()),
+            WeightCurve::Power { exponent } => {
+                require!(*exponent > 0, ErrorCode::InvalidWeightCurve);
+                return Ok(());
+            }
+            WeightCurve::Exponential { doublings } => {
+                require!(
+                    *doublings > 0 && *doublings <= MAX_WEIGHT_CURVE_DOUBLINGS,
+                    ErrorCode::InvalidWeightCurve
+                );
+                return Ok(());
+            }
+            WeightCurve::Step { tiers } => tiers,
+            WeightCurve::Piecewise { points } => points,
+        };
+        require!(
+            !points.is_empty() && points.len() <= MAX_WEIGHT_CURVE_POINTS,
+            ErrorCode::InvalidWeightCurve
+        );
+        require!(
+            points[0].duration >= min_duration && points[0].weight >= base_weight,
+            ErrorCode::InvalidWeightCurve
+        );
+        for pair in points.windows(2) {
+            require!(
+                pair[1].duration > pair[0].duration && pair[1].weight >= pair[0].weight,
+                ErrorCode::InvalidWeightCurve
+            );
+        }
+        let last_point = &points[points.len() - 1];
+        require!(
+            last_point.duration <= max_duration && last_point.weight <= max_weight,
+            ErrorCode::InvalidWeightCurve
+        );
+        Ok(())
+    }
+}
+
+pub const WEIGHT_CURVE_LINEAR: u8 = 0;
+pub const WEIGHT_CURVE_STEP: u8 = 1;
+pub const WEIGHT_CURVE_POWER: u8 = 2;
+pub const WEIGHT_CURVE_PIECEWISE: u8 = 3;
+pub const WEIGHT_CURVE_EXPONENTIAL: u8 = 4;
+
+/// Zero copy representation of a [WeightCurve]. A zeroed value is `Linear`, so StakePools
+/// created before weight curves existed are unaffected.
+#[assert_size(96)]
+#[derive(Clone, Copy, AnchorDeserialize, AnchorSerialize, Pod, Zeroable)]
+#[repr(C)]
+pub struct WeightCurveData {
+    /// One of the `WEIGHT_CURVE_*` values
+    pub kind: u8,
+    /// Exponent of a `Power` curve
+    pub exponent: u8,
+    /// Number of `points` in use by a `Step` or `Piecewise` curve
+    pub num_points: u8,
+    /// Doublings of an `Exponential` curve
+    pub doublings: u8,
+    _padding0: [u8; 4],
+    pub points: [WeightCurvePoint; MAX_WEIGHT_CURVE_POINTS],
+    _reserved0: [u8; 8],
+}
+
+impl From<&WeightCurve> for WeightCurveData {
+    fn from(weight_curve: &WeightCurve) -> Self {
+        let mut res = Self::zeroed();
+        let points = match weight_curve {
+            WeightCurve::Linear => return res,
+            WeightCurve::Power { exponent } => {
+                res.kind = WEIGHT_CURVE_POWER;
+                res.exponent = *exponent;
+                return res;
+            }
+            WeightCurve::Exponential { doublings } => {
+                res.kind = WEIGHT_CURVE_EXPONENTIAL;
+                res.doublings = *doublings;
+                return res;
+            }
+            WeightCurve::Step { tiers } => {
+                res.kind = WEIGHT_CURVE_STEP;
+                tiers
+            }
+            WeightCurve::Piecewise { points } => {
+                res.kind = WEIGHT_CURVE_PIECEWISE;
+                points
+            }
+        };
+        res.num_points = points.len().try_into().unwrap();
+        res.points[..points.len()].copy_from_slice(points);
+        res
+    }
+}
+
+impl From<&WeightCurveData> for WeightCurve {
+    fn from(weight_curve: &WeightCurveData) -> Self {
+        let points = weight_curve.get_points().to_vec();
+        match weight_curve.kind {
+            WEIGHT_CURVE_STEP => WeightCurve::Step { tiers: points },
+            WEIGHT_CURVE_POWER => WeightCurve::Power {
+                exponent: weight_curve.exponent,
+            },
+            WEIGHT_CURVE_PIECEWISE => WeightCurve::Piecewise { points },
+            WEIGHT_CURVE_EXPONENTIAL => WeightCurve::Exponential {
+                doublings: weight_curve.doublings,
+            },
+            _ => WeightCurve::Linear,
+        }
+    }
+}
+
+impl WeightCurveData {
+    /// The points in use by a `Step` or `Piecewise` curve
+    pub fn get_points(&self) -> &[WeightCurvePoint] {
+        &self.points[..usize::from(self.num_points)]
+    }
+}
+
+/// The account of a StakePool may be followed by the [RewardPoolExtension] of each RewardPool,
+/// see [StakePoolLoader]. They are allocated by the first instruction writing to them, so the
+/// StakePool layout is unchanged for existing accounts.
 #[assert_size(1112)]
 #[account(zero_copy)]
 #[repr(C)]
@@ -130,18 +796,38 @@ pub struct StakePool {
     pub nonce: u8,
     /** Bump seed for stake_mint */
     pub bump_seed: u8,
-    /// Each bit constrols a setting. Add settings together as needed. Settings supported:
+    /// Each bit constrols a setting, see [PoolFlags]. Add settings together as needed. Settings
+    /// supported:
     /// * `ESCAPE_HATCH_ENABLED` - 1, allows withdraws regardless of lockup time
-    /// * `PLACEHOLDER_A` - 2
     /// * `DISABLE_DEPOSITS` - 4, causes the deposit ix to always fail
     /// * `DEPOSIT_IGNORES_LP` - 8, the deposit ix will not mint any lp token
     /// * `WITHDRAW_IGNORES_LP` - 16, the withdraw ix will not burn any lp token
     ///
     /// Do not access directly, use functions such as `escape_hatch_enabled`
     pub flags: u8,
+    _padding0: [u8; 1],
+    /// Maximum penalty in basis points of the deposit for withdrawing before the lockup ends. The
+    /// penalty is scaled by the fraction of the lockup remaining. 0 disables early withdraws.
+    pub early_withdraw_penalty_bps: u16,
     // padding to next 8-byte
-    _padding0: [u8; 5],
-    _reserved0: [u8; 256],
+    _padding1: [u8; 2],
+    /// Token Account of `mint` receiving early withdraw penalties. May be the `reward_vault` of
+    /// a RewardPool distributing `mint`, to return penalties to the remaining stakers.
+    pub treasury: Pubkey,
+    /// Function mapping lockup durations to weights. See [WeightCurve].
+    pub weight_curve: WeightCurveData,
+    /// Proposed new `authority`, which must accept to complete the transfer. Pubkey default
+    /// when there is no pending transfer.
+    pub pending_authority: Pubkey,
+    /// May toggle `ESCAPE_HATCH_ENABLED` and `DISABLE_DEPOSITS` in addition to the `authority`.
+    /// Pubkey default when unassigned.
+    pub pauser: Pubkey,
+    /// May add and fund RewardPools and set their emissions in addition to the `authority`.
+    /// Pubkey default when unassigned.
+    pub reward_manager: Pubkey,
+    /// May update the `stake_mint` metadata in addition to the `authority`. Pubkey default when
+    /// unassigned.
+    pub metadata_admin: Pubkey,
 }
 
 impl StakePool {
@@ -151,21 +837,84 @@ impl StakePool {
     pub fn total_weighted_stake_u128(&self) -> primitive::u128 {
         self.total_weighted_stake.as_u128()
     }
+    /// Typed `flags`, ignoring any unknown bits
+    pub fn pool_flags(&self) -> PoolFlags {
+        PoolFlags::from_bits_truncate(self.flags)
+    }
     /// True if flag `ESCAPE_HATCH_ENABLED` active, false otherwise
     pub fn escape_hatch_enabled(&self) -> bool {
-        (self.flags & 0b0000_0001) != 0
+        self.pool_flags().contains(PoolFlags::ESCAPE_HATCH_ENABLED)
     }
     /// True if flag `DISABLE_DEPOSITS` enabled, false otherwise
     pub fn deposits_disabled(&self) -> bool {
-        (self.flags & 0b0000_0100) != 0
+        self.pool_flags().contains(PoolFlags::DISABLE_DEPOSITS)
     }
     /// True if flag `DEPOSIT_IGNORES_LP` enabled, false otherwise
     pub fn deposits_ignores_lp(&self) -> bool {
-        (self.flags & 0b0000_1000) != 0
+        self.pool_flags().contains(PoolFlags::DEPOSIT_IGNORES_LP)
     }
     /// True if flag `WITHDRAW_IGNORES_LP` enabled, false otherwise
     pub fn withdraw_ignores_lp(&self) -> bool {
-        (self.flags & 0b0001_0000) != 0
+        self.pool_flags().contains(PoolFlags::WITHDRAW_IGNORES_LP)
+    }
+
+    /// True if `key` is the `authority` or holds the given role
+    fn is_authority_or(&self, key: &Pubkey, role: &Pubkey) -> bool {
+        *key == self.authority || (*role != Pubkey::default() && key == role)
+    }
+    /// True if `key` may change the `PAUSER_FLAGS`
+    pub fn is_pauser(&self, key: &Pubkey) -> bool {
+        self.is_authority_or(key, &self.pauser)
+    }
+    /// True if `key` may manage RewardPools
+    pub fn is_reward_manager(&self, key: &Pubkey) -> bool {
+        self.is_authority_or(key, &self.reward_manager)
+    }
+    /// True if `key` may update the `stake_mint` metadata
+    pub fn is_metadata_admin(&self, key: &Pubkey) -> bool {
+        self.is_authority_or(key, &self.metadata_admin)
+    }
+    /// True if `key` may replace the current flags with `flags`. The `pauser` may only change
+    /// the `PAUSER_FLAGS`, so unknown bits must be left as they are.
+    pub fn can_set_flags(&self, key: &Pubkey, flags: u8) -> bool {
+        *key == self.authority
+            || (self.is_pauser(key) && (self.flags ^ flags) & !PAUSER_FLAGS.bits() == 0)
+    }
+
+    /// True if locked StakeDepositReceipts may be withdrawn with a penalty
+    pub fn early_withdraw_enabled(&self) -> bool {
+        self.early_withdraw_penalty_bps != 0
+    }
+
+    /// Penalty for withdrawing `amount` of the StakeDepositReceipt at `current_timestamp`.
+    /// Scales linearly from `early_withdraw_penalty_bps` at the deposit time down to 0 when the
+    /// lockup ends.
+    pub fn get_early_withdraw_penalty(
+        &self,
+        stake_deposit_receipt: &StakeDepositReceipt,
+        amount: u64,
+        current_timestamp: i64,
+    ) -> u64 {
+        let unlock_timestamp = stake_deposit_receipt.get_unlock_timestamp();
+        if current_timestamp >= unlock_timestamp || stake_deposit_receipt.lockup_duration == 0 {
+            return 0;
+        }
+        let remaining_lockup = u64::min(
+            unlock_timestamp.abs_diff(current_timestamp),
+            stake_deposit_receipt.lockup_duration,
+        );
+        U192::from(amount)
+            .checked_mul(U192::from(self.early_withdraw_penalty_bps))
+            .unwrap()
+            .checked_mul(U192::from(remaining_lockup))
+            .unwrap()
+            .checked_div(
+                U192::from(BPS_DENOMINATOR)
+                    .checked_mul(U192::from(stake_deposit_receipt.lockup_duration))
+                    .unwrap(),
+            )
+            .unwrap()
+            .as_u64()
     }
 
     pub fn get_claimed_amounts_of_reward_pools(&self) -> [u128; MAX_REWARD_POOLS] {
@@ -176,90 +925,129 @@ impl StakePool {
         ret
     }
 
-    /// Update amount of reward each effective stake should receive based on current deposits.
-    /// Iterates over reward pools:
-    ///   - check for changes in Token Account balance
-    ///   - update `rewards_per_effective_stake` based on balance change
-    /// <br>
-    /// * `remaining_accounts` - The remaining_accounts passed into the instruction
-    /// * `reward_vault_account_offset` - The number of accounts to move the cursor/index each
-    /// iteration
-    pub fn recalculate_rewards_per_effective_stake<'info>(
+    /// Claimed amount of the given StakeDepositReceipt for the RewardPool at `index`. Claimed
+    /// amounts of a previous RewardPool in the same slot are raised to the base of the current one.
+    pub fn get_receipt_claimed_amount(
+        &self,
+        reward_pool_extension: &RewardPoolExtension,
+        stake_deposit_receipt: &StakeDepositReceipt,
+        index: usize,
+    ) -> primitive::u128 {
+        primitive::u128::max(
+            stake_deposit_receipt.claimed_amounts[index].as_u128(),
+            reward_pool_extension.base_rewards_per_effective_stake_u128(),
+        )
+    }
+
+    /// Amount of the RewardPool at `index` the given StakeDepositReceipt may claim, based on the
+    /// last recalculated `rewards_per_effective_stake`.
+    pub fn get_receipt_claimable_amount(
+        &self,
+        reward_pool_extension: &RewardPoolExtension,
+        stake_deposit_receipt: &StakeDepositReceipt,
+        index: usize,
+    ) -> u64 {
+        let claimed_amount =
+            self.get_receipt_claimed_amount(reward_pool_extension, stake_deposit_receipt, index);
+        let claimable_per_effective_stake = self.reward_pools[index]
+            .rewards_per_effective_stake_u128()
+            .checked_sub(claimed_amount)
+            .unwrap();
+        // Note: Cannot overflow, 2^128 * 2^128 < 2^256
+        U256::from(claimable_per_effective_stake)
+            .checked_mul(U256::from(stake_deposit_receipt.effective_stake_u128()))
+            .unwrap()
+            .checked_div(U256::from(SCALE_FACTOR_BASE_SQUARED))
+            .unwrap()
+            .as_u64()
+    }
+
+    /// Update `rewards_per_effective_stake` of the RewardPool at `index` given the current balance
+    /// of its `reward_vault`. The balance change is credited to current stakers, or for streaming
+    /// RewardPools, the amount emitted since the last update. Returns the amount credited.
+    ///
+    /// Pure, so it can be used off-chain to project rewards with a fetched StakePool. Callers must
+    /// skip empty RewardPools. Nothing accrues while the StakePool has no stake, leaving the
+    /// rewards to the first depositor.
+    pub fn accrue_reward_pool(
         &mut self,
-        remaining_accounts: &[AccountInfo<'info>],
-        reward_vault_account_offset: usize,
-    ) -> Result<()> {
+        reward_pool_extension: &mut RewardPoolExtension,
+        index: usize,
+        vault_amount: u64,
+        current_timestamp: i64,
+    ) -> Result<u64> {
         let total_weighted_stake = self.total_weighted_stake_u128();
-        if total_weighted_stake == 0 {
-            // do nothing if total stake is 0. This will allow the first
-            // depositor to collect all of the rewards accumulated thus far.
-            return Ok(());
+        let reward_pool = &mut self.reward_pools[index];
+        if reward_pool.is_retired() || total_weighted_stake == 0 {
+            // retired reward pools no longer accrue, any balance increase is left to be swept
+            return Ok(0);
         }
 
-        let mut remaining_accounts_index: usize = 0;
-        for reward_pool in &mut self.reward_pools {
-            // skip when reward pool is not initialized
-            if reward_pool.is_empty() {
-                continue;
-            }
+        let extension = reward_pool_extension;
+        if reward_pool.last_amount == vault_amount && !extension.is_streaming() {
+            // no change in token account balance, can skip update
+            return Ok(0);
+        }
 
-            if remaining_accounts_index >= remaining_accounts.len() {
-                msg!(
-                    "Missing at least one reward vault account. Failed at index {:?}",
-                    remaining_accounts_index
-                );
-                return err!(ErrorCode::InvalidRewardPoolVaultIndex);
+        let mut balance_diff = vault_amount
+            .checked_sub(reward_pool.last_amount)
+            .ok_or(ErrorCode::RewardPoolVaultBalanceDecreased)?;
+        reward_pool.last_amount = vault_amount;
+        if extension.is_streaming() {
+            // new tokens are scheduled and only the amount emitted since the last update is
+            // credited to current stakers
+            balance_diff = extension.stream_rewards(balance_diff, current_timestamp);
+            if balance_diff == 0 {
+                return Ok(0);
             }
-            let account_info = &remaining_accounts[remaining_accounts_index];
+        }
+        // Scaled balance diff is scaled by SCALE_FACTOR_BASE squared because
+        //  total_weighted_stake is shifted by SCALE_FACTOR_BASE and this
+        //  avoids precision loss in the later division.
+        // Note: Cannot overflow because (u64::MAX * 10 ^ 18) < 2^128
+        let scaled_balance_diff = primitive::u128::from(balance_diff)
+            .checked_mul(primitive::u128::from(SCALE_FACTOR_BASE_SQUARED))
+            .unwrap();
 
-            // assert that the remaining account indexes and reward pool
-            // indexes line up.
-            if reward_pool.reward_vault != account_info.key() {
-                msg!(
-                    "expected pool: {:?} but got {:?}",
-                    reward_pool.reward_vault,
-                    account_info.key()
-                );
-                return err!(ErrorCode::InvalidRewardPoolVault);
-            }
+        let additional_rewards_per_effective_stake = scaled_balance_diff
+            .checked_div(total_weighted_stake)
+            .unwrap();
 
-            let token_account: Account<'info, TokenAccount> =
-                Account::try_from(&account_info).map_err(|_| ErrorCode::InvalidRewardPoolVault)?;
-            remaining_accounts_index += reward_vault_account_offset;
+        let rewards_updated = reward_pool
+            .rewards_per_effective_stake_u128()
+            .checked_add(additional_rewards_per_effective_stake)
+            .unwrap();
 
-            if reward_pool.last_amount == token_account.amount {
-                // no change in token account balance, can skip update
+        reward_pool.rewards_per_effective_stake = u128(rewards_updated.to_le_bytes());
+        Ok(balance_diff)
+    }
+
+    /// Amounts of each RewardPool the StakeDepositReceipt could claim at `current_timestamp`,
+    /// given the current balance of each `reward_vault`, indexed like `reward_pools`. Balances of
+    /// empty RewardPools are ignored.
+    ///
+    /// Performs the same recalculation as `claim_all` on a copy of the StakePool, without
+    /// accessing accounts or the Clock, so pending rewards can be shown off-chain. Fails when a
+    /// `reward_vault` balance is below the RewardPool's `last_amount`, e.g. a stale balance.
+    pub fn get_claimable_rewards(
+        &self,
+        reward_pool_extensions: &[RewardPoolExtension; MAX_REWARD_POOLS],
+        stake_deposit_receipt: &StakeDepositReceipt,
+        reward_vault_amounts: &[u64; MAX_REWARD_POOLS],
+        current_timestamp: i64,
+    ) -> Result<[u64; MAX_REWARD_POOLS]> {
+        let mut stake_pool = *self;
+        let mut claimable = [0u64; MAX_REWARD_POOLS];
+        for (index, vault_amount) in reward_vault_amounts.iter().enumerate() {
+            if stake_pool.reward_pools[index].is_empty() {
                 continue;
             }
-
-            let balance_diff = primitive::u128::from(
-                token_account
-                    .amount
-                    .checked_sub(reward_pool.last_amount)
-                    .unwrap(),
-            );
-
-            // Scaled balance diff is scaled by SCALE_FACTOR_BASE squared because
-            //  total_weighted_stake is shifted by SCALE_FACTOR_BASE and this
-            //  avoids precision loss in the later division.
-            // Note: Cannot overflow because (u64::MAX * 10 ^ 18) < 2^128
-            let scaled_balance_diff = balance_diff
-                .checked_mul(primitive::u128::from(SCALE_FACTOR_BASE_SQUARED))
-                .unwrap();
-
-            let additional_rewards_per_effective_stake = scaled_balance_diff
-                .checked_div(total_weighted_stake)
-                .unwrap();
-
-            reward_pool.last_amount = token_account.amount;
-            let rewards_updated = reward_pool
-                .rewards_per_effective_stake_u128()
-                .checked_add(additional_rewards_per_effective_stake)
-                .unwrap();
-
-            reward_pool.rewards_per_effective_stake = u128(rewards_updated.to_le_bytes());
+            let mut extension = reward_pool_extensions[index];
+            stake_pool.accrue_reward_pool(&mut extension, index, *vault_amount, current_timestamp)?;
+            claimable[index] =
+                stake_pool.get_receipt_claimable_amount(&extension, stake_deposit_receipt, index);
         }
-        Ok(())
+        Ok(claimable)
     }
 
     /// Calculate the stake weight based on a given duration for the current StakePool
@@ -268,6 +1056,47 @@ impl StakePool {
             panic!("Unreachable: the lockup is less than the minimum allowed")
         }
 
+        match self.weight_curve.kind {
+            WEIGHT_CURVE_STEP => self.get_step_weight(duration),
+            WEIGHT_CURVE_PIECEWISE => self.get_piecewise_weight(duration),
+            _ => self.get_interpolated_weight(duration),
+        }
+    }
+
+    /// Weight of the last tier reached by the duration
+    fn get_step_weight(&self, duration: u64) -> u64 {
+        self.weight_curve
+            .get_points()
+            .iter()
+            .rev()
+            .find(|tier| duration >= tier.duration)
+            .map_or(self.base_weight, |tier| tier.weight)
+    }
+
+    /// Weight on the line between the points surrounding the duration
+    fn get_piecewise_weight(&self, duration: u64) -> u64 {
+        let mut from = WeightCurvePoint {
+            duration: self.min_duration,
+            weight: self.base_weight,
+        };
+        for to in self.weight_curve.get_points() {
+            if duration < to.duration {
+                let weight_diff = U192::from(to.weight.checked_sub(from.weight).unwrap());
+                let interpolated = weight_diff
+                    .checked_mul(U192::from(duration.checked_sub(from.duration).unwrap()))
+                    .unwrap()
+                    .checked_div(U192::from(to.duration.checked_sub(from.duration).unwrap()))
+                    .unwrap();
+                return from.weight.checked_add(interpolated.as_u64()).unwrap();
+            }
+            from = *to;
+        }
+        from.weight
+    }
+
+    /// Weight between `base_weight` and `max_weight` for the `Linear`, `Power` and `Exponential`
+    /// curves
+    fn get_interpolated_weight(&self, duration: u64) -> u64 {
         let duration_span = self.max_duration.checked_sub(self.min_duration).unwrap();
         if duration_span == 0 {
             return self.base_weight;
@@ -281,12 +1110,33 @@ impl StakePool {
 
         // The multiplier on a scale of 0 - 1 (aka SCALE_FACTOR_BASE), based on where the duration falls
         // on the line of min - max duration.
-        let normalized_weight = U192::from(duration_exceeding_min)
+        let mut normalized_weight = U192::from(duration_exceeding_min)
             // must scale to account for decimals
             .checked_mul(U192::from(SCALE_FACTOR_BASE))
             .unwrap()
             .checked_div(U192::from(duration_span))
             .unwrap();
+        if self.weight_curve.kind == WEIGHT_CURVE_POWER {
+            let position = normalized_weight;
+            for _ in 1..self.weight_curve.exponent {
+                normalized_weight = normalized_weight
+                    .checked_mul(position)
+                    .unwrap()
+                    .checked_div(U192::from(SCALE_FACTOR_BASE))
+                    .unwrap();
+            }
+        } else if self.weight_curve.kind == WEIGHT_CURVE_EXPONENTIAL {
+            // (2^(doublings * position) - 1) / (2^doublings - 1), so 0 and 1 map to themselves
+            let doublings = U192::from(self.weight_curve.doublings);
+            let scale = U192::from(SCALE_FACTOR_BASE);
+            normalized_weight = exp2(normalized_weight.checked_mul(doublings).unwrap())
+                .checked_sub(scale)
+                .unwrap()
+                .checked_mul(scale)
+                .unwrap()
+                .checked_div((scale << self.weight_curve.doublings).checked_sub(scale).unwrap())
+                .unwrap();
+        }
         let weight_diff = U192::from(self.max_weight)
             .checked_sub(U192::from(self.base_weight))
             .unwrap();
@@ -325,9 +1175,119 @@ pub struct StakeDepositReceipt {
     pub claimed_amounts: [u128; MAX_REWARD_POOLS],
 }
 
+/// Size of the number of RewardCheckpoints stored after the data of a StakeDepositReceipt account
+pub const REWARD_CHECKPOINT_COUNT_LEN: usize = 4;
+/// Size of the vote lock stored after the number of RewardCheckpoints of a StakeDepositReceipt
+pub const VOTING_UNTIL_LEN: usize = 8;
+
 impl StakeDepositReceipt {
     pub const LEN: usize = std::mem::size_of::<StakeDepositReceipt>();
 
+    /// Number of RewardCheckpoints of a StakeDepositReceipt account. It is stored after the
+    /// StakeDepositReceipt data, so the layout is unchanged, and is 0 when absent.
+    pub fn get_reward_checkpoint_count(stake_deposit_receipt: &AccountInfo) -> Result<u32> {
+        let data = stake_deposit_receipt.try_borrow_data()?;
+        Ok(data
+            .get(8 + Self::LEN..8 + Self::LEN + REWARD_CHECKPOINT_COUNT_LEN)
+            .map_or(0, |count| u32::from_le_bytes(count.try_into().unwrap())))
+    }
+
+    /// Store the number of RewardCheckpoints of a StakeDepositReceipt account, reallocating it
+    /// with the rent paid by `payer` when the count is not stored yet.
+    pub fn set_reward_checkpoint_count<'info>(
+        stake_deposit_receipt: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        count: u32,
+    ) -> Result<()> {
+        let offset = 8 + Self::LEN;
+        Self::realloc_trailing_data(
+            stake_deposit_receipt,
+            payer,
+            system_program,
+            offset + REWARD_CHECKPOINT_COUNT_LEN,
+        )?;
+        stake_deposit_receipt.try_borrow_mut_data()?[offset..offset + REWARD_CHECKPOINT_COUNT_LEN]
+            .copy_from_slice(&count.to_le_bytes());
+        Ok(())
+    }
+
+    /// Timestamp in seconds until which the StakeDepositReceipt is locked after voting. It is
+    /// stored after the number of RewardCheckpoints and is 0 when absent.
+    pub fn get_voting_until(stake_deposit_receipt: &AccountInfo) -> Result<i64> {
+        let offset = 8 + Self::LEN + REWARD_CHECKPOINT_COUNT_LEN;
+        let data = stake_deposit_receipt.try_borrow_data()?;
+        Ok(data
+            .get(offset..offset + VOTING_UNTIL_LEN)
+            .map_or(0, |voting_until| i64::from_le_bytes(voting_until.try_into().unwrap())))
+    }
+
+    /// Store the vote lock of a StakeDepositReceipt account, reallocating it with the rent paid
+    /// by `payer` when the lock is not stored yet.
+    pub fn set_voting_until<'info>(
+        stake_deposit_receipt: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        voting_until: i64,
+    ) -> Result<()> {
+        let offset = 8 + Self::LEN + REWARD_CHECKPOINT_COUNT_LEN;
+        Self::realloc_trailing_data(
+            stake_deposit_receipt,
+            payer,
+            system_program,
+            offset + VOTING_UNTIL_LEN,
+        )?;
+        stake_deposit_receipt.try_borrow_mut_data()?[offset..offset + VOTING_UNTIL_LEN]
+            .copy_from_slice(&voting_until.to_le_bytes());
+        Ok(())
+    }
+
+    /// Throw error if the StakeDepositReceipt account is locked after voting, which prevents its
+    /// stake from being moved to another owner until the vote has ended.
+    pub fn validate_not_voting(stake_deposit_receipt: &AccountInfo) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= Self::get_voting_until(stake_deposit_receipt)?,
+            ErrorCode::StakeDepositReceiptVoting
+        );
+        Ok(())
+    }
+
+    /// Grow a StakeDepositReceipt account to `len` to store data after the StakeDepositReceipt,
+    /// with the rent paid by `payer`. Accounts that are large enough are left unchanged.
+    fn realloc_trailing_data<'info>(
+        stake_deposit_receipt: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        len: usize,
+    ) -> Result<()> {
+        if stake_deposit_receipt.data_len() >= len {
+            return Ok(());
+        }
+        let rent = Rent::get()?.minimum_balance(len);
+        if rent > stake_deposit_receipt.lamports() {
+            let cpi_ctx = CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: stake_deposit_receipt.clone(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, rent - stake_deposit_receipt.lamports())?;
+        }
+        stake_deposit_receipt.realloc(len, true)?;
+        Ok(())
+    }
+
+    /// Throw error if the StakeDepositReceipt account still has RewardCheckpoints, which must be
+    /// removed before the account is closed.
+    pub fn validate_no_reward_checkpoints(stake_deposit_receipt: &AccountInfo) -> Result<()> {
+        require!(
+            Self::get_reward_checkpoint_count(stake_deposit_receipt)? == 0,
+            ErrorCode::StakeDepositReceiptHasRewardCheckpoints
+        );
+        Ok(())
+    }
+
     pub fn effective_stake_u128(&self) -> primitive::u128 {
         self.effective_stake.as_u128()
     }
@@ -347,6 +1307,20 @@ impl StakeDepositReceipt {
             .unwrap()
     }
 
+    /// Portion of this receipt's effective stake that is backed by `amount` of the deposited
+    /// tokens. Rounds down, so the remaining stake is never under-counted.
+    pub fn get_effective_stake_for_amount(&self, amount: u64) -> primitive::u128 {
+        if self.deposit_amount == 0 {
+            return 0;
+        }
+        U192::from(self.effective_stake_u128())
+            .checked_mul(U192::from(amount))
+            .unwrap()
+            .checked_div(U192::from(self.deposit_amount))
+            .unwrap()
+            .as_u128()
+    }
+
     /// Effective stake converted to u64 token amount
     pub fn get_token_amount_from_stake(effective_stake: primitive::u128, max_weight: u64) -> u64 {
         let digit_shift = get_digit_shift_by_max_scalar(max_weight);
@@ -359,21 +1333,411 @@ impl StakeDepositReceipt {
             .unwrap()
     }
 
+    /// Timestamp in seconds of when the stake lockup ends
+    pub fn get_unlock_timestamp(&self) -> i64 {
+        self.deposit_timestamp
+            .checked_add(self.lockup_duration.try_into().unwrap())
+            .unwrap()
+    }
+
     /// Throw error if the StakeDepositReceipt is still locked
     pub fn validate_unlocked(&self) -> Result<()> {
         let current_timestamp = Clock::get()?.unix_timestamp;
-        if current_timestamp
-            < self
-                .deposit_timestamp
-                .checked_add(self.lockup_duration.try_into().unwrap())
-                .unwrap()
-        {
+        if current_timestamp < self.get_unlock_timestamp() {
             return Err(ErrorCode::StakeStillLocked.into());
         }
         Ok(())
     }
 }
 
+/// RewardPool in its own account, so a StakePool may have an unbounded number of them in addition
+/// to the `MAX_REWARD_POOLS` embedded in the StakePool. StakeDepositReceipts opt in with a
+/// [RewardCheckpoint] and only opted in effective stake earns rewards, so deposits never iterate
+/// ExternalRewardPools. Each checkpoint is settled lazily when it is synced.
+///
+/// Funding is streamed over a duration, like a streaming [RewardPool], so joining right before a
+/// fund only earns the share emitted while the stake is present. Instructions decreasing the
+/// effective stake of a receipt sync all of its RewardCheckpoints, so the stake of every
+/// checkpoint is backed by the receipt.
+#[account]
+pub struct ExternalRewardPool {
+    /** StakePool the RewardPool belongs to */
+    pub stake_pool: Pubkey,
+    /** Mint of the reward token */
+    pub reward_mint: Pubkey,
+    /** Token Account to store the reward token, owned by the StakePool */
+    pub reward_vault: Pubkey,
+    /** Token program of the `reward_mint`, either SPL Token or Token-2022 */
+    pub token_program: Pubkey,
+    /** Sum of the effective stake of all RewardCheckpoints of the RewardPool */
+    pub total_effective_stake: u128,
+    /// Ever increasing accumulator of the amount of rewards per effective stake, increased
+    /// whenever the RewardPool is funded. Shifted by SCALE_FACTOR_BASE_SQUARED like
+    /// [RewardPool].
+    pub rewards_per_effective_stake: u128,
+    /** Amount of rewards emitted per second while streaming */
+    pub rewards_per_second: u64,
+    /** Timestamp in seconds of when the emission starts */
+    pub start_time: i64,
+    /** Timestamp in seconds of when the emission ends, 0 when the RewardPool is not streaming */
+    pub end_time: i64,
+    /** Timestamp in seconds of the last time emissions were distributed */
+    pub last_update_time: i64,
+    /** Amount of tokens in the `reward_vault` that have not been emitted yet. Tokens left when
+    the emission ends, or emitted while no stake has joined, are carried into the next schedule. */
+    pub undistributed_amount: u64,
+    /** Timestamp the RewardPool was retired at, 0 while active */
+    pub retired_at: i64,
+    /** Id to derive multiple RewardPools from the same StakePool */
+    pub id: u32,
+}
+
+impl ExternalRewardPool {
+    pub const LEN: usize = std::mem::size_of::<ExternalRewardPool>();
+
+    pub fn total_effective_stake_u128(&self) -> primitive::u128 {
+        self.total_effective_stake.as_u128()
+    }
+
+    pub fn rewards_per_effective_stake_u128(&self) -> primitive::u128 {
+        self.rewards_per_effective_stake.as_u128()
+    }
+
+    pub fn is_retired(&self) -> bool {
+        self.retired_at != 0
+    }
+
+    /// True once the claim window after retiring has passed. Unclaimed rewards may then be swept
+    /// and RewardCheckpoints may leave without claiming.
+    pub fn is_sweepable(&self, current_timestamp: i64) -> bool {
+        self.is_retired()
+            && current_timestamp
+                >= self
+                    .retired_at
+                    .checked_add(RETIRED_REWARD_POOL_CLAIM_WINDOW)
+                    .unwrap()
+    }
+
+    /// True if the RewardPool is emitting funded rewards
+    pub fn is_streaming(&self) -> bool {
+        self.end_time != 0
+    }
+
+    /// Stream `amount` newly funded tokens, together with any `undistributed_amount` carried
+    /// over, evenly over `duration` seconds starting at `current_timestamp`. Must be called
+    /// after `accrue`.
+    pub fn schedule_emission(&mut self, amount: u64, duration: u64, current_timestamp: i64) {
+        self.undistributed_amount = self.undistributed_amount.checked_add(amount).unwrap();
+        self.rewards_per_second = get_rewards_per_second(self.undistributed_amount, duration);
+        self.start_time = current_timestamp;
+        self.end_time = current_timestamp
+            .checked_add(i64::try_from(duration).unwrap())
+            .unwrap();
+        self.last_update_time = current_timestamp;
+    }
+
+    /// Stop streaming, leaving the `undistributed_amount` to be swept
+    pub fn stop_emission(&mut self) {
+        self.rewards_per_second = 0;
+        self.start_time = 0;
+        self.end_time = 0;
+    }
+
+    /// Distribute the rewards emitted since `last_update_time` to the current RewardCheckpoints.
+    /// Must be called before `total_effective_stake` changes. Nothing is distributed while no
+    /// stake has joined, the emission of that time is carried into the next schedule instead.
+    pub fn accrue(&mut self, current_timestamp: i64) {
+        if !self.is_streaming() {
+            return;
+        }
+        if self.total_effective_stake_u128() > 0 {
+            let emitted = get_scheduled_emission(
+                self.rewards_per_second,
+                self.start_time,
+                self.end_time,
+                self.last_update_time,
+                current_timestamp,
+            )
+            .min(self.undistributed_amount);
+            self.distribute(emitted);
+            self.undistributed_amount = self.undistributed_amount.checked_sub(emitted).unwrap();
+        }
+        self.last_update_time = current_timestamp;
+        if current_timestamp >= self.end_time {
+            self.stop_emission();
+        }
+    }
+
+    /// Distribute `amount` of rewards to the current RewardCheckpoints, pro rata to their
+    /// effective stake.
+    fn distribute(&mut self, amount: u64) {
+        let total_effective_stake = self.total_effective_stake_u128();
+        // Note: Cannot overflow because (u64::MAX * 10 ^ 18) < 2^128
+        let additional_rewards_per_effective_stake = primitive::u128::from(amount)
+            .checked_mul(primitive::u128::from(SCALE_FACTOR_BASE_SQUARED))
+            .unwrap()
+            .checked_div(total_effective_stake)
+            .unwrap();
+        let rewards_updated = self
+            .rewards_per_effective_stake_u128()
+            .checked_add(additional_rewards_per_effective_stake)
+            .unwrap();
+        self.rewards_per_effective_stake = u128(rewards_updated.to_le_bytes());
+    }
+}
+
+/// Participation of a StakeDepositReceipt in an [ExternalRewardPool].
+///
+/// The checkpoint keeps its own copy of the receipt's effective stake, which is counted in the
+/// RewardPool's `total_effective_stake` and brought up to date whenever it is synced. Increases
+/// of the receipt's effective stake are earned on from the next sync, decreases are synced by the
+/// instruction making them.
+#[account]
+pub struct RewardCheckpoint {
+    /** Owner of the StakeDepositReceipt */
+    pub owner: Pubkey,
+    /** ExternalRewardPool the checkpoint participates in */
+    pub reward_pool: Pubkey,
+    /** StakeDepositReceipt earning the rewards */
+    pub stake_deposit_receipt: Pubkey,
+    /** Effective stake counted in the RewardPool's `total_effective_stake` */
+    pub effective_stake: u128,
+    /** The RewardPool's `rewards_per_effective_stake` when the checkpoint was last synced */
+    pub rewards_per_effective_stake: u128,
+    /** Rewards settled by syncing that have not been claimed yet */
+    pub unclaimed: u64,
+    /** True once the StakeDepositReceipt was withdrawn. The checkpoint then only holds its
+    unclaimed rewards until it leaves. */
+    pub receipt_closed: bool,
+}
+
+impl RewardCheckpoint {
+    pub const LEN: usize = std::mem::size_of::<RewardCheckpoint>();
+
+    pub fn effective_stake_u128(&self) -> primitive::u128 {
+        self.effective_stake.as_u128()
+    }
+
+    /// Effective stake of the checkpoint's StakeDepositReceipt, 0 once it was withdrawn
+    pub fn get_receipt_effective_stake(
+        &self,
+        stake_deposit_receipt: &AccountInfo,
+    ) -> Result<primitive::u128> {
+        if self.receipt_closed {
+            return Ok(0);
+        }
+        let stake_deposit_receipt: Account<StakeDepositReceipt> =
+            Account::try_from(stake_deposit_receipt)?;
+        Ok(stake_deposit_receipt.effective_stake_u128())
+    }
+
+    /// Settle the rewards earned since the last sync into `unclaimed` and update the effective
+    /// stake to `receipt_effective_stake`, which is 0 for a withdrawn StakeDepositReceipt.
+    pub fn sync(
+        &mut self,
+        reward_pool: &mut ExternalRewardPool,
+        receipt_effective_stake: primitive::u128,
+        current_timestamp: i64,
+    ) {
+        reward_pool.accrue(current_timestamp);
+        let claimable_per_effective_stake = reward_pool
+            .rewards_per_effective_stake_u128()
+            .checked_sub(self.rewards_per_effective_stake.as_u128())
+            .unwrap();
+        // Note: Cannot overflow, 2^128 * 2^128 < 2^256
+        let earned = U256::from(claimable_per_effective_stake)
+            .checked_mul(U256::from(self.effective_stake_u128()))
+            .unwrap()
+            .checked_div(U256::from(SCALE_FACTOR_BASE_SQUARED))
+            .unwrap()
+            .as_u64();
+        self.unclaimed = self.unclaimed.checked_add(earned).unwrap();
+        self.rewards_per_effective_stake = reward_pool.rewards_per_effective_stake;
+
+        let total_effective_stake = reward_pool
+            .total_effective_stake_u128()
+            .checked_sub(self.effective_stake_u128())
+            .unwrap()
+            .checked_add(receipt_effective_stake)
+            .unwrap();
+        reward_pool.total_effective_stake = u128(total_effective_stake.to_le_bytes());
+        self.effective_stake = u128(receipt_effective_stake.to_le_bytes());
+    }
+
+    /// Sync every RewardCheckpoint of `stake_deposit_receipt` to `receipt_effective_stake`, marking
+    /// them withdrawn when `receipt_closed`. The last accounts of `remaining_accounts` must hold
+    /// an (ExternalRewardPool, RewardCheckpoint) pair per checkpoint of the receipt, the accounts
+    /// before them are returned.
+    pub fn sync_all<'a, 'info>(
+        stake_deposit_receipt: &AccountInfo<'info>,
+        remaining_accounts: &'a [AccountInfo<'info>],
+        receipt_effective_stake: primitive::u128,
+        receipt_closed: bool,
+    ) -> Result<&'a [AccountInfo<'info>]> {
+        let count = StakeDepositReceipt::get_reward_checkpoint_count(stake_deposit_receipt)?;
+        let checkpoint_accounts_len = usize::try_from(count).unwrap().checked_mul(2).unwrap();
+        let checkpoint_accounts_start = remaining_accounts
+            .len()
+            .checked_sub(checkpoint_accounts_len)
+            .ok_or(ErrorCode::InvalidRewardCheckpoints)?;
+        let (remaining_accounts, checkpoint_accounts) =
+            remaining_accounts.split_at(checkpoint_accounts_start);
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let mut synced: Vec<Pubkey> = Vec::with_capacity(usize::try_from(count).unwrap());
+        for accounts in checkpoint_accounts.chunks_exact(2) {
+            let mut reward_pool: Account<ExternalRewardPool> = Account::try_from(&accounts[0])?;
+            let mut reward_checkpoint: Account<RewardCheckpoint> =
+                Account::try_from(&accounts[1])?;
+            // each checkpoint of the receipt must be passed once
+            if reward_checkpoint.reward_pool != reward_pool.key()
+                || reward_checkpoint.stake_deposit_receipt != stake_deposit_receipt.key()
+                || synced.contains(&reward_checkpoint.key())
+            {
+                return err!(ErrorCode::InvalidRewardCheckpoints);
+            }
+            synced.push(reward_checkpoint.key());
+            reward_checkpoint.sync(&mut reward_pool, receipt_effective_stake, current_timestamp);
+            reward_checkpoint.receipt_closed = receipt_closed;
+            reward_pool.exit(&crate::ID)?;
+            reward_checkpoint.exit(&crate::ID)?;
+        }
+        Ok(remaining_accounts)
+    }
+}
+
+/// Allows claiming the rewards of an owner's StakeDepositReceipts on their behalf with
+/// `crank_claim`. Rewards are always transferred to the owner's associated token accounts.
+#[account]
+pub struct ClaimDelegate {
+    /** Owner of the StakeDepositReceipts */
+    pub owner: Pubkey,
+    /** StakePool the StakeDepositReceipts belong to */
+    pub stake_pool: Pubkey,
+    /** Pubkey allowed to claim on behalf of the owner. Pubkey default allows anyone */
+    pub delegate: Pubkey,
+}
+
+impl ClaimDelegate {
+    pub const LEN: usize = std::mem::size_of::<ClaimDelegate>();
+
+    /// Whether `key` may claim on behalf of the owner
+    pub fn can_claim(&self, key: &Pubkey) -> bool {
+        self.delegate == Pubkey::default() || self.delegate == *key
+    }
+}
+
+/// Implements the Anchor account traits for SPL Governance addin accounts, which are owned by the
+/// addin program and start with the 8 byte discriminator defined by the addin api, the first 8
+/// bytes of `sha256("account:<AccountName>")`.
+macro_rules! governance_addin_account {
+    ($account:ty, $discriminator:expr) => {
+        impl Discriminator for $account {
+            const DISCRIMINATOR: [u8; 8] = $discriminator;
+        }
+
+        impl Owner for $account {
+            fn owner() -> Pubkey {
+                crate::ID
+            }
+        }
+
+        impl AccountSerialize for $account {
+            fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+                writer
+                    .write_all(&<$account>::DISCRIMINATOR)
+                    .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotSerialize)?;
+                AnchorSerialize::serialize(self, writer)
+                    .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotSerialize)?;
+                Ok(())
+            }
+        }
+
+        impl AccountDeserialize for $account {
+            fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+                if buf.len() < 8 || buf[..8] != <$account>::DISCRIMINATOR {
+                    return err!(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch);
+                }
+                Self::try_deserialize_unchecked(buf)
+            }
+
+            fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+                let mut data: &[u8] = &buf[8..];
+                AnchorDeserialize::deserialize(&mut data)
+                    .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into())
+            }
+        }
+    };
+}
+
+/// Governance action a [VoterWeightRecord] may be restricted to. Defined by the SPL Governance
+/// addin api.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum VoterWeightAction {
+    CastVote,
+    CommentProposal,
+    CreateGovernance,
+    CreateProposal,
+    SignOffProposal,
+}
+
+/// Voting weight of a `governing_token_owner`, in the layout of the SPL Governance addin api.
+/// The weight is the sum of the owner's StakeDepositReceipts effective stake in `stake_mint`
+/// tokens, for Realms using the StakePool's `stake_mint` as their governing token.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct VoterWeightRecord {
+    /** The Realm the VoterWeightRecord belongs to */
+    pub realm: Pubkey,
+    /** Governing Token Mint of the Realm, the `stake_mint` of the StakePool */
+    pub governing_token_mint: Pubkey,
+    /** Owner of the StakeDepositReceipts the weight is derived from */
+    pub governing_token_owner: Pubkey,
+    /** Voter's weight at `voter_weight_expiry` */
+    pub voter_weight: u64,
+    /// Slot the weight is valid for. SPL Governance only accepts the weight in this slot, so it
+    /// must be updated in the same transaction as the governance instruction.
+    pub voter_weight_expiry: Option<u64>,
+    /** Governance action the weight is valid for, any action when None */
+    pub weight_action: Option<VoterWeightAction>,
+    /** Target of `weight_action`, any target when None */
+    pub weight_action_target: Option<Pubkey>,
+    /** Reserved by the addin api */
+    pub reserved: [u8; 8],
+}
+
+governance_addin_account!(
+    VoterWeightRecord,
+    [0x2e, 0xf9, 0x9b, 0x4b, 0x99, 0xf8, 0x74, 0x09]
+);
+
+impl VoterWeightRecord {
+    pub const LEN: usize = 8 + 32 * 3 + 8 + (1 + 8) + (1 + 1) + (1 + 32) + 8;
+}
+
+/// Maximum voting weight of a Realm's governing token, in the layout of the SPL Governance
+/// addin api. The weight is the StakePool's `total_weighted_stake` in `stake_mint` tokens.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MaxVoterWeightRecord {
+    /** The Realm the MaxVoterWeightRecord belongs to */
+    pub realm: Pubkey,
+    /** Governing Token Mint of the Realm, the `stake_mint` of the StakePool */
+    pub governing_token_mint: Pubkey,
+    /** Max voter weight at `max_voter_weight_expiry` */
+    pub max_voter_weight: u64,
+    /** Slot the max weight is valid for, see [VoterWeightRecord] */
+    pub max_voter_weight_expiry: Option<u64>,
+    /** Reserved by the addin api */
+    pub reserved: [u8; 8],
+}
+
+governance_addin_account!(
+    MaxVoterWeightRecord,
+    [0x9d, 0x5f, 0xf2, 0x97, 0x10, 0x62, 0x1a, 0x76]
+);
+
+impl MaxVoterWeightRecord {
+    pub const LEN: usize = 8 + 32 * 2 + 8 + (1 + 8) + 8;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -451,6 +1815,314 @@ mod tests {
         assert_eq!(stake_pool.get_stake_weight(max_duration + 1), max_weight);
     }
 
+    #[test]
+    fn get_effective_stake_for_amount_is_proportional() {
+        let stake_pool = generic_stakepool();
+        let weight = stake_pool.get_stake_weight(150);
+        let deposit_amount = 1_000_000_000;
+        let mut receipt = StakeDepositReceipt {
+            owner: Pubkey::default(),
+            payer: Pubkey::default(),
+            stake_pool: Pubkey::default(),
+            lockup_duration: 150,
+            deposit_timestamp: 0,
+            deposit_amount,
+            effective_stake: u128(
+                StakeDepositReceipt::get_effective_stake_amount(weight, deposit_amount)
+                    .to_le_bytes(),
+            ),
+            claimed_amounts: Default::default(),
+        };
+        assert_eq!(
+            receipt.get_effective_stake_for_amount(deposit_amount / 4),
+            StakeDepositReceipt::get_effective_stake_amount(weight, deposit_amount / 4)
+        );
+        assert_eq!(
+            receipt.get_effective_stake_for_amount(deposit_amount),
+            receipt.effective_stake_u128()
+        );
+        receipt.deposit_amount = 0;
+        assert_eq!(receipt.get_effective_stake_for_amount(1), 0);
+    }
+
+    #[test]
+    fn reward_pool_is_sweepable_after_claim_window() {
+        let mut reward_pool = RewardPool::new(&Pubkey::new_unique(), u128::default());
+        assert!(!reward_pool.is_sweepable(i64::MAX));
+        reward_pool.retired_at = 100;
+        assert!(!reward_pool.is_sweepable(100 + RETIRED_REWARD_POOL_CLAIM_WINDOW - 1));
+        assert!(reward_pool.is_sweepable(100 + RETIRED_REWARD_POOL_CLAIM_WINDOW));
+    }
+
+    #[test]
+    fn get_receipt_claimed_amount_respects_reused_slot_base() {
+        let stake_pool = generic_stakepool();
+        let mut extension = RewardPoolExtension::zeroed();
+        let mut receipt = StakeDepositReceipt {
+            owner: Pubkey::default(),
+            payer: Pubkey::default(),
+            stake_pool: Pubkey::default(),
+            lockup_duration: 0,
+            deposit_timestamp: 0,
+            deposit_amount: 0,
+            effective_stake: u128::default(),
+            claimed_amounts: Default::default(),
+        };
+        receipt.claimed_amounts[1] = u128(10u128.to_le_bytes());
+        assert_eq!(stake_pool.get_receipt_claimed_amount(&extension, &receipt, 1), 10);
+
+        // slot was reused after the previous RewardPool reached 50
+        extension.base_rewards_per_effective_stake = u128(50u128.to_le_bytes());
+        assert_eq!(stake_pool.get_receipt_claimed_amount(&extension, &receipt, 1), 50);
+
+        receipt.claimed_amounts[1] = u128(75u128.to_le_bytes());
+        assert_eq!(stake_pool.get_receipt_claimed_amount(&extension, &receipt, 1), 75);
+        assert_eq!(
+            stake_pool.get_receipt_claimed_amount(&RewardPoolExtension::zeroed(), &receipt, 0),
+            0
+        );
+    }
+
+    #[test]
+    fn stream_rewards_is_bounded_by_schedule_and_undistributed_amount() {
+        let mut extension = RewardPoolExtension::new(u128::default());
+        extension.rewards_per_second = 10;
+        extension.start_time = 100;
+        extension.end_time = 200;
+        extension.last_update_time = 50;
+
+        // funding before the start is held back entirely
+        assert_eq!(extension.stream_rewards(5_000, 80), 0);
+        assert_eq!(extension.undistributed_amount, 5_000);
+        assert_eq!(extension.stream_rewards(0, 150), 500);
+        assert_eq!(extension.last_update_time, 150);
+        // only the rest of the schedule is emitted at the end and streaming stops
+        assert_eq!(extension.stream_rewards(0, 1_000), 500);
+        assert_eq!(extension.undistributed_amount, 4_000);
+        assert!(!extension.is_streaming());
+
+        // emission never exceeds the tokens received
+        extension.rewards_per_second = 1_000_000;
+        extension.start_time = 2_000;
+        extension.end_time = 10_000;
+        assert_eq!(extension.stream_rewards(4_000, 3_000), 8_000);
+        assert_eq!(extension.undistributed_amount, 0);
+        assert!(extension.is_streaming());
+    }
+
+    #[test]
+    fn stream_rewards_carries_funding_beyond_the_schedule() {
+        let mut extension = RewardPoolExtension::new(u128::default());
+        extension.schedule_emission(1_000, 100, 100);
+        assert_eq!(extension.rewards_per_second, 10);
+
+        // funding exceeding the schedule is not dumped at the end
+        assert_eq!(extension.stream_rewards(5_000, 150), 500);
+        assert_eq!(extension.stream_rewards(0, 250), 500);
+        assert_eq!(extension.undistributed_amount, 5_000);
+        assert!(!extension.is_streaming());
+        assert_eq!(extension.get_emission_amount(1_000), 0);
+
+        // and is streamed by the next schedule instead
+        extension.schedule_emission(1_000, 600, 1_000);
+        assert_eq!(extension.rewards_per_second, 10);
+        assert_eq!(extension.stream_rewards(0, 1_300), 3_000);
+        assert_eq!(extension.stream_rewards(0, 1_600), 3_000);
+        assert_eq!(extension.undistributed_amount, 0);
+    }
+
+    #[test]
+    fn schedule_emission_emits_everything_by_the_end() {
+        let mut extension = RewardPoolExtension::new(u128::default());
+        extension.schedule_emission(1_000, 3, 100);
+        assert_eq!(extension.rewards_per_second, 334);
+
+        assert_eq!(extension.stream_rewards(0, 102), 668);
+        assert_eq!(extension.stream_rewards(0, 103), 332);
+        assert_eq!(extension.undistributed_amount, 0);
+        assert!(!extension.is_streaming());
+    }
+
+    #[test]
+    fn get_early_withdraw_penalty_scales_with_remaining_lockup() {
+        let mut stake_pool = generic_stakepool();
+        stake_pool.early_withdraw_penalty_bps = 1_000;
+        let receipt = StakeDepositReceipt {
+            owner: Pubkey::default(),
+            payer: Pubkey::default(),
+            stake_pool: Pubkey::default(),
+            lockup_duration: 200,
+            deposit_timestamp: 1_000,
+            deposit_amount: 1_000_000,
+            effective_stake: u128::default(),
+            claimed_amounts: Default::default(),
+        };
+        assert_eq!(stake_pool.get_early_withdraw_penalty(&receipt, 1_000_000, 1_000), 100_000);
+        assert_eq!(stake_pool.get_early_withdraw_penalty(&receipt, 1_000_000, 1_150), 25_000);
+        assert_eq!(stake_pool.get_early_withdraw_penalty(&receipt, 1_000_000, 1_200), 0);
+        assert_eq!(stake_pool.get_early_withdraw_penalty(&receipt, 500_000, 1_100), 25_000);
+    }
+
+    #[test]
+    fn get_stake_weight_step_tiers() {
+        let mut stake_pool = mock_stakepool(SCALE_FACTOR_BASE, 3 * SCALE_FACTOR_BASE, 0, 400);
+        let tiers = vec![
+            WeightCurvePoint { duration: 30, weight: SCALE_FACTOR_BASE * 5 / 4 },
+            WeightCurvePoint { duration: 90, weight: SCALE_FACTOR_BASE * 3 / 2 },
+            WeightCurvePoint { duration: 180, weight: SCALE_FACTOR_BASE * 2 },
+            WeightCurvePoint { duration: 365, weight: SCALE_FACTOR_BASE * 3 },
+        ];
+        let curve = WeightCurve::Step { tiers };
+        assert!(curve.validate(stake_pool.base_weight, stake_pool.max_weight, 0, 400).is_ok());
+        stake_pool.weight_curve = WeightCurveData::from(&curve);
+        assert_eq!(stake_pool.get_stake_weight(29), SCALE_FACTOR_BASE);
+        assert_eq!(stake_pool.get_stake_weight(30), SCALE_FACTOR_BASE * 5 / 4);
+        assert_eq!(stake_pool.get_stake_weight(179), SCALE_FACTOR_BASE * 3 / 2);
+        assert_eq!(stake_pool.get_stake_weight(365), SCALE_FACTOR_BASE * 3);
+        assert_eq!(stake_pool.get_stake_weight(1_000), SCALE_FACTOR_BASE * 3);
+    }
+
+    #[test]
+    fn get_stake_weight_piecewise() {
+        let mut stake_pool = generic_stakepool();
+        let curve = WeightCurve::Piecewise {
+            points: vec![
+                WeightCurvePoint { duration: 150, weight: SCALE_FACTOR_BASE * 3 / 2 },
+                WeightCurvePoint { duration: 250, weight: SCALE_FACTOR_BASE * 2 },
+            ],
+        };
+        stake_pool.weight_curve = WeightCurveData::from(&curve);
+        // min duration 100 at base weight 1 up to 1.5 at 150
+        assert_eq!(stake_pool.get_stake_weight(100), SCALE_FACTOR_BASE);
+        assert_eq!(stake_pool.get_stake_weight(125), SCALE_FACTOR_BASE * 5 / 4);
+        assert_eq!(stake_pool.get_stake_weight(200), SCALE_FACTOR_BASE * 7 / 4);
+        assert_eq!(stake_pool.get_stake_weight(300), SCALE_FACTOR_BASE * 2);
+    }
+
+    #[test]
+    fn get_stake_weight_quadratic() {
+        let mut stake_pool = generic_stakepool();
+        stake_pool.weight_curve = WeightCurveData::from(&WeightCurve::Power { exponent: 2 });
+        assert_eq!(stake_pool.get_stake_weight(100), SCALE_FACTOR_BASE);
+        // halfway through the durations is a quarter of the way through the weights
+        assert_eq!(stake_pool.get_stake_weight(150), SCALE_FACTOR_BASE * 5 / 4);
+        assert_eq!(stake_pool.get_stake_weight(200), SCALE_FACTOR_BASE * 2);
+    }
+
+    #[test]
+    fn get_stake_weight_exponential() {
+        let mut stake_pool = generic_stakepool();
+        stake_pool.weight_curve =
+            WeightCurveData::from(&WeightCurve::Exponential { doublings: 4 });
+        assert_eq!(stake_pool.get_stake_weight(100), SCALE_FACTOR_BASE);
+        // (2^2 - 1) / (2^4 - 1) of the way through the weights
+        assert_eq!(stake_pool.get_stake_weight(150), SCALE_FACTOR_BASE * 6 / 5);
+        assert_eq!(stake_pool.get_stake_weight(175), 1_466_666_666);
+        assert_eq!(stake_pool.get_stake_weight(200), SCALE_FACTOR_BASE * 2);
+
+        // fractional powers of 2, sqrt(2) = 1.414213562
+        stake_pool.weight_curve =
+            WeightCurveData::from(&WeightCurve::Exponential { doublings: 1 });
+        assert_eq!(stake_pool.get_stake_weight(150), 1_414_213_559);
+        stake_pool.weight_curve = WeightCurveData::from(&WeightCurve::Exponential {
+            doublings: MAX_WEIGHT_CURVE_DOUBLINGS,
+        });
+        // 1 + 2^-0.32 = 1.801069877
+        assert_eq!(stake_pool.get_stake_weight(199), 1_801_069_875);
+        assert_eq!(stake_pool.get_stake_weight(200), SCALE_FACTOR_BASE * 2);
+    }
+
+    #[test]
+    fn weight_curve_validate() {
+        let point = |duration, weight| WeightCurvePoint { duration, weight };
+        let base = SCALE_FACTOR_BASE;
+        let max = 2 * SCALE_FACTOR_BASE;
+        assert!(WeightCurve::Linear.validate(base, max, 0, 100).is_ok());
+        assert!(WeightCurve::Power { exponent: 0 }.validate(base, max, 0, 100).is_err());
+        assert!(WeightCurve::Exponential { doublings: 0 }
+            .validate(base, max, 0, 100)
+            .is_err());
+        assert!(WeightCurve::Exponential {
+            doublings: MAX_WEIGHT_CURVE_DOUBLINGS + 1
+        }
+        .validate(base, max, 0, 100)
+        .is_err());
+        assert!(WeightCurve::Step { tiers: vec![] }.validate(base, max, 0, 100).is_err());
+        // above max weight
+        let tiers = vec![point(10, max + 1)];
+        assert!(WeightCurve::Step { tiers }.validate(base, max, 0, 100).is_err());
+        // below min duration
+        let tiers = vec![point(10, max)];
+        assert!(WeightCurve::Step { tiers }.validate(base, max, 11, 100).is_err());
+        // durations not increasing
+        let points = vec![point(10, base), point(10, max)];
+        assert!(WeightCurve::Piecewise { points }.validate(base, max, 0, 100).is_err());
+        // weights decreasing
+        let points = vec![point(10, max), point(20, base)];
+        assert!(WeightCurve::Piecewise { points }.validate(base, max, 0, 100).is_err());
+        // beyond max duration
+        let points = vec![point(10, base), point(101, max)];
+        assert!(WeightCurve::Piecewise { points }.validate(base, max, 0, 100).is_err());
+        let points = vec![point(10, base); MAX_WEIGHT_CURVE_POINTS + 1];
+        assert!(WeightCurve::Piecewise { points }.validate(base, max, 0, 100).is_err());
+    }
+
+    #[test]
+    fn weight_curve_data_round_trip() {
+        let points = vec![
+            WeightCurvePoint { duration: 10, weight: SCALE_FACTOR_BASE },
+            WeightCurvePoint { duration: 20, weight: 2 * SCALE_FACTOR_BASE },
+        ];
+        for curve in [
+            WeightCurve::Linear,
+            WeightCurve::Power { exponent: 3 },
+            WeightCurve::Exponential { doublings: 5 },
+            WeightCurve::Step { tiers: points.clone() },
+            WeightCurve::Piecewise { points },
+        ] {
+            assert_eq!(WeightCurve::from(&WeightCurveData::from(&curve)), curve);
+        }
+    }
+
+    #[test]
+    fn pauser_can_only_change_pauser_flags() {
+        let mut stake_pool = generic_stakepool();
+        stake_pool.authority = Pubkey::new_unique();
+        let pauser = Pubkey::new_unique();
+        assert!(!stake_pool.can_set_flags(&pauser, DISABLE_DEPOSITS));
+        assert!(!stake_pool.is_pauser(&Pubkey::default()));
+
+        stake_pool.pauser = pauser;
+        stake_pool.flags = WITHDRAW_IGNORES_LP;
+        assert!(stake_pool.can_set_flags(&pauser, WITHDRAW_IGNORES_LP | DISABLE_DEPOSITS));
+        assert!(stake_pool.can_set_flags(&pauser, WITHDRAW_IGNORES_LP | PAUSER_FLAGS.bits()));
+        assert!(!stake_pool.can_set_flags(&pauser, DISABLE_DEPOSITS));
+        assert!(!stake_pool.can_set_flags(&pauser, WITHDRAW_IGNORES_LP | DEPOSIT_IGNORES_LP));
+        assert!(stake_pool.can_set_flags(&stake_pool.authority, 0));
+        assert!(!stake_pool.is_reward_manager(&pauser));
+
+        // unknown bits, such as the legacy bit 2, may be kept but not cleared by the pauser
+        stake_pool.flags = WITHDRAW_IGNORES_LP | 2;
+        assert!(stake_pool.can_set_flags(&pauser, stake_pool.flags | DISABLE_DEPOSITS));
+        assert!(!stake_pool.can_set_flags(&pauser, WITHDRAW_IGNORES_LP | DISABLE_DEPOSITS));
+    }
+
+    #[test]
+    fn pool_flags_ignore_unknown_bits() {
+        let mut stake_pool = generic_stakepool();
+        stake_pool.flags = ESCAPE_HATCH_ENABLED | WITHDRAW_IGNORES_LP | 2;
+        assert_eq!(
+            stake_pool.pool_flags(),
+            PoolFlags::ESCAPE_HATCH_ENABLED | PoolFlags::WITHDRAW_IGNORES_LP
+        );
+        assert!(stake_pool.escape_hatch_enabled());
+        assert!(!stake_pool.deposits_disabled());
+        assert!(!stake_pool.deposits_ignores_lp());
+        assert!(stake_pool.withdraw_ignores_lp());
+        assert!(PoolFlags::from_bits(2).is_none());
+        assert!(PoolFlags::from_bits(PAUSER_FLAGS.bits() | DEPOSIT_IGNORES_LP).is_some());
+    }
+
     // A badly configured pool where the min duration = max duration.
     #[test]
     fn get_stake_weight_min_duration_equals_max() {
@@ -460,4 +2132,218 @@ mod tests {
         let max_duration = stake_pool.max_duration;
         assert_eq!(stake_pool.get_stake_weight(max_duration + 1), base_weight);
     }
+
+    #[test]
+    fn voter_weight_record_round_trip() {
+        let record = VoterWeightRecord {
+            realm: Pubkey::new_unique(),
+            governing_token_mint: Pubkey::new_unique(),
+            governing_token_owner: Pubkey::new_unique(),
+            voter_weight: 1_000,
+            voter_weight_expiry: Some(42),
+            weight_action: Some(VoterWeightAction::CastVote),
+            weight_action_target: Some(Pubkey::new_unique()),
+            reserved: [0; 8],
+        };
+        let mut data = Vec::new();
+        record.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), VoterWeightRecord::LEN);
+        assert_eq!(data[..8], [0x2e, 0xf9, 0x9b, 0x4b, 0x99, 0xf8, 0x74, 0x09]);
+        let deserialized = VoterWeightRecord::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(deserialized.voter_weight, 1_000);
+        assert_eq!(deserialized.voter_weight_expiry, Some(42));
+
+        let mut max_data = Vec::new();
+        MaxVoterWeightRecord {
+            realm: record.realm,
+            governing_token_mint: record.governing_token_mint,
+            max_voter_weight: 0,
+            max_voter_weight_expiry: Some(0),
+            reserved: [0; 8],
+        }
+        .try_serialize(&mut max_data)
+        .unwrap();
+        assert_eq!(max_data.len(), MaxVoterWeightRecord::LEN);
+        assert_eq!(max_data[..8], [0x9d, 0x5f, 0xf2, 0x97, 0x10, 0x62, 0x1a, 0x76]);
+        assert!(VoterWeightRecord::try_deserialize(&mut max_data.as_slice()).is_err());
+    }
+
+    #[test]
+    fn is_reward_pool_selected_by_mask() {
+        assert!(is_reward_pool_selected(0b101, 0));
+        assert!(!is_reward_pool_selected(0b101, 1));
+        assert!(is_reward_pool_selected(0b101, 2));
+        assert!((0..MAX_REWARD_POOLS)
+            .all(|index| is_reward_pool_selected(ALL_REWARD_POOLS, index)));
+        assert_eq!(ALL_REWARD_POOLS >> MAX_REWARD_POOLS, 0);
+    }
+
+    #[test]
+    fn claim_delegate_can_claim() {
+        let delegate = Pubkey::new_unique();
+        let mut claim_delegate = ClaimDelegate {
+            owner: Pubkey::new_unique(),
+            stake_pool: Pubkey::new_unique(),
+            delegate,
+        };
+        assert!(claim_delegate.can_claim(&delegate));
+        assert!(!claim_delegate.can_claim(&Pubkey::new_unique()));
+        claim_delegate.delegate = Pubkey::default();
+        assert!(claim_delegate.can_claim(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn get_receipt_claimable_amount_since_last_claim() {
+        let mut stake_pool = generic_stakepool();
+        stake_pool.reward_pools[0] = RewardPool::new(
+            &Pubkey::new_unique(),
+            u128((3 * primitive::u128::from(SCALE_FACTOR_BASE)).to_le_bytes()),
+        );
+        let mut receipt = StakeDepositReceipt {
+            owner: Pubkey::default(),
+            payer: Pubkey::default(),
+            stake_pool: Pubkey::default(),
+            lockup_duration: 0,
+            deposit_timestamp: 0,
+            deposit_amount: 1_000,
+            effective_stake: u128(
+                (1_000 * primitive::u128::from(SCALE_FACTOR_BASE)).to_le_bytes(),
+            ),
+            claimed_amounts: [u128::default(); MAX_REWARD_POOLS],
+        };
+        let extension = RewardPoolExtension::zeroed();
+        assert_eq!(stake_pool.get_receipt_claimable_amount(&extension, &receipt, 0), 3_000);
+        receipt.claimed_amounts[0] = u128(primitive::u128::from(SCALE_FACTOR_BASE).to_le_bytes());
+        assert_eq!(stake_pool.get_receipt_claimable_amount(&extension, &receipt, 0), 2_000);
+    }
+
+    #[test]
+    fn get_claimable_rewards_accrues_vault_balance_changes() {
+        let scale = primitive::u128::from(SCALE_FACTOR_BASE);
+        let mut stake_pool = generic_stakepool();
+        stake_pool.total_weighted_stake = u128((2_000 * scale).to_le_bytes());
+        stake_pool.reward_pools[0] = RewardPool::new(&Pubkey::new_unique(), u128::default());
+        stake_pool.reward_pools[2] =
+            RewardPool::new(&Pubkey::new_unique(), u128(scale.to_le_bytes()));
+        stake_pool.reward_pools[2].last_amount = 5_000;
+        let receipt = StakeDepositReceipt {
+            owner: Pubkey::default(),
+            payer: Pubkey::default(),
+            stake_pool: Pubkey::default(),
+            lockup_duration: 0,
+            deposit_timestamp: 0,
+            deposit_amount: 1_000,
+            effective_stake: u128((1_000 * scale).to_le_bytes()),
+            claimed_amounts: [u128::default(); MAX_REWARD_POOLS],
+        };
+
+        let mut reward_vault_amounts = [0u64; MAX_REWARD_POOLS];
+        reward_vault_amounts[0] = 1_000;
+        reward_vault_amounts[1] = 9_999;
+        reward_vault_amounts[2] = 5_000;
+        let claimable = stake_pool.get_claimable_rewards(
+            &[RewardPoolExtension::zeroed(); MAX_REWARD_POOLS],
+            &receipt,
+            &reward_vault_amounts,
+            0,
+        )
+        .unwrap();
+        let mut expected = [0u64; MAX_REWARD_POOLS];
+        // half of the new balance of RewardPool 0 and the previously accrued rewards of 2, while
+        // the balance of the empty RewardPool 1 is ignored
+        expected[0] = 500;
+        expected[2] = 1_000;
+        assert_eq!(claimable, expected);
+        // the StakePool itself is left untouched
+        assert_eq!(stake_pool.reward_pools[0].last_amount, 0);
+
+        // a balance below the last recorded amount is rejected rather than panicking
+        reward_vault_amounts[2] = 4_999;
+        assert!(stake_pool
+            .get_claimable_rewards(
+                &[RewardPoolExtension::zeroed(); MAX_REWARD_POOLS],
+                &receipt,
+                &reward_vault_amounts,
+                0,
+            )
+            .is_err());
+
+        // nothing accrues without stake
+        stake_pool.total_weighted_stake = u128::default();
+        reward_vault_amounts[2] = 5_000;
+        let claimable = stake_pool
+            .get_claimable_rewards(
+                &[RewardPoolExtension::zeroed(); MAX_REWARD_POOLS],
+                &receipt,
+                &reward_vault_amounts,
+                0,
+            )
+            .unwrap();
+        expected[0] = 0;
+        assert_eq!(claimable, expected);
+    }
+
+    #[test]
+    fn external_reward_pool_streams_funding_to_synced_effective_stake() {
+        let scale = primitive::u128::from(SCALE_FACTOR_BASE);
+        let mut reward_pool = ExternalRewardPool {
+            stake_pool: Pubkey::default(),
+            reward_mint: Pubkey::default(),
+            reward_vault: Pubkey::default(),
+            token_program: Pubkey::default(),
+            total_effective_stake: u128::default(),
+            rewards_per_effective_stake: u128::default(),
+            rewards_per_second: 0,
+            start_time: 0,
+            end_time: 0,
+            last_update_time: 0,
+            undistributed_amount: 0,
+            retired_at: 0,
+            id: 0,
+        };
+        let new_checkpoint = |reward_pool: &mut ExternalRewardPool, effective_stake, timestamp| {
+            reward_pool.accrue(timestamp);
+            let mut checkpoint = RewardCheckpoint {
+                owner: Pubkey::default(),
+                reward_pool: Pubkey::default(),
+                stake_deposit_receipt: Pubkey::default(),
+                effective_stake: u128::default(),
+                rewards_per_effective_stake: reward_pool.rewards_per_effective_stake,
+                unclaimed: 0,
+                receipt_closed: false,
+            };
+            checkpoint.sync(reward_pool, effective_stake, timestamp);
+            checkpoint
+        };
+
+        // emission without any stake is carried into the next schedule
+        reward_pool.schedule_emission(1_000, 100, 0);
+        let mut checkpoint_a = new_checkpoint(&mut reward_pool, 1_000 * scale, 50);
+        assert_eq!(reward_pool.undistributed_amount, 1_000);
+        checkpoint_a.sync(&mut reward_pool, 1_000 * scale, 100);
+        assert_eq!(checkpoint_a.unclaimed, 500);
+        assert_eq!(reward_pool.undistributed_amount, 500);
+        assert!(!reward_pool.is_streaming());
+
+        reward_pool.accrue(100);
+        reward_pool.schedule_emission(3_500, 100, 100);
+        assert_eq!(reward_pool.rewards_per_second, 40);
+        // joining right after a fund only earns the share emitted while staked
+        let mut checkpoint_b = new_checkpoint(&mut reward_pool, 3_000 * scale, 101);
+        assert_eq!(reward_pool.total_effective_stake_u128(), 4_000 * scale);
+        checkpoint_a.sync(&mut reward_pool, 1_000 * scale, 200);
+        checkpoint_b.sync(&mut reward_pool, 3_000 * scale, 200);
+        assert_eq!(checkpoint_a.unclaimed, 500 + 40 + 990);
+        assert_eq!(checkpoint_b.unclaimed, 2_970);
+        assert_eq!(reward_pool.undistributed_amount, 0);
+
+        // rewards are earned on the synced effective stake, so none are stranded
+        reward_pool.schedule_emission(4_000, 100, 200);
+        checkpoint_b.sync(&mut reward_pool, 1_000 * scale, 250);
+        checkpoint_a.sync(&mut reward_pool, 1_000 * scale, 300);
+        checkpoint_b.sync(&mut reward_pool, 0, 300);
+        assert_eq!(checkpoint_a.unclaimed, 1_530 + 500 + 1_000);
+        assert_eq!(checkpoint_b.unclaimed, 2_970 + 1_500 + 1_000);
+        assert_eq!(reward_pool.total_effective_stake_u128(), 1_000 * scale);
+    }
 }
@@ -0,0 +1,232 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import {
+  createDepositorSplAccounts,
+  mintToBeStaked,
+  rewardMint1,
+  rewardMint2,
+} from "./hooks";
+import {
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createTransferInstruction,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { addRewardPool, initStakePool } from "@mithraic-labs/token-staking";
+import { deposit } from "./utils";
+import { assertBNEqual } from "./genericTests";
+
+describe("claim-selected", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const depositor = new anchor.web3.Keypair();
+  const stakePoolNonce = 50;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const [rewardVault1Key, rewardVault2Key] = [rewardMint1, rewardMint2].map(
+    (rewardMint) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          stakePoolKey.toBuffer(),
+          rewardMint.toBuffer(),
+          Buffer.from("rewardVault", "utf-8"),
+        ],
+        program.programId
+      )[0]
+  );
+  const mintToBeStakedAccountKey = getAssociatedTokenAddressSync(
+    mintToBeStaked,
+    depositor.publicKey
+  );
+  const stakeMintAccountKey = getAssociatedTokenAddressSync(
+    stakeMint,
+    depositor.publicKey
+  );
+  const depositorReward1AccountKey = getAssociatedTokenAddressSync(
+    rewardMint1,
+    depositor.publicKey
+  );
+  const depositorReward2AccountKey = getAssociatedTokenAddressSync(
+    rewardMint2,
+    depositor.publicKey
+  );
+  const receiptNonce = 0;
+  const [stakeReceiptKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      depositor.publicKey.toBuffer(),
+      stakePoolKey.toBuffer(),
+      new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+      Buffer.from("stakeDepositReceipt", "utf-8"),
+    ],
+    program.programId
+  );
+  const totalReward = 1_000_000_000;
+
+  before(async () => {
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor, stakePoolNonce),
+      initStakePool(program, mintToBeStaked, stakePoolNonce),
+    ]);
+    await addRewardPool(program, stakePoolNonce, mintToBeStaked, rewardMint1);
+    await addRewardPool(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      rewardMint2,
+      1
+    );
+    await deposit(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      depositor,
+      mintToBeStakedAccountKey,
+      stakeMintAccountKey,
+      new anchor.BN(1_000_000_000),
+      new anchor.BN(0),
+      receiptNonce,
+      [rewardVault1Key, rewardVault2Key]
+    );
+    // fund both RewardPools, but only give the depositor an account for the
+    // second reward mint
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createTransferInstruction(
+          getAssociatedTokenAddressSync(
+            rewardMint1,
+            program.provider.publicKey
+          ),
+          rewardVault1Key,
+          program.provider.publicKey,
+          totalReward
+        ),
+        createTransferInstruction(
+          getAssociatedTokenAddressSync(
+            rewardMint2,
+            program.provider.publicKey
+          ),
+          rewardVault2Key,
+          program.provider.publicKey,
+          totalReward
+        ),
+        createAssociatedTokenAccountInstruction(
+          program.provider.publicKey,
+          depositorReward2AccountKey,
+          depositor.publicKey,
+          rewardMint2,
+          TOKEN_PROGRAM_ID
+        )
+      )
+    );
+  });
+
+  it("Claim only the selected RewardPool", async () => {
+    await program.methods
+      .claimSelected(0b10)
+      .accounts({
+        claimBase: {
+          owner: depositor.publicKey,
+          stakePool: stakePoolKey,
+          stakeDepositReceipt: stakeReceiptKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+      })
+      .remainingAccounts([
+        { pubkey: rewardVault2Key, isWritable: true, isSigner: false },
+        {
+          pubkey: depositorReward2AccountKey,
+          isWritable: true,
+          isSigner: false,
+        },
+      ])
+      .signers([depositor])
+      .rpc();
+
+    const [depositorReward2Account, stakeReceipt, stakePool] =
+      await Promise.all([
+        getAccount(program.provider.connection, depositorReward2AccountKey),
+        program.account.stakeDepositReceipt.fetch(stakeReceiptKey),
+        program.account.stakePool.fetch(stakePoolKey),
+      ]);
+    assert.equal(depositorReward2Account.amount, BigInt(totalReward));
+    assertBNEqual(stakeReceipt.claimedAmounts[0], 0);
+    assertBNEqual(stakeReceipt.claimedAmounts[1], totalReward);
+    // the first RewardPool has not been synced yet
+    assertBNEqual(stakePool.rewardPools[0].rewardsPerEffectiveStake, 0);
+    assertBNEqual(stakePool.rewardPools[1].lastAmount, 0);
+  });
+
+  it("Claim the remaining RewardPool later", async () => {
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createAssociatedTokenAccountInstruction(
+          program.provider.publicKey,
+          depositorReward1AccountKey,
+          depositor.publicKey,
+          rewardMint1,
+          TOKEN_PROGRAM_ID
+        )
+      )
+    );
+    await program.methods
+      .claimSelected(0b01)
+      .accounts({
+        claimBase: {
+          owner: depositor.publicKey,
+          stakePool: stakePoolKey,
+          stakeDepositReceipt: stakeReceiptKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+      })
+      .remainingAccounts([
+        { pubkey: rewardVault1Key, isWritable: true, isSigner: false },
+        {
+          pubkey: depositorReward1AccountKey,
+          isWritable: true,
+          isSigner: false,
+        },
+      ])
+      .signers([depositor])
+      .rpc();
+
+    const depositorReward1Account = await getAccount(
+      program.provider.connection,
+      depositorReward1AccountKey
+    );
+    assert.equal(depositorReward1Account.amount, BigInt(totalReward));
+  });
+
+  it("Fail to select an empty RewardPool", async () => {
+    try {
+      await program.methods
+        .claimSelected(0b100)
+        .accounts({
+          claimBase: {
+            owner: depositor.publicKey,
+            stakePool: stakePoolKey,
+            stakeDepositReceipt: stakeReceiptKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+        })
+        .signers([depositor])
+        .rpc();
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidRewardPoolMask");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+});
@@ -0,0 +1,222 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import { createDepositorSplAccounts, mintToBeStaked } from "./hooks";
+import {
+  TOKEN_PROGRAM_ID,
+  createMintToInstruction,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { addRewardPool, initStakePool } from "@mithraic-labs/token-staking";
+import { deposit } from "./utils";
+import { assertBNEqual } from "./genericTests";
+
+describe("compound", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const depositor = new anchor.web3.Keypair();
+  const stakePoolNonce = 52;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [vaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("vault", "utf-8")],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  // RewardPool distributing the staked mint
+  const [rewardVaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      stakePoolKey.toBuffer(),
+      mintToBeStaked.toBuffer(),
+      Buffer.from("rewardVault", "utf-8"),
+    ],
+    program.programId
+  );
+  const mintToBeStakedAccountKey = getAssociatedTokenAddressSync(
+    mintToBeStaked,
+    depositor.publicKey
+  );
+  const stakeMintAccountKey = getAssociatedTokenAddressSync(
+    stakeMint,
+    depositor.publicKey
+  );
+  const receiptNonce = 0;
+  const [stakeReceiptKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      depositor.publicKey.toBuffer(),
+      stakePoolKey.toBuffer(),
+      new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+      Buffer.from("stakeDepositReceipt", "utf-8"),
+    ],
+    program.programId
+  );
+  const totalReward = 500_000_000;
+
+  before(async () => {
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor, stakePoolNonce),
+      initStakePool(program, mintToBeStaked, stakePoolNonce),
+    ]);
+    await addRewardPool(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      mintToBeStaked
+    );
+    await deposit(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      depositor,
+      mintToBeStakedAccountKey,
+      stakeMintAccountKey,
+      new anchor.BN(1_000_000_000),
+      new anchor.BN(0),
+      receiptNonce,
+      [rewardVaultKey]
+    );
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createMintToInstruction(
+          mintToBeStaked,
+          rewardVaultKey,
+          program.provider.publicKey,
+          totalReward
+        )
+      )
+    );
+  });
+
+  it("Compound rewards of the staked mint into the receipt", async () => {
+    const [receiptBefore, stakePoolBefore, stakeMintAccountBefore] =
+      await Promise.all([
+        program.account.stakeDepositReceipt.fetch(stakeReceiptKey),
+        program.account.stakePool.fetch(stakePoolKey),
+        getAccount(program.provider.connection, stakeMintAccountKey),
+      ]);
+
+    await program.methods
+      .compound(0)
+      .accounts({
+        claimBase: {
+          owner: depositor.publicKey,
+          stakePool: stakePoolKey,
+          stakeDepositReceipt: stakeReceiptKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+        vault: vaultKey,
+        mint: mintToBeStaked,
+        rewardVault: rewardVaultKey,
+        stakeMint,
+        destination: stakeMintAccountKey,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: rewardVaultKey, isWritable: true, isSigner: false },
+        { pubkey: mintToBeStakedAccountKey, isWritable: true, isSigner: false },
+      ])
+      .signers([depositor])
+      .rpc();
+
+    const [receipt, stakePool, stakeMintAccount, rewardVault] =
+      await Promise.all([
+        program.account.stakeDepositReceipt.fetch(stakeReceiptKey),
+        program.account.stakePool.fetch(stakePoolKey),
+        getAccount(program.provider.connection, stakeMintAccountKey),
+        getAccount(program.provider.connection, rewardVaultKey),
+      ]);
+    assertBNEqual(
+      receipt.depositAmount,
+      receiptBefore.depositAmount.addn(totalReward)
+    );
+    // staked at the receipt's existing weight of 1
+    const additionalEffectiveStake = new anchor.BN(totalReward).mul(
+      new anchor.BN(1_000_000_000)
+    );
+    assertBNEqual(
+      receipt.effectiveStake,
+      receiptBefore.effectiveStake.add(additionalEffectiveStake)
+    );
+    assertBNEqual(
+      stakePool.totalWeightedStake,
+      stakePoolBefore.totalWeightedStake.add(additionalEffectiveStake)
+    );
+    assert.equal(
+      stakeMintAccount.amount,
+      stakeMintAccountBefore.amount + BigInt(totalReward)
+    );
+    assert.equal(rewardVault.amount, BigInt(0));
+    assertBNEqual(stakePool.rewardPools[0].lastAmount, 0);
+  });
+
+  it("Fail to compound into a receipt whose lockup has ended", async () => {
+    const lockedReceiptNonce = 1;
+    const [lockedReceiptKey] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        depositor.publicKey.toBuffer(),
+        stakePoolKey.toBuffer(),
+        new anchor.BN(lockedReceiptNonce).toArrayLike(Buffer, "le", 4),
+        Buffer.from("stakeDepositReceipt", "utf-8"),
+      ],
+      program.programId
+    );
+    await deposit(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      depositor,
+      mintToBeStakedAccountKey,
+      stakeMintAccountKey,
+      new anchor.BN(1_000_000_000),
+      new anchor.BN(1),
+      lockedReceiptNonce,
+      [rewardVaultKey]
+    );
+    // wait for the 1 second lockup to end
+    await new Promise((resolve) => setTimeout(resolve, 2_000));
+
+    try {
+      await program.methods
+        .compound(0)
+        .accounts({
+          claimBase: {
+            owner: depositor.publicKey,
+            stakePool: stakePoolKey,
+            stakeDepositReceipt: lockedReceiptKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          vault: vaultKey,
+          mint: mintToBeStaked,
+          rewardVault: rewardVaultKey,
+          stakeMint,
+          destination: stakeMintAccountKey,
+          mintTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts([
+          { pubkey: rewardVaultKey, isWritable: true, isSigner: false },
+          {
+            pubkey: mintToBeStakedAccountKey,
+            isWritable: true,
+            isSigner: false,
+          },
+        ])
+        .signers([depositor])
+        .rpc();
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "StakeDepositReceiptUnlocked");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+});
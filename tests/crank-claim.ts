@@ -0,0 +1,197 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import {
+  airdropSol,
+  createDepositorSplAccounts,
+  mintToBeStaked,
+  rewardMint1,
+} from "./hooks";
+import {
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createTransferInstruction,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { addRewardPool, initStakePool } from "@mithraic-labs/token-staking";
+import { deposit } from "./utils";
+
+describe("crank-claim", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const depositor = new anchor.web3.Keypair();
+  const delegate = new anchor.web3.Keypair();
+  const stakePoolNonce = 51;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const [rewardVaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      stakePoolKey.toBuffer(),
+      rewardMint1.toBuffer(),
+      Buffer.from("rewardVault", "utf-8"),
+    ],
+    program.programId
+  );
+  const [claimDelegateKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      depositor.publicKey.toBuffer(),
+      stakePoolKey.toBuffer(),
+      Buffer.from("claimDelegate", "utf-8"),
+    ],
+    program.programId
+  );
+  const mintToBeStakedAccountKey = getAssociatedTokenAddressSync(
+    mintToBeStaked,
+    depositor.publicKey
+  );
+  const stakeMintAccountKey = getAssociatedTokenAddressSync(
+    stakeMint,
+    depositor.publicKey
+  );
+  const depositorReward1AccountKey = getAssociatedTokenAddressSync(
+    rewardMint1,
+    depositor.publicKey
+  );
+  const providerReward1AccountKey = getAssociatedTokenAddressSync(
+    rewardMint1,
+    program.provider.publicKey
+  );
+  const receiptNonce = 0;
+  const [stakeReceiptKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      depositor.publicKey.toBuffer(),
+      stakePoolKey.toBuffer(),
+      new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+      Buffer.from("stakeDepositReceipt", "utf-8"),
+    ],
+    program.programId
+  );
+  const totalReward = 1_000_000_000;
+
+  const crankClaim = (
+    cranker: anchor.web3.Keypair,
+    ownerRewardAccount: anchor.web3.PublicKey
+  ) =>
+    program.methods
+      .crankClaim(0b1)
+      .accounts({
+        cranker: cranker.publicKey,
+        owner: depositor.publicKey,
+        stakePool: stakePoolKey,
+        stakeDepositReceipt: stakeReceiptKey,
+        claimDelegate: claimDelegateKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: rewardVaultKey, isWritable: true, isSigner: false },
+        { pubkey: ownerRewardAccount, isWritable: true, isSigner: false },
+      ])
+      .signers([cranker])
+      .rpc();
+
+  before(async () => {
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor, stakePoolNonce),
+      initStakePool(program, mintToBeStaked, stakePoolNonce),
+      airdropSol(program.provider.connection, delegate.publicKey, 1),
+    ]);
+    await addRewardPool(program, stakePoolNonce, mintToBeStaked, rewardMint1);
+    await deposit(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      depositor,
+      mintToBeStakedAccountKey,
+      stakeMintAccountKey,
+      new anchor.BN(1_000_000_000),
+      new anchor.BN(0),
+      receiptNonce,
+      [rewardVaultKey]
+    );
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createTransferInstruction(
+          providerReward1AccountKey,
+          rewardVaultKey,
+          program.provider.publicKey,
+          totalReward
+        ),
+        createAssociatedTokenAccountInstruction(
+          program.provider.publicKey,
+          depositorReward1AccountKey,
+          depositor.publicKey,
+          rewardMint1,
+          TOKEN_PROGRAM_ID
+        )
+      )
+    );
+    await program.methods
+      .setClaimDelegate(delegate.publicKey)
+      .accounts({
+        owner: depositor.publicKey,
+        stakePool: stakePoolKey,
+        claimDelegate: claimDelegateKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([depositor])
+      .rpc();
+  });
+
+  it("Fail to claim when not the claim delegate", async () => {
+    const cranker = new anchor.web3.Keypair();
+    await airdropSol(program.provider.connection, cranker.publicKey, 1);
+    try {
+      await crankClaim(cranker, depositorReward1AccountKey);
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidClaimDelegate");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+
+  it("Fail to claim to an account other than the owner's ATA", async () => {
+    try {
+      await crankClaim(delegate, providerReward1AccountKey);
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidOwnerRewardAccount");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+
+  it("Claim delegate claims to the owner's ATA", async () => {
+    await crankClaim(delegate, depositorReward1AccountKey);
+    const depositorReward1Account = await getAccount(
+      program.provider.connection,
+      depositorReward1AccountKey
+    );
+    assert.equal(depositorReward1Account.amount, BigInt(totalReward));
+  });
+
+  it("Revoke the claim delegate", async () => {
+    await program.methods
+      .revokeClaimDelegate()
+      .accounts({
+        owner: depositor.publicKey,
+        claimDelegate: claimDelegateKey,
+      })
+      .signers([depositor])
+      .rpc();
+    const claimDelegate = await program.provider.connection.getAccountInfo(
+      claimDelegateKey
+    );
+    assert.isNull(claimDelegate);
+  });
+});
@@ -127,10 +127,12 @@ describe("deposit", () => {
         from: mintToBeStakedAccount,
         stakePool: stakePoolKey,
         vault: vaultKey,
+        mint: mintToBeStaked,
         stakeMint,
         destination: stakeMintAccountKey,
         stakeDepositReceipt: stakeReceiptKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -215,9 +217,11 @@ describe("deposit", () => {
         stakeMint,
         stakePool: stakePoolKey,
         vault: vaultKey,
+        mint: mintToBeStaked,
         destination: stakeMintAccountKey,
         stakeDepositReceipt: stakeReceiptKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -306,10 +310,12 @@ describe("deposit", () => {
         from: mintToBeStakedAccount,
         stakePool: stakePoolKey,
         vault: vaultKey,
+        mint: mintToBeStaked,
         stakeMint,
         destination: stakeMintAccountKey,
         stakeDepositReceipt: stakeReceiptKey1,
         tokenProgram: TOKEN_PROGRAM_ID,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -351,10 +357,12 @@ describe("deposit", () => {
         from: mintToBeStakedAccount,
         stakePool: stakePoolKey,
         vault: vaultKey,
+        mint: mintToBeStaked,
         stakeMint,
         destination: stakeMintAccountKey,
         stakeDepositReceipt: stakeReceiptKey2,
         tokenProgram: TOKEN_PROGRAM_ID,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -440,9 +448,11 @@ describe("deposit", () => {
           stakeMint,
           stakePool: stakePoolKey,
           vault: vaultKey,
+          mint: mintToBeStaked,
           destination: stakeMintAccountKey,
           stakeDepositReceipt: stakeReceiptKey,
           tokenProgram: TOKEN_PROGRAM_ID,
+          mintTokenProgram: TOKEN_PROGRAM_ID,
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
@@ -492,9 +502,11 @@ describe("deposit", () => {
           stakeMint,
           stakePool: stakePoolKey,
           vault: vaultKey,
+          mint: mintToBeStaked,
           destination: stakeMintAccountKey,
           stakeDepositReceipt: stakeReceiptKey,
           tokenProgram: TOKEN_PROGRAM_ID,
+          mintTokenProgram: TOKEN_PROGRAM_ID,
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
@@ -552,9 +564,11 @@ describe("deposit", () => {
         stakeMint,
         stakePool: stakePoolKey,
         vault: vaultKey,
+        mint: mintToBeStaked,
         destination: stakeMintAccountKey,
         stakeDepositReceipt: stakeReceiptKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -629,10 +643,12 @@ describe("deposit", () => {
         from: mintToBeStakedAccount,
         stakePool: stakePoolKey,
         vault: vaultKey,
+        mint: mintToBeStaked,
         stakeMint,
         destination: stakeMintAccountKey,
         stakeDepositReceipt: stakeReceiptKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -717,9 +733,11 @@ describe("deposit", () => {
         stakeMint: stakeMint,
         stakePool: stakePoolKey,
         vault: vaultKey,
+        mint: mintToBeStaked,
         destination: stakeMintAccountKey,
         stakeDepositReceipt: stakeReceiptKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
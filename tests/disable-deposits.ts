@@ -173,10 +173,12 @@ describe("disable deposits flag", () => {
         from: mintToBeStakedAccountKey,
         stakePool: stakePoolKey,
         vault: vaultKey,
+        mint: mintToBeStaked,
         stakeMint: stakeMint,
         destination: stakeMintAccountKey,
         stakeDepositReceipt: stakeReceiptKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -0,0 +1,157 @@
+import * as anchor from "@coral-xyz/anchor";
+import { splTokenProgram } from "@coral-xyz/spl-token";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import { createDepositorSplAccounts, mintToBeStaked } from "./hooks";
+import {
+  TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { initStakePool } from "@mithraic-labs/token-staking";
+import { deposit } from "./utils";
+import { assertBNEqual } from "./genericTests";
+
+describe("early-withdraw", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const tokenProgramInstance = splTokenProgram({ programId: TOKEN_PROGRAM_ID });
+  const depositor1 = new anchor.web3.Keypair();
+  const treasuryOwner = new anchor.web3.Keypair();
+  const stakePoolNonce = 38;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const [vaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("vault", "utf-8")],
+    program.programId
+  );
+  const mintToBeStakedAccountKey = getAssociatedTokenAddressSync(
+    mintToBeStaked,
+    depositor1.publicKey
+  );
+  const stakeMintAccountKey = getAssociatedTokenAddressSync(
+    stakeMint,
+    depositor1.publicKey
+  );
+  const treasuryKey = getAssociatedTokenAddressSync(
+    mintToBeStaked,
+    treasuryOwner.publicKey
+  );
+  const receiptNonce = 0;
+  const [stakeReceiptKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      depositor1.publicKey.toBuffer(),
+      stakePoolKey.toBuffer(),
+      new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+      Buffer.from("stakeDepositReceipt", "utf-8"),
+    ],
+    program.programId
+  );
+  const withdraw = (treasury: anchor.web3.PublicKey | null) =>
+    program.methods
+      .withdraw()
+      .accounts({
+        claimBase: {
+          owner: depositor1.publicKey,
+          stakePool: stakePoolKey,
+          stakeDepositReceipt: stakeReceiptKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+        vault: vaultKey,
+        mint: mintToBeStaked,
+        stakeMint,
+        from: stakeMintAccountKey,
+        destination: mintToBeStakedAccountKey,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
+        treasury,
+      })
+      .signers([depositor1])
+      .rpc();
+
+  before(async () => {
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor1, stakePoolNonce),
+      createDepositorSplAccounts(program, treasuryOwner, stakePoolNonce),
+      initStakePool(program, mintToBeStaked, stakePoolNonce),
+    ]);
+    // deposit 1 token locked for a long time
+    await deposit(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      depositor1,
+      mintToBeStakedAccountKey,
+      stakeMintAccountKey,
+      new anchor.BN(1_000_000_000),
+      new anchor.BN(100_000_000),
+      receiptNonce
+    );
+  });
+
+  it("Fail to set a penalty above 100%", async () => {
+    try {
+      await program.methods
+        .setEarlyWithdrawPenalty(10_001)
+        .accounts({
+          authority: program.provider.publicKey,
+          stakePool: stakePoolKey,
+          treasury: treasuryKey,
+        })
+        .rpc();
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidEarlyWithdrawPenalty");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+
+  it("Fail to withdraw early without the treasury", async () => {
+    await program.methods
+      .setEarlyWithdrawPenalty(1_000)
+      .accounts({
+        authority: program.provider.publicKey,
+        stakePool: stakePoolKey,
+        treasury: treasuryKey,
+      })
+      .rpc();
+    try {
+      await withdraw(null);
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidTreasury");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+
+  it("withdraw early and pay the penalty to the treasury", async () => {
+    const [ownerTokenBefore, treasuryBefore] = await Promise.all([
+      tokenProgramInstance.account.account.fetch(mintToBeStakedAccountKey),
+      tokenProgramInstance.account.account.fetch(treasuryKey),
+    ]);
+    await withdraw(treasuryKey);
+    const [ownerTokenAfter, treasuryAfter, receipt] = await Promise.all([
+      tokenProgramInstance.account.account.fetch(mintToBeStakedAccountKey),
+      tokenProgramInstance.account.account.fetch(treasuryKey),
+      program.provider.connection.getAccountInfo(stakeReceiptKey),
+    ]);
+    assert.isNull(receipt, "StakeDepositReceipt account not closed");
+    const penalty = treasuryAfter.amount.sub(treasuryBefore.amount);
+    // nearly the entire lockup remains, so the penalty is close to 10%
+    assert.isTrue(penalty.lten(100_000_000));
+    assert.isTrue(penalty.gtn(99_000_000));
+    assertBNEqual(
+      ownerTokenAfter.amount.sub(ownerTokenBefore.amount),
+      new anchor.BN(1_000_000_000).sub(penalty)
+    );
+  });
+});
@@ -0,0 +1,111 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import { assert } from "chai";
+import { mintToBeStaked } from "./hooks";
+import { initStakePool } from "@mithraic-labs/token-staking";
+
+describe("enable-flags", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const stakePoolNonce = 44;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const pauser = new anchor.web3.Keypair();
+
+  before(async () => {
+    await initStakePool(program, mintToBeStaked, stakePoolNonce);
+    await program.methods
+      .setRole({ pauser: {} }, pauser.publicKey)
+      .accounts({
+        authority: program.provider.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .rpc();
+  });
+
+  it("Enable and disable flags without touching the others", async () => {
+    await program.methods
+      .enableFlags(1 + 8)
+      .accounts({
+        authority: program.provider.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .rpc();
+    let stakePool = await program.account.stakePool.fetch(stakePoolKey);
+    assert.equal(stakePool.flags, 1 + 8);
+
+    await program.methods
+      .disableFlags(1 + 16)
+      .accounts({
+        authority: program.provider.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .rpc();
+    stakePool = await program.account.stakePool.fetch(stakePoolKey);
+    assert.equal(stakePool.flags, 8);
+  });
+
+  it("Pauser enables and disables pauser flags", async () => {
+    await program.methods
+      .enableFlags(4)
+      .accounts({
+        authority: pauser.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .signers([pauser])
+      .rpc();
+    let stakePool = await program.account.stakePool.fetch(stakePoolKey);
+    assert.equal(stakePool.flags, 8 + 4);
+
+    await program.methods
+      .disableFlags(4)
+      .accounts({
+        authority: pauser.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .signers([pauser])
+      .rpc();
+    stakePool = await program.account.stakePool.fetch(stakePoolKey);
+    assert.equal(stakePool.flags, 8);
+  });
+
+  it("Pauser cannot disable other flags", async () => {
+    try {
+      await program.methods
+        .disableFlags(8)
+        .accounts({
+          authority: pauser.publicKey,
+          stakePool: stakePoolKey,
+        })
+        .signers([pauser])
+        .rpc();
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidAuthority");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+
+  it("Fail to enable unknown flags", async () => {
+    try {
+      await program.methods
+        .enableFlags(2)
+        .accounts({
+          authority: program.provider.publicKey,
+          stakePool: stakePoolKey,
+        })
+        .rpc();
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidFlags");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+});
@@ -181,9 +181,11 @@ describe("escape hatch withdrawals", () => {
           tokenProgram: TOKEN_PROGRAM_ID,
         },
         vault: vaultKey,
+        mint: mintToBeStaked,
         stakeMint,
         from: stakeMintAccountKey,
         destination: mintToBeStakedAccountKey,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
       })
       .remainingAccounts([
         {
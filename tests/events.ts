@@ -0,0 +1,161 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import { createDepositorSplAccounts, mintToBeStaked } from "./hooks";
+import {
+  TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { initStakePool } from "@mithraic-labs/token-staking";
+import { assertBNEqual, assertKeysEqual } from "./genericTests";
+
+describe("events", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const depositor = new anchor.web3.Keypair();
+  const stakePoolNonce = 45;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [vaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("vault", "utf-8")],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const receiptNonce = 0;
+  const [stakeReceiptKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      depositor.publicKey.toBuffer(),
+      stakePoolKey.toBuffer(),
+      new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+      Buffer.from("stakeDepositReceipt", "utf-8"),
+    ],
+    program.programId
+  );
+  const getEvents = async (signature: string) => {
+    const tx = await program.provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    return Array.from(parser.parseLogs(tx.meta.logMessages));
+  };
+
+  before(async () => {
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor, stakePoolNonce),
+      initStakePool(program, mintToBeStaked, stakePoolNonce),
+    ]);
+  });
+
+  it("Deposit emits a DepositEvent", async () => {
+    const signature = await program.methods
+      .deposit(receiptNonce, new anchor.BN(1_000_000_000), new anchor.BN(0))
+      .accounts({
+        payer: depositor.publicKey,
+        owner: depositor.publicKey,
+        from: getAssociatedTokenAddressSync(
+          mintToBeStaked,
+          depositor.publicKey
+        ),
+        stakePool: stakePoolKey,
+        vault: vaultKey,
+        mint: mintToBeStaked,
+        stakeMint,
+        destination: getAssociatedTokenAddressSync(
+          stakeMint,
+          depositor.publicKey
+        ),
+        stakeDepositReceipt: stakeReceiptKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([depositor])
+      .rpc({ commitment: "confirmed" });
+    const events = await getEvents(signature);
+    assert.equal(events.length, 1);
+    assert.equal(events[0].name, "DepositEvent");
+    assertKeysEqual(events[0].data.stakePool, stakePoolKey);
+    assertKeysEqual(events[0].data.owner, depositor.publicKey);
+    assertKeysEqual(events[0].data.stakeDepositReceipt, stakeReceiptKey);
+    assertBNEqual(events[0].data.amount, 1_000_000_000);
+    assertBNEqual(events[0].data.lockupDuration, 0);
+  });
+
+  it("SetFlags emits a FlagsChanged event", async () => {
+    const signature = await program.methods
+      .setFlags(4)
+      .accounts({
+        authority: program.provider.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .rpc({ commitment: "confirmed" });
+    const events = await getEvents(signature);
+    assert.equal(events.length, 1);
+    assert.equal(events[0].name, "FlagsChanged");
+    assert.equal(events[0].data.oldFlags, 0);
+    assert.equal(events[0].data.newFlags, 4);
+  });
+
+  it("SetRole emits a RoleSet event", async () => {
+    const pauser = new anchor.web3.Keypair();
+    const signature = await program.methods
+      .setRole({ pauser: {} }, pauser.publicKey)
+      .accounts({
+        authority: program.provider.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .rpc({ commitment: "confirmed" });
+    const events = await getEvents(signature);
+    assert.equal(events.length, 1);
+    assert.equal(events[0].name, "RoleSet");
+    assertKeysEqual(events[0].data.stakePool, stakePoolKey);
+    assert.deepEqual(events[0].data.role, { pauser: {} });
+    assertKeysEqual(events[0].data.key, pauser.publicKey);
+  });
+
+  it("ProposeAuthority and CancelAuthorityTransfer emit events", async () => {
+    const newAuthority = new anchor.web3.Keypair();
+    const proposeSignature = await program.methods
+      .proposeAuthority()
+      .accounts({
+        authority: program.provider.publicKey,
+        newAuthority: newAuthority.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .rpc({ commitment: "confirmed" });
+    const proposeEvents = await getEvents(proposeSignature);
+    assert.equal(proposeEvents.length, 1);
+    assert.equal(proposeEvents[0].name, "AuthorityTransferProposed");
+    assertKeysEqual(
+      proposeEvents[0].data.pendingAuthority,
+      newAuthority.publicKey
+    );
+
+    const cancelSignature = await program.methods
+      .cancelAuthorityTransfer()
+      .accounts({
+        authority: program.provider.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .rpc({ commitment: "confirmed" });
+    const cancelEvents = await getEvents(cancelSignature);
+    assert.equal(cancelEvents.length, 1);
+    assert.equal(cancelEvents[0].name, "AuthorityTransferCancelled");
+    assertKeysEqual(
+      cancelEvents[0].data.pendingAuthority,
+      newAuthority.publicKey
+    );
+  });
+});
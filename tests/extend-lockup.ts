@@ -0,0 +1,160 @@
+import * as anchor from "@coral-xyz/anchor";
+import { splTokenProgram } from "@coral-xyz/spl-token";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import { createDepositorSplAccounts, mintToBeStaked } from "./hooks";
+import {
+  TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  SCALE_FACTOR_BASE,
+  calculateStakeWeight,
+  getDigitShift,
+  initStakePool,
+} from "@mithraic-labs/token-staking";
+import { deposit } from "./utils";
+import { assertBNEqual } from "./genericTests";
+
+describe("extend-lockup", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const tokenProgramInstance = splTokenProgram({ programId: TOKEN_PROGRAM_ID });
+  const depositor1 = new anchor.web3.Keypair();
+  const stakePoolNonce = 31;
+  const baseWeight = new anchor.BN(SCALE_FACTOR_BASE.toString());
+  const maxWeight = new anchor.BN((2n * SCALE_FACTOR_BASE).toString());
+  const minDuration = new anchor.BN(0);
+  const maxDuration = new anchor.BN(1_000_000);
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const mintToBeStakedAccountKey = getAssociatedTokenAddressSync(
+    mintToBeStaked,
+    depositor1.publicKey
+  );
+  const stakeMintAccountKey = getAssociatedTokenAddressSync(
+    stakeMint,
+    depositor1.publicKey
+  );
+  const receiptNonce = 0;
+  const [stakeReceiptKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      depositor1.publicKey.toBuffer(),
+      stakePoolKey.toBuffer(),
+      new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+      Buffer.from("stakeDepositReceipt", "utf-8"),
+    ],
+    program.programId
+  );
+
+  const extendLockup = (newLockupDuration: anchor.BN) =>
+    program.methods
+      .extendLockup(newLockupDuration)
+      .accounts({
+        claimBase: {
+          owner: depositor1.publicKey,
+          stakePool: stakePoolKey,
+          stakeDepositReceipt: stakeReceiptKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+        stakeMint,
+        destination: stakeMintAccountKey,
+      })
+      .signers([depositor1])
+      .rpc();
+
+  before(async () => {
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor1, stakePoolNonce),
+      initStakePool(
+        program,
+        mintToBeStaked,
+        stakePoolNonce,
+        maxWeight,
+        minDuration,
+        maxDuration
+      ),
+    ]);
+    // deposit 1 token locked for 10% of the max duration
+    await deposit(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      depositor1,
+      mintToBeStakedAccountKey,
+      stakeMintAccountKey,
+      new anchor.BN(1_000_000_000),
+      new anchor.BN(100_000),
+      receiptNonce
+    );
+  });
+
+  it("extend lockup and mint the additional stake", async () => {
+    const [stakePoolBefore, receiptBefore, sTokenAccountBefore] =
+      await Promise.all([
+        program.account.stakePool.fetch(stakePoolKey),
+        program.account.stakeDepositReceipt.fetch(stakeReceiptKey),
+        tokenProgramInstance.account.account.fetch(stakeMintAccountKey),
+      ]);
+
+    const newLockupDuration = new anchor.BN(500_000);
+    await extendLockup(newLockupDuration);
+
+    const [stakePoolAfter, receiptAfter, sTokenAccountAfter] =
+      await Promise.all([
+        program.account.stakePool.fetch(stakePoolKey),
+        program.account.stakeDepositReceipt.fetch(stakeReceiptKey),
+        tokenProgramInstance.account.account.fetch(stakeMintAccountKey),
+      ]);
+    const weight = calculateStakeWeight(
+      minDuration,
+      maxDuration,
+      baseWeight,
+      maxWeight,
+      newLockupDuration
+    );
+    const expectedEffectiveStake = receiptBefore.depositAmount.mul(weight);
+    const addedStake = expectedEffectiveStake.sub(receiptBefore.effectiveStake);
+    assertBNEqual(receiptAfter.lockupDuration, newLockupDuration);
+    assert.isTrue(
+      receiptAfter.depositTimestamp.gte(receiptBefore.depositTimestamp)
+    );
+    assertBNEqual(receiptAfter.effectiveStake, expectedEffectiveStake);
+    assertBNEqual(
+      stakePoolAfter.totalWeightedStake,
+      stakePoolBefore.totalWeightedStake.add(addedStake)
+    );
+    const digitShift = new anchor.BN(10).pow(
+      new anchor.BN(getDigitShift(BigInt(maxWeight.toString())))
+    );
+    const toLpAmount = (effectiveStake: anchor.BN) =>
+      effectiveStake.div(baseWeight).div(digitShift);
+    assertBNEqual(
+      sTokenAccountAfter.amount,
+      sTokenAccountBefore.amount
+        .add(toLpAmount(expectedEffectiveStake))
+        .sub(toLpAmount(receiptBefore.effectiveStake))
+    );
+  });
+
+  it("Fail to shorten lockup", async () => {
+    try {
+      await extendLockup(new anchor.BN(100_000));
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidLockupExtension");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+});
@@ -0,0 +1,294 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import { createDepositorSplAccounts, mintToBeStaked } from "./hooks";
+import {
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { initStakePool } from "@mithraic-labs/token-staking";
+import { deposit } from "./utils";
+import { assertBNEqual } from "./genericTests";
+
+describe("external-reward-pool", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const depositor = new anchor.web3.Keypair();
+  const stakePoolNonce = 53;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const rewardPoolId = 7;
+  const [rewardPoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      stakePoolKey.toBuffer(),
+      new anchor.BN(rewardPoolId).toArrayLike(Buffer, "le", 4),
+      Buffer.from("rewardPool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [rewardVaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [rewardPoolKey.toBuffer(), Buffer.from("rewardVault", "utf-8")],
+    program.programId
+  );
+  const [vaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("vault", "utf-8")],
+    program.programId
+  );
+  const mintToBeStakedAccountKey = getAssociatedTokenAddressSync(
+    mintToBeStaked,
+    depositor.publicKey
+  );
+  const stakeMintAccountKey = getAssociatedTokenAddressSync(
+    stakeMint,
+    depositor.publicKey
+  );
+  const receiptNonce = 0;
+  const [stakeReceiptKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      depositor.publicKey.toBuffer(),
+      stakePoolKey.toBuffer(),
+      new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+      Buffer.from("stakeDepositReceipt", "utf-8"),
+    ],
+    program.programId
+  );
+  const [rewardCheckpointKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      rewardPoolKey.toBuffer(),
+      stakeReceiptKey.toBuffer(),
+      Buffer.from("rewardCheckpoint", "utf-8"),
+    ],
+    program.programId
+  );
+  const fundAmount = 1_000_000;
+  const fundDuration = 2;
+
+  before(async () => {
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor, stakePoolNonce),
+      initStakePool(program, mintToBeStaked, stakePoolNonce),
+    ]);
+    await deposit(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      depositor,
+      mintToBeStakedAccountKey,
+      stakeMintAccountKey,
+      new anchor.BN(1_000_000_000),
+      new anchor.BN(0),
+      receiptNonce,
+      []
+    );
+  });
+
+  it("Add an ExternalRewardPool", async () => {
+    await program.methods
+      .addExternalRewardPool(rewardPoolId)
+      .accounts({
+        payer: program.provider.publicKey,
+        authority: program.provider.publicKey,
+        rewardMint: mintToBeStaked,
+        stakePool: stakePoolKey,
+        rewardPool: rewardPoolKey,
+        rewardVault: rewardVaultKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    const rewardPool = await program.account.externalRewardPool.fetch(
+      rewardPoolKey
+    );
+    assert.isTrue(rewardPool.stakePool.equals(stakePoolKey));
+    assert.isTrue(rewardPool.rewardVault.equals(rewardVaultKey));
+    assert.equal(rewardPool.id, rewardPoolId);
+    assertBNEqual(rewardPool.totalEffectiveStake, 0);
+  });
+
+  it("Join, fund and claim the ExternalRewardPool", async () => {
+    await program.methods
+      .joinExternalRewardPool()
+      .accounts({
+        payer: depositor.publicKey,
+        owner: depositor.publicKey,
+        rewardPool: rewardPoolKey,
+        stakeDepositReceipt: stakeReceiptKey,
+        rewardCheckpoint: rewardCheckpointKey,
+      })
+      .signers([depositor])
+      .rpc();
+    const receipt = await program.account.stakeDepositReceipt.fetch(
+      stakeReceiptKey
+    );
+    const rewardPoolJoined = await program.account.externalRewardPool.fetch(
+      rewardPoolKey
+    );
+    assertBNEqual(rewardPoolJoined.totalEffectiveStake, receipt.effectiveStake);
+
+    await program.methods
+      .fundExternalRewardPool(
+        new anchor.BN(fundAmount),
+        new anchor.BN(fundDuration)
+      )
+      .accounts({
+        funder: depositor.publicKey,
+        rewardPool: rewardPoolKey,
+        rewardVault: rewardVaultKey,
+        rewardMint: mintToBeStaked,
+        from: mintToBeStakedAccountKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([depositor])
+      .rpc();
+    const rewardPoolFunded = await program.account.externalRewardPool.fetch(
+      rewardPoolKey
+    );
+    // the funding is streamed rather than distributed immediately
+    assertBNEqual(rewardPoolFunded.undistributedAmount, fundAmount);
+    assertBNEqual(rewardPoolFunded.rewardsPerEffectiveStake, 0);
+    assertBNEqual(rewardPoolFunded.rewardsPerSecond, fundAmount / fundDuration);
+    await new Promise((resolve) => setTimeout(resolve, (fundDuration + 1) * 1_000));
+
+    const ownerAccountBefore = await getAccount(
+      program.provider.connection,
+      mintToBeStakedAccountKey
+    );
+    await program.methods
+      .claimExternalRewardPool()
+      .accounts({
+        owner: depositor.publicKey,
+        stakePool: stakePoolKey,
+        rewardPool: rewardPoolKey,
+        rewardCheckpoint: rewardCheckpointKey,
+        stakeDepositReceipt: stakeReceiptKey,
+        rewardVault: rewardVaultKey,
+        rewardMint: mintToBeStaked,
+        ownerRewardAccount: mintToBeStakedAccountKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([depositor])
+      .rpc();
+    const [ownerAccount, rewardVault, checkpoint] = await Promise.all([
+      getAccount(program.provider.connection, mintToBeStakedAccountKey),
+      getAccount(program.provider.connection, rewardVaultKey),
+      program.account.rewardCheckpoint.fetch(rewardCheckpointKey),
+    ]);
+    // the only participant receives all of the rewards
+    assert.equal(
+      ownerAccount.amount,
+      ownerAccountBefore.amount + BigInt(fundAmount)
+    );
+    assert.equal(rewardVault.amount, BigInt(0));
+    assertBNEqual(checkpoint.unclaimed, 0);
+  });
+
+  const withdraw = () =>
+    program.methods.withdraw().accounts({
+      claimBase: {
+        owner: depositor.publicKey,
+        stakePool: stakePoolKey,
+        stakeDepositReceipt: stakeReceiptKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      },
+      vault: vaultKey,
+      mint: mintToBeStaked,
+      stakeMint,
+      from: stakeMintAccountKey,
+      destination: mintToBeStakedAccountKey,
+      mintTokenProgram: TOKEN_PROGRAM_ID,
+    });
+
+  it("Fail to withdraw without the RewardCheckpoints of the receipt", async () => {
+    try {
+      await withdraw().signers([depositor]).rpc();
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidRewardCheckpoints");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+
+  it("Withdraw a receipt that has joined the ExternalRewardPool", async () => {
+    await withdraw()
+      .remainingAccounts([
+        { pubkey: rewardPoolKey, isWritable: true, isSigner: false },
+        { pubkey: rewardCheckpointKey, isWritable: true, isSigner: false },
+      ])
+      .signers([depositor])
+      .rpc();
+    const [rewardPool, checkpoint, receipt] = await Promise.all([
+      program.account.externalRewardPool.fetch(rewardPoolKey),
+      program.account.rewardCheckpoint.fetch(rewardCheckpointKey),
+      program.provider.connection.getAccountInfo(stakeReceiptKey),
+    ]);
+    assert.isNull(receipt);
+    assert.isTrue(checkpoint.receiptClosed);
+    assertBNEqual(checkpoint.effectiveStake, 0);
+    assertBNEqual(rewardPool.totalEffectiveStake, 0);
+  });
+
+  it("Leave the ExternalRewardPool", async () => {
+    await program.methods
+      .leaveExternalRewardPool()
+      .accounts({
+        owner: depositor.publicKey,
+        rewardPool: rewardPoolKey,
+        rewardCheckpoint: rewardCheckpointKey,
+        stakeDepositReceipt: stakeReceiptKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([depositor])
+      .rpc();
+    const [rewardPool, checkpoint] = await Promise.all([
+      program.account.externalRewardPool.fetch(rewardPoolKey),
+      program.provider.connection.getAccountInfo(rewardCheckpointKey),
+    ]);
+    assertBNEqual(rewardPool.totalEffectiveStake, 0);
+    assert.isNull(checkpoint);
+  });
+
+  it("Retire the ExternalRewardPool", async () => {
+    await program.methods
+      .retireExternalRewardPool()
+      .accounts({
+        authority: program.provider.publicKey,
+        stakePool: stakePoolKey,
+        rewardPool: rewardPoolKey,
+      })
+      .rpc();
+    const rewardPool = await program.account.externalRewardPool.fetch(
+      rewardPoolKey
+    );
+    assert.isTrue(rewardPool.retiredAt.gtn(0));
+
+    try {
+      await program.methods
+        .sweepExternalRewardPool()
+        .accounts({
+          authority: program.provider.publicKey,
+          stakePool: stakePoolKey,
+          rewardPool: rewardPoolKey,
+          rewardVault: rewardVaultKey,
+          rewardMint: mintToBeStaked,
+          destination: mintToBeStakedAccountKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "RewardPoolNotSweepable");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+});
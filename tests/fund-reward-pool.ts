@@ -0,0 +1,148 @@
+import * as anchor from "@coral-xyz/anchor";
+import { splTokenProgram } from "@coral-xyz/spl-token";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import {
+  createDepositorSplAccounts,
+  mintToBeStaked,
+  rewardMint1,
+} from "./hooks";
+import {
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  addRewardPool,
+  fetchRewardPoolExtensions,
+  initStakePool,
+} from "@mithraic-labs/token-staking";
+import { deposit } from "./utils";
+import { assertBNEqual } from "./genericTests";
+
+describe("fund-reward-pool", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const tokenProgramInstance = splTokenProgram({ programId: TOKEN_PROGRAM_ID });
+  const depositor1 = new anchor.web3.Keypair();
+  const stakePoolNonce = 37;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const [rewardVaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      stakePoolKey.toBuffer(),
+      rewardMint1.toBuffer(),
+      Buffer.from("rewardVault", "utf-8"),
+    ],
+    program.programId
+  );
+  const depositorReward1AccountKey = getAssociatedTokenAddressSync(
+    rewardMint1,
+    depositor1.publicKey
+  );
+  const fundRewardPool = (
+    funder: anchor.web3.Keypair | undefined,
+    from: anchor.web3.PublicKey,
+    amount: anchor.BN,
+    duration: anchor.BN
+  ) =>
+    program.methods
+      .fundRewardPool(0, amount, duration)
+      .accounts({
+        funder: funder?.publicKey ?? program.provider.publicKey,
+        stakePool: stakePoolKey,
+        rewardVault: rewardVaultKey,
+        rewardMint: rewardMint1,
+        from,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        {
+          pubkey: rewardVaultKey,
+          isWritable: true,
+          isSigner: false,
+        },
+      ])
+      .signers(funder ? [funder] : [])
+      .rpc();
+
+  before(async () => {
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor1, stakePoolNonce),
+      initStakePool(program, mintToBeStaked, stakePoolNonce),
+    ]);
+    await addRewardPool(program, stakePoolNonce, mintToBeStaked, rewardMint1);
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createAssociatedTokenAccountInstruction(
+          program.provider.publicKey,
+          depositorReward1AccountKey,
+          depositor1.publicKey,
+          rewardMint1,
+          TOKEN_PROGRAM_ID
+        )
+      )
+    );
+    await deposit(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      depositor1,
+      getAssociatedTokenAddressSync(mintToBeStaked, depositor1.publicKey),
+      getAssociatedTokenAddressSync(stakeMint, depositor1.publicKey),
+      new anchor.BN(1_000_000_000),
+      new anchor.BN(0),
+      0,
+      [rewardVaultKey]
+    );
+  });
+
+  it("fund a RewardPool and stream it over the duration", async () => {
+    const amount = new anchor.BN(1_000_000_000);
+    const duration = new anchor.BN(1_000_000);
+    await fundRewardPool(
+      undefined,
+      getAssociatedTokenAddressSync(rewardMint1, program.provider.publicKey),
+      amount,
+      duration
+    );
+    const [stakePool, rewardVault, rewardPoolExtensions] = await Promise.all([
+      program.account.stakePool.fetch(stakePoolKey),
+      tokenProgramInstance.account.account.fetch(rewardVaultKey),
+      fetchRewardPoolExtensions(program, stakePoolKey),
+    ]);
+    const extension = rewardPoolExtensions[0];
+    assertBNEqual(rewardVault.amount, amount);
+    assertBNEqual(stakePool.rewardPools[0].lastAmount, amount);
+    assertBNEqual(extension.undistributedAmount, amount);
+    assertBNEqual(extension.rewardsPerSecond, amount.div(duration));
+    assertBNEqual(extension.endTime.sub(extension.startTime), duration);
+  });
+
+  it("Fail to set a schedule when not the authority", async () => {
+    try {
+      await fundRewardPool(
+        depositor1,
+        depositorReward1AccountKey,
+        new anchor.BN(0),
+        new anchor.BN(1)
+      );
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidAuthority");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+});
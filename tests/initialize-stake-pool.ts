@@ -3,7 +3,11 @@ import { SPL_TOKEN_PROGRAM_ID, splTokenProgram } from "@coral-xyz/spl-token";
 import { SplTokenStaking } from "../target/types/spl_token_staking";
 import { assert } from "chai";
 import { TEST_MINT_DECIMALS, mintToBeStaked } from "./hooks";
-import { SCALE_FACTOR_BASE } from "@mithraic-labs/token-staking";
+import {
+  SCALE_FACTOR_BASE,
+  WeightCurve,
+  initStakePool,
+} from "@mithraic-labs/token-staking";
 import { TOKEN_PROGRAM_ID, getMint } from "@solana/spl-token";
 import {
   assertBNEqual,
@@ -41,7 +45,14 @@ describe("initialize-stake-pool", () => {
     const baseWeight = new anchor.BN(SCALE_FACTOR_BASE.toString());
     const maxWeight = new anchor.BN(4 * parseInt(SCALE_FACTOR_BASE.toString()));
     await program.methods
-      .initializeStakePool(nonce, maxWeight, minDuration, maxDuration)
+      .initializeStakePool(
+        nonce,
+        maxWeight,
+        minDuration,
+        maxDuration,
+        { linear: {} },
+        false
+      )
       .accounts({
         authority: program.provider.publicKey,
         stakePool: stakePoolKey,
@@ -49,6 +60,7 @@ describe("initialize-stake-pool", () => {
         mint: mintToBeStaked,
         vault: vaultKey,
         tokenProgram: SPL_TOKEN_PROGRAM_ID,
+        mintTokenProgram: SPL_TOKEN_PROGRAM_ID,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -115,7 +127,14 @@ describe("initialize-stake-pool", () => {
     // 10_000_000_000_000_000_000 exceeds the max shift of 1_000_000_000_000_000_000
     const maxWeight = new anchor.BN("10000000000000000000");
     await program.methods
-      .initializeStakePool(nonce, maxWeight, minDuration, maxDuration)
+      .initializeStakePool(
+        nonce,
+        maxWeight,
+        minDuration,
+        maxDuration,
+        { linear: {} },
+        false
+      )
       .accounts({
         authority: program.provider.publicKey,
         stakePool: stakePoolKey,
@@ -123,6 +142,7 @@ describe("initialize-stake-pool", () => {
         mint: mintToBeStaked,
         vault: vaultKey,
         tokenProgram: SPL_TOKEN_PROGRAM_ID,
+        mintTokenProgram: SPL_TOKEN_PROGRAM_ID,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -164,7 +184,14 @@ describe("initialize-stake-pool", () => {
     const maxDuration = new anchor.BN(31536000); // 1 year in seconds
     const maxWeight = new anchor.BN(4 * parseInt(SCALE_FACTOR_BASE.toString()));
     await program.methods
-      .initializeStakePool(nonce, maxWeight, minDuration, maxDuration)
+      .initializeStakePool(
+        nonce,
+        maxWeight,
+        minDuration,
+        maxDuration,
+        { linear: {} },
+        false
+      )
       .accounts({
         payer: program.provider.publicKey,
         authority: authority.publicKey,
@@ -173,6 +200,7 @@ describe("initialize-stake-pool", () => {
         mint: mintToBeStaked,
         vault: vaultKey,
         tokenProgram: SPL_TOKEN_PROGRAM_ID,
+        mintTokenProgram: SPL_TOKEN_PROGRAM_ID,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -187,4 +215,97 @@ describe("initialize-stake-pool", () => {
     // assert the authority is the newly created authority (not payer)
     assertKeysEqual(stakePool.authority, authority.publicKey);
   });
+  it("StakePool initialized with a step WeightCurve", async () => {
+    const nonce = 39;
+    const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 1),
+        mintToBeStaked.toBuffer(),
+        program.provider.publicKey.toBuffer(),
+        Buffer.from("stakePool", "utf-8"),
+      ],
+      program.programId
+    );
+    const day = 60 * 60 * 24;
+    const weight = (multiplier: number) =>
+      new anchor.BN(SCALE_FACTOR_BASE.toString()).muln(multiplier * 4).divn(4);
+    const tiers = [
+      { duration: new anchor.BN(30 * day), weight: weight(1.25) },
+      { duration: new anchor.BN(90 * day), weight: weight(1.5) },
+      { duration: new anchor.BN(180 * day), weight: weight(2) },
+      { duration: new anchor.BN(365 * day), weight: weight(3) },
+    ];
+    await initStakePool(
+      program,
+      mintToBeStaked,
+      nonce,
+      weight(3),
+      new anchor.BN(0),
+      new anchor.BN(365 * day),
+      undefined,
+      { step: { tiers } }
+    );
+    const stakePool = await program.account.stakePool.fetch(stakePoolKey);
+    assert.equal(stakePool.weightCurve.numPoints, tiers.length);
+    tiers.forEach((tier, index) => {
+      const point = stakePool.weightCurve.points[index];
+      assertBNEqual(point.duration, tier.duration);
+      assertBNEqual(point.weight, tier.weight);
+    });
+  });
+
+  it("StakePool initialized with an exponential WeightCurve", async () => {
+    const nonce = 41;
+    const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 1),
+        mintToBeStaked.toBuffer(),
+        program.provider.publicKey.toBuffer(),
+        Buffer.from("stakePool", "utf-8"),
+      ],
+      program.programId
+    );
+    await initStakePool(
+      program,
+      mintToBeStaked,
+      nonce,
+      new anchor.BN((2n * SCALE_FACTOR_BASE).toString()),
+      new anchor.BN(0),
+      new anchor.BN(1_000),
+      undefined,
+      { exponential: { doublings: 4 } }
+    );
+    const stakePool = await program.account.stakePool.fetch(stakePoolKey);
+    assert.equal(stakePool.weightCurve.kind, 4);
+    assert.equal(stakePool.weightCurve.doublings, 4);
+  });
+
+  it("Fail to initialize with a WeightCurve above the max weight", async () => {
+    const weightCurve: WeightCurve = {
+      piecewise: {
+        points: [
+          {
+            duration: new anchor.BN(100),
+            weight: new anchor.BN((2n * SCALE_FACTOR_BASE).toString()),
+          },
+        ],
+      },
+    };
+    try {
+      await initStakePool(
+        program,
+        mintToBeStaked,
+        40,
+        new anchor.BN(SCALE_FACTOR_BASE.toString()),
+        new anchor.BN(0),
+        new anchor.BN(1_000),
+        undefined,
+        weightCurve
+      );
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidWeightCurve");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
 });
@@ -0,0 +1,215 @@
+import * as anchor from "@coral-xyz/anchor";
+import { splTokenProgram } from "@coral-xyz/spl-token";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import {
+  createDepositorSplAccounts,
+  mintToBeStaked,
+  rewardMint1,
+} from "./hooks";
+import {
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createTransferInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { addRewardPool, initStakePool } from "@mithraic-labs/token-staking";
+import { deposit } from "./utils";
+import { assertBNEqual } from "./genericTests";
+
+describe("merge-receipts", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const tokenProgramInstance = splTokenProgram({ programId: TOKEN_PROGRAM_ID });
+  const depositor1 = new anchor.web3.Keypair();
+  const stakePoolNonce = 32;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const [rewardVaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      stakePoolKey.toBuffer(),
+      rewardMint1.toBuffer(),
+      Buffer.from("rewardVault", "utf-8"),
+    ],
+    program.programId
+  );
+  const mintToBeStakedAccountKey = getAssociatedTokenAddressSync(
+    mintToBeStaked,
+    depositor1.publicKey
+  );
+  const stakeMintAccountKey = getAssociatedTokenAddressSync(
+    stakeMint,
+    depositor1.publicKey
+  );
+  const depositorReward1AccountKey = getAssociatedTokenAddressSync(
+    rewardMint1,
+    depositor1.publicKey
+  );
+  const getStakeReceiptKey = (receiptNonce: number) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        depositor1.publicKey.toBuffer(),
+        stakePoolKey.toBuffer(),
+        new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+        Buffer.from("stakeDepositReceipt", "utf-8"),
+      ],
+      program.programId
+    )[0];
+  const destinationReceiptKey = getStakeReceiptKey(0);
+  const sourceReceiptKey = getStakeReceiptKey(1);
+
+  const mergeReceipts = (
+    destination: anchor.web3.PublicKey,
+    source: anchor.web3.PublicKey
+  ) =>
+    program.methods
+      .mergeReceipts()
+      .accounts({
+        claimBase: {
+          owner: depositor1.publicKey,
+          stakePool: stakePoolKey,
+          stakeDepositReceipt: destination,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+        sourceStakeDepositReceipt: source,
+        stakeMint,
+        destination: stakeMintAccountKey,
+      })
+      .remainingAccounts([
+        {
+          pubkey: rewardVaultKey,
+          isWritable: true,
+          isSigner: false,
+        },
+        {
+          pubkey: depositorReward1AccountKey,
+          isWritable: true,
+          isSigner: false,
+        },
+      ])
+      .signers([depositor1])
+      .rpc();
+
+  before(async () => {
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor1, stakePoolNonce),
+      initStakePool(program, mintToBeStaked, stakePoolNonce),
+    ]);
+    await addRewardPool(program, stakePoolNonce, mintToBeStaked, rewardMint1);
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createAssociatedTokenAccountInstruction(
+          program.provider.publicKey,
+          depositorReward1AccountKey,
+          depositor1.publicKey,
+          rewardMint1,
+          TOKEN_PROGRAM_ID
+        )
+      )
+    );
+    // deposit 1 unlocked token
+    await deposit(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      depositor1,
+      mintToBeStakedAccountKey,
+      stakeMintAccountKey,
+      new anchor.BN(1_000_000_000),
+      new anchor.BN(0),
+      0,
+      [rewardVaultKey]
+    );
+    // deposit 2 locked tokens
+    await deposit(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      depositor1,
+      mintToBeStakedAccountKey,
+      stakeMintAccountKey,
+      new anchor.BN(2_000_000_000),
+      new anchor.BN(1_000_000),
+      1,
+      [rewardVaultKey]
+    );
+  });
+
+  it("Fail to merge a receipt into itself", async () => {
+    try {
+      await mergeReceipts(destinationReceiptKey, destinationReceiptKey);
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "DuplicateStakeDepositReceipt");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+
+  it("merge receipts and claim rewards of both", async () => {
+    const totalReward1 = 3_000_000_000;
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createTransferInstruction(
+          getAssociatedTokenAddressSync(
+            rewardMint1,
+            program.provider.publicKey
+          ),
+          rewardVaultKey,
+          program.provider.publicKey,
+          totalReward1
+        )
+      )
+    );
+    const [stakePoolBefore, destinationBefore, sourceBefore] =
+      await Promise.all([
+        program.account.stakePool.fetch(stakePoolKey),
+        program.account.stakeDepositReceipt.fetch(destinationReceiptKey),
+        program.account.stakeDepositReceipt.fetch(sourceReceiptKey),
+      ]);
+
+    await mergeReceipts(destinationReceiptKey, sourceReceiptKey);
+
+    const [stakePoolAfter, destinationAfter, sourceAfter, rewardAccount] =
+      await Promise.all([
+        program.account.stakePool.fetch(stakePoolKey),
+        program.account.stakeDepositReceipt.fetch(destinationReceiptKey),
+        program.provider.connection.getAccountInfo(sourceReceiptKey),
+        tokenProgramInstance.account.account.fetch(depositorReward1AccountKey),
+      ]);
+    assert.isNull(sourceAfter, "Source StakeDepositReceipt not closed");
+    assertBNEqual(
+      destinationAfter.depositAmount,
+      destinationBefore.depositAmount.add(sourceBefore.depositAmount)
+    );
+    assertBNEqual(
+      destinationAfter.effectiveStake,
+      destinationBefore.effectiveStake.add(sourceBefore.effectiveStake)
+    );
+    assertBNEqual(
+      stakePoolAfter.totalWeightedStake,
+      stakePoolBefore.totalWeightedStake
+    );
+    // takes the later unlock time of the source
+    assertBNEqual(destinationAfter.lockupDuration, sourceBefore.lockupDuration);
+    assertBNEqual(
+      destinationAfter.depositTimestamp,
+      sourceBefore.depositTimestamp
+    );
+    assertBNEqual(rewardAccount.amount, totalReward1);
+    assertBNEqual(
+      destinationAfter.claimedAmounts[0],
+      stakePoolAfter.rewardPools[0].rewardsPerEffectiveStake
+    );
+  });
+});
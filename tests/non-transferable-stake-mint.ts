@@ -0,0 +1,190 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import { createDepositorSplAccounts, mintToBeStaked } from "./hooks";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createTransferCheckedInstruction,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { initStakePool } from "@mithraic-labs/token-staking";
+import { assertBNEqual } from "./genericTests";
+
+describe("non-transferable-stake-mint", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const depositor = new anchor.web3.Keypair();
+  const stakePoolNonce = 48;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [vaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("vault", "utf-8")],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const mintToBeStakedAccountKey = getAssociatedTokenAddressSync(
+    mintToBeStaked,
+    depositor.publicKey
+  );
+  const stakeMintAccountKey = getAssociatedTokenAddressSync(
+    stakeMint,
+    depositor.publicKey,
+    false,
+    TOKEN_2022_PROGRAM_ID
+  );
+  const providerStakeMintAccountKey = getAssociatedTokenAddressSync(
+    stakeMint,
+    program.provider.publicKey,
+    false,
+    TOKEN_2022_PROGRAM_ID
+  );
+  const receiptNonce = 0;
+  const [stakeReceiptKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      depositor.publicKey.toBuffer(),
+      stakePoolKey.toBuffer(),
+      new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+      Buffer.from("stakeDepositReceipt", "utf-8"),
+    ],
+    program.programId
+  );
+
+  before(async () => {
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor, stakePoolNonce),
+      initStakePool(
+        program,
+        mintToBeStaked,
+        stakePoolNonce,
+        undefined,
+        undefined,
+        undefined,
+        undefined,
+        undefined,
+        undefined,
+        true
+      ),
+    ]);
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createAssociatedTokenAccountInstruction(
+          program.provider.publicKey,
+          stakeMintAccountKey,
+          depositor.publicKey,
+          stakeMint,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createAssociatedTokenAccountInstruction(
+          program.provider.publicKey,
+          providerStakeMintAccountKey,
+          program.provider.publicKey,
+          stakeMint,
+          TOKEN_2022_PROGRAM_ID
+        )
+      )
+    );
+  });
+
+  it("Deposit mints the non-transferable stake_mint", async () => {
+    await program.methods
+      .deposit(receiptNonce, new anchor.BN(1_000_000_000), new anchor.BN(0))
+      .accounts({
+        payer: depositor.publicKey,
+        owner: depositor.publicKey,
+        from: mintToBeStakedAccountKey,
+        stakePool: stakePoolKey,
+        vault: vaultKey,
+        mint: mintToBeStaked,
+        stakeMint,
+        destination: stakeMintAccountKey,
+        stakeDepositReceipt: stakeReceiptKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([depositor])
+      .rpc();
+    const stakeMintAccount = await getAccount(
+      program.provider.connection,
+      stakeMintAccountKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    assert.isTrue(stakeMintAccount.amount > BigInt(0));
+  });
+
+  it("Fail to transfer the stake_mint", async () => {
+    const stakeMintInfo = await getMint(
+      program.provider.connection,
+      stakeMint,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    try {
+      await program.provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          createTransferCheckedInstruction(
+            stakeMintAccountKey,
+            stakeMint,
+            providerStakeMintAccountKey,
+            depositor.publicKey,
+            1,
+            stakeMintInfo.decimals,
+            [],
+            TOKEN_2022_PROGRAM_ID
+          )
+        ),
+        [depositor]
+      );
+    } catch (err) {
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+
+  it("Withdraw burns the non-transferable stake_mint", async () => {
+    await program.methods
+      .withdraw()
+      .accounts({
+        claimBase: {
+          owner: depositor.publicKey,
+          stakePool: stakePoolKey,
+          stakeDepositReceipt: stakeReceiptKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+        },
+        vault: vaultKey,
+        mint: mintToBeStaked,
+        stakeMint,
+        from: stakeMintAccountKey,
+        destination: mintToBeStakedAccountKey,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
+        treasury: null,
+      })
+      .signers([depositor])
+      .rpc();
+    const stakeMintAccount = await getAccount(
+      program.provider.connection,
+      stakeMintAccountKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    assertBNEqual(new anchor.BN(stakeMintAccount.amount.toString()), 0);
+  });
+});
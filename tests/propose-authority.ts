@@ -0,0 +1,79 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import { assert } from "chai";
+import { mintToBeStaked } from "./hooks";
+import { initStakePool } from "@mithraic-labs/token-staking";
+import { assertKeyDefault, assertKeysEqual } from "./genericTests";
+
+describe("propose-authority", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const stakePoolNonce = 42;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const newAuthority = new anchor.web3.Keypair();
+  const proposeAuthority = () =>
+    program.methods
+      .proposeAuthority()
+      .accounts({
+        authority: program.provider.publicKey,
+        newAuthority: newAuthority.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .rpc();
+  const acceptAuthority = (pendingAuthority: anchor.web3.Keypair) =>
+    program.methods
+      .acceptAuthority()
+      .accounts({
+        pendingAuthority: pendingAuthority.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .signers([pendingAuthority])
+      .rpc();
+
+  before(async () => {
+    await initStakePool(program, mintToBeStaked, stakePoolNonce);
+  });
+
+  it("Propose and cancel an authority transfer", async () => {
+    await proposeAuthority();
+    let stakePool = await program.account.stakePool.fetch(stakePoolKey);
+    assertKeysEqual(stakePool.pendingAuthority, newAuthority.publicKey);
+    assertKeysEqual(stakePool.authority, program.provider.publicKey);
+
+    await program.methods
+      .cancelAuthorityTransfer()
+      .accounts({
+        authority: program.provider.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .rpc();
+    stakePool = await program.account.stakePool.fetch(stakePoolKey);
+    assertKeyDefault(stakePool.pendingAuthority);
+  });
+
+  it("Fail to accept when not the pending authority", async () => {
+    await proposeAuthority();
+    try {
+      await acceptAuthority(new anchor.web3.Keypair());
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidPendingAuthority");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+
+  it("Accept the authority transfer", async () => {
+    await acceptAuthority(newAuthority);
+    const stakePool = await program.account.stakePool.fetch(stakePoolKey);
+    assertKeysEqual(stakePool.authority, newAuthority.publicKey);
+    assertKeyDefault(stakePool.pendingAuthority);
+  });
+});
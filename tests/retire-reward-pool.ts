@@ -0,0 +1,195 @@
+import * as anchor from "@coral-xyz/anchor";
+import { splTokenProgram } from "@coral-xyz/spl-token";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import {
+  createDepositorSplAccounts,
+  mintToBeStaked,
+  rewardMint1,
+} from "./hooks";
+import {
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createTransferInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { addRewardPool, initStakePool } from "@mithraic-labs/token-staking";
+import { deposit } from "./utils";
+import { assertBNEqual } from "./genericTests";
+
+describe("retire-reward-pool", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const tokenProgramInstance = splTokenProgram({ programId: TOKEN_PROGRAM_ID });
+  const depositor1 = new anchor.web3.Keypair();
+  const stakePoolNonce = 35;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const [rewardVaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      stakePoolKey.toBuffer(),
+      rewardMint1.toBuffer(),
+      Buffer.from("rewardVault", "utf-8"),
+    ],
+    program.programId
+  );
+  const depositorReward1AccountKey = getAssociatedTokenAddressSync(
+    rewardMint1,
+    depositor1.publicKey
+  );
+  const authorityReward1AccountKey = getAssociatedTokenAddressSync(
+    rewardMint1,
+    program.provider.publicKey
+  );
+  const receiptNonce = 0;
+  const [stakeReceiptKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      depositor1.publicKey.toBuffer(),
+      stakePoolKey.toBuffer(),
+      new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+      Buffer.from("stakeDepositReceipt", "utf-8"),
+    ],
+    program.programId
+  );
+  const transferRewards = (amount: number) =>
+    program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createTransferInstruction(
+          authorityReward1AccountKey,
+          rewardVaultKey,
+          program.provider.publicKey,
+          amount
+        )
+      )
+    );
+  const retireRewardPool = () =>
+    program.methods
+      .retireRewardPool(0)
+      .accounts({
+        authority: program.provider.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .remainingAccounts([
+        {
+          pubkey: rewardVaultKey,
+          isWritable: false,
+          isSigner: false,
+        },
+      ])
+      .rpc();
+
+  before(async () => {
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor1, stakePoolNonce),
+      initStakePool(program, mintToBeStaked, stakePoolNonce),
+    ]);
+    await addRewardPool(program, stakePoolNonce, mintToBeStaked, rewardMint1);
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createAssociatedTokenAccountInstruction(
+          program.provider.publicKey,
+          depositorReward1AccountKey,
+          depositor1.publicKey,
+          rewardMint1,
+          TOKEN_PROGRAM_ID
+        )
+      )
+    );
+    await deposit(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      depositor1,
+      getAssociatedTokenAddressSync(mintToBeStaked, depositor1.publicKey),
+      getAssociatedTokenAddressSync(stakeMint, depositor1.publicKey),
+      new anchor.BN(1_000_000_000),
+      new anchor.BN(0),
+      receiptNonce,
+      [rewardVaultKey]
+    );
+  });
+
+  it("retire a RewardPool and stop accrual", async () => {
+    const accruedReward = 1_000_000_000;
+    await transferRewards(accruedReward);
+    await retireRewardPool();
+    const stakePool = await program.account.stakePool.fetch(stakePoolKey);
+    assert.isTrue(stakePool.rewardPools[0].retiredAt.gtn(0));
+    assertBNEqual(stakePool.rewardPools[0].lastAmount, accruedReward);
+
+    // rewards sent after retirement are not claimable
+    await transferRewards(500_000_000);
+    await program.methods
+      .claimAll()
+      .accounts({
+        claimBase: {
+          owner: depositor1.publicKey,
+          stakePool: stakePoolKey,
+          stakeDepositReceipt: stakeReceiptKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+      })
+      .remainingAccounts([
+        {
+          pubkey: rewardVaultKey,
+          isWritable: true,
+          isSigner: false,
+        },
+        {
+          pubkey: depositorReward1AccountKey,
+          isWritable: true,
+          isSigner: false,
+        },
+      ])
+      .signers([depositor1])
+      .rpc();
+    const [depositorReward1Account, rewardVault] = await Promise.all([
+      tokenProgramInstance.account.account.fetch(depositorReward1AccountKey),
+      tokenProgramInstance.account.account.fetch(rewardVaultKey),
+    ]);
+    assertBNEqual(depositorReward1Account.amount, accruedReward);
+    assertBNEqual(rewardVault.amount, 500_000_000);
+  });
+
+  it("Fail to retire a retired RewardPool", async () => {
+    try {
+      await retireRewardPool();
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "RewardPoolRetired");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+
+  it("Fail to sweep during the claim window", async () => {
+    try {
+      await program.methods
+        .sweepRewardPool(0)
+        .accounts({
+          authority: program.provider.publicKey,
+          stakePool: stakePoolKey,
+          rewardVault: rewardVaultKey,
+          rewardMint: rewardMint1,
+          destination: authorityReward1AccountKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "RewardPoolNotSweepable");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+});
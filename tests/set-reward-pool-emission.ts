@@ -0,0 +1,193 @@
+import * as anchor from "@coral-xyz/anchor";
+import { splTokenProgram } from "@coral-xyz/spl-token";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import {
+  createDepositorSplAccounts,
+  mintToBeStaked,
+  rewardMint1,
+} from "./hooks";
+import {
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createTransferInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  addRewardPool,
+  fetchRewardPoolExtensions,
+  initStakePool,
+} from "@mithraic-labs/token-staking";
+import { deposit } from "./utils";
+import { assertBNEqual } from "./genericTests";
+
+describe("set-reward-pool-emission", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const tokenProgramInstance = splTokenProgram({ programId: TOKEN_PROGRAM_ID });
+  const depositor1 = new anchor.web3.Keypair();
+  const stakePoolNonce = 36;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const [rewardVaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      stakePoolKey.toBuffer(),
+      rewardMint1.toBuffer(),
+      Buffer.from("rewardVault", "utf-8"),
+    ],
+    program.programId
+  );
+  const depositorReward1AccountKey = getAssociatedTokenAddressSync(
+    rewardMint1,
+    depositor1.publicKey
+  );
+  const receiptNonce = 0;
+  const [stakeReceiptKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      depositor1.publicKey.toBuffer(),
+      stakePoolKey.toBuffer(),
+      new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+      Buffer.from("stakeDepositReceipt", "utf-8"),
+    ],
+    program.programId
+  );
+  const setRewardPoolEmission = (
+    rewardsPerSecond: anchor.BN,
+    startTime: anchor.BN,
+    endTime: anchor.BN
+  ) =>
+    program.methods
+      .setRewardPoolEmission(0, rewardsPerSecond, startTime, endTime)
+      .accounts({
+        authority: program.provider.publicKey,
+        payer: program.provider.publicKey,
+        stakePool: stakePoolKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        {
+          pubkey: rewardVaultKey,
+          isWritable: false,
+          isSigner: false,
+        },
+      ])
+      .rpc();
+
+  before(async () => {
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor1, stakePoolNonce),
+      initStakePool(program, mintToBeStaked, stakePoolNonce),
+    ]);
+    await addRewardPool(program, stakePoolNonce, mintToBeStaked, rewardMint1);
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createAssociatedTokenAccountInstruction(
+          program.provider.publicKey,
+          depositorReward1AccountKey,
+          depositor1.publicKey,
+          rewardMint1,
+          TOKEN_PROGRAM_ID
+        )
+      )
+    );
+    await deposit(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      depositor1,
+      getAssociatedTokenAddressSync(mintToBeStaked, depositor1.publicKey),
+      getAssociatedTokenAddressSync(stakeMint, depositor1.publicKey),
+      new anchor.BN(1_000_000_000),
+      new anchor.BN(0),
+      receiptNonce,
+      [rewardVaultKey]
+    );
+  });
+
+  it("Fail to set an emission that already ended", async () => {
+    try {
+      await setRewardPoolEmission(
+        new anchor.BN(1),
+        new anchor.BN(0),
+        new anchor.BN(1)
+      );
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidEmissionSchedule");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+
+  it("hold back rewards until the emission starts", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const startTime = new anchor.BN(now + 1_000_000);
+    const endTime = new anchor.BN(now + 2_000_000);
+    const rewardsPerSecond = new anchor.BN(1_000);
+    await setRewardPoolEmission(rewardsPerSecond, startTime, endTime);
+
+    const totalReward1 = 1_000_000_000;
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createTransferInstruction(
+          getAssociatedTokenAddressSync(
+            rewardMint1,
+            program.provider.publicKey
+          ),
+          rewardVaultKey,
+          program.provider.publicKey,
+          totalReward1
+        )
+      )
+    );
+    await program.methods
+      .claimAll()
+      .accounts({
+        claimBase: {
+          owner: depositor1.publicKey,
+          stakePool: stakePoolKey,
+          stakeDepositReceipt: stakeReceiptKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+      })
+      .remainingAccounts([
+        {
+          pubkey: rewardVaultKey,
+          isWritable: true,
+          isSigner: false,
+        },
+        {
+          pubkey: depositorReward1AccountKey,
+          isWritable: true,
+          isSigner: false,
+        },
+      ])
+      .signers([depositor1])
+      .rpc();
+
+    const [stakePool, depositorReward1Account, rewardPoolExtensions] =
+      await Promise.all([
+        program.account.stakePool.fetch(stakePoolKey),
+        tokenProgramInstance.account.account.fetch(depositorReward1AccountKey),
+        fetchRewardPoolExtensions(program, stakePoolKey),
+      ]);
+    const extension = rewardPoolExtensions[0];
+    assertBNEqual(extension.rewardsPerSecond, rewardsPerSecond);
+    assertBNEqual(extension.startTime, startTime);
+    assertBNEqual(extension.endTime, endTime);
+    assertBNEqual(extension.undistributedAmount, totalReward1);
+    assertBNEqual(stakePool.rewardPools[0].lastAmount, totalReward1);
+    assertBNEqual(stakePool.rewardPools[0].rewardsPerEffectiveStake, 0);
+    assertBNEqual(depositorReward1Account.amount, 0);
+  });
+});
@@ -0,0 +1,95 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SPL_TOKEN_PROGRAM_ID } from "@coral-xyz/spl-token";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import { assert } from "chai";
+import { mintToBeStaked, rewardMint1 } from "./hooks";
+import { initStakePool } from "@mithraic-labs/token-staking";
+import { assertKeysEqual } from "./genericTests";
+
+describe("set-role", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const stakePoolNonce = 43;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const pauser = new anchor.web3.Keypair();
+  const rewardManager = new anchor.web3.Keypair();
+  const setFlags = (flags: number) =>
+    program.methods
+      .setFlags(flags)
+      .accounts({
+        authority: pauser.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .signers([pauser])
+      .rpc();
+
+  before(async () => {
+    await initStakePool(program, mintToBeStaked, stakePoolNonce);
+    await program.methods
+      .setRole({ pauser: {} }, pauser.publicKey)
+      .accounts({
+        authority: program.provider.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .rpc();
+    await program.methods
+      .setRole({ rewardManager: {} }, rewardManager.publicKey)
+      .accounts({
+        authority: program.provider.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .rpc();
+  });
+
+  it("Pauser disables deposits", async () => {
+    await setFlags(4);
+    const stakePool = await program.account.stakePool.fetch(stakePoolKey);
+    assertKeysEqual(stakePool.pauser, pauser.publicKey);
+    assert.equal(stakePool.flags, 4);
+  });
+
+  it("Pauser cannot change other flags", async () => {
+    try {
+      await setFlags(4 + 8);
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidAuthority");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+
+  it("Reward manager adds a RewardPool", async () => {
+    const [rewardVaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        stakePoolKey.toBuffer(),
+        rewardMint1.toBuffer(),
+        Buffer.from("rewardVault", "utf-8"),
+      ],
+      program.programId
+    );
+    await program.methods
+      .addRewardPool(0)
+      .accounts({
+        payer: program.provider.publicKey,
+        authority: rewardManager.publicKey,
+        rewardMint: rewardMint1,
+        stakePool: stakePoolKey,
+        rewardVault: rewardVaultKey,
+        tokenProgram: SPL_TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([rewardManager])
+      .rpc();
+    const stakePool = await program.account.stakePool.fetch(stakePoolKey);
+    assertKeysEqual(stakePool.rewardPools[0].rewardVault, rewardVaultKey);
+  });
+});
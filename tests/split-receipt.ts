@@ -0,0 +1,139 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import { createDepositorSplAccounts, mintToBeStaked } from "./hooks";
+import {
+  TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { initStakePool } from "@mithraic-labs/token-staking";
+import { deposit } from "./utils";
+import { assertBNEqual } from "./genericTests";
+
+describe("split-receipt", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const depositor1 = new anchor.web3.Keypair();
+  const stakePoolNonce = 33;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const mintToBeStakedAccountKey = getAssociatedTokenAddressSync(
+    mintToBeStaked,
+    depositor1.publicKey
+  );
+  const stakeMintAccountKey = getAssociatedTokenAddressSync(
+    stakeMint,
+    depositor1.publicKey
+  );
+  const getStakeReceiptKey = (receiptNonce: number) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        depositor1.publicKey.toBuffer(),
+        stakePoolKey.toBuffer(),
+        new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+        Buffer.from("stakeDepositReceipt", "utf-8"),
+      ],
+      program.programId
+    )[0];
+  const stakeReceiptKey = getStakeReceiptKey(0);
+
+  const splitReceipt = (amount: anchor.BN, newNonce: number) =>
+    program.methods
+      .splitReceipt(amount, newNonce)
+      .accounts({
+        claimBase: {
+          owner: depositor1.publicKey,
+          stakePool: stakePoolKey,
+          stakeDepositReceipt: stakeReceiptKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+        payer: depositor1.publicKey,
+        newStakeDepositReceipt: getStakeReceiptKey(newNonce),
+        stakeMint,
+        from: stakeMintAccountKey,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([depositor1])
+      .rpc();
+
+  before(async () => {
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor1, stakePoolNonce),
+      initStakePool(program, mintToBeStaked, stakePoolNonce),
+    ]);
+    // deposit 1 locked token
+    await deposit(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      depositor1,
+      mintToBeStakedAccountKey,
+      stakeMintAccountKey,
+      new anchor.BN(1_000_000_000),
+      new anchor.BN(1_000_000),
+      0
+    );
+  });
+
+  it("split a locked receipt", async () => {
+    const [stakePoolBefore, receiptBefore] = await Promise.all([
+      program.account.stakePool.fetch(stakePoolKey),
+      program.account.stakeDepositReceipt.fetch(stakeReceiptKey),
+    ]);
+
+    await splitReceipt(new anchor.BN(400_000_000), 1);
+
+    const [stakePoolAfter, receiptAfter, newReceipt] = await Promise.all([
+      program.account.stakePool.fetch(stakePoolKey),
+      program.account.stakeDepositReceipt.fetch(stakeReceiptKey),
+      program.account.stakeDepositReceipt.fetch(getStakeReceiptKey(1)),
+    ]);
+    assertBNEqual(receiptAfter.depositAmount, 600_000_000);
+    assertBNEqual(newReceipt.depositAmount, 400_000_000);
+    assertBNEqual(
+      receiptAfter.effectiveStake.add(newReceipt.effectiveStake),
+      receiptBefore.effectiveStake
+    );
+    assertBNEqual(
+      newReceipt.effectiveStake,
+      receiptBefore.effectiveStake
+        .mul(new anchor.BN(400_000_000))
+        .div(receiptBefore.depositAmount)
+    );
+    assertBNEqual(newReceipt.lockupDuration, receiptBefore.lockupDuration);
+    assertBNEqual(newReceipt.depositTimestamp, receiptBefore.depositTimestamp);
+    assert.equal(
+      newReceipt.owner.toString(),
+      depositor1.publicKey.toString()
+    );
+    assertBNEqual(
+      stakePoolAfter.totalWeightedStake,
+      stakePoolBefore.totalWeightedStake
+    );
+  });
+
+  it("Fail to split the entire deposit", async () => {
+    const receipt = await program.account.stakeDepositReceipt.fetch(
+      stakeReceiptKey
+    );
+    try {
+      await splitReceipt(receipt.depositAmount, 2);
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidSplitAmount");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+});
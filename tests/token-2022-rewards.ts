@@ -0,0 +1,201 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import { createDepositorSplAccounts, mintToBeStaked } from "./hooks";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createMintToInstruction,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMintLen,
+} from "@solana/spl-token";
+import { addRewardPool, initStakePool } from "@mithraic-labs/token-staking";
+import { deposit } from "./utils";
+import { assertBNEqual } from "./genericTests";
+
+describe("token-2022-rewards", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const depositor = new anchor.web3.Keypair();
+  const rewardMintKeypair = new anchor.web3.Keypair();
+  const rewardMint = rewardMintKeypair.publicKey;
+  const transferFeeBps = 100;
+  const stakePoolNonce = 47;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const [rewardVaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      stakePoolKey.toBuffer(),
+      rewardMint.toBuffer(),
+      Buffer.from("rewardVault", "utf-8"),
+    ],
+    program.programId
+  );
+  const depositorRewardAccountKey = getAssociatedTokenAddressSync(
+    rewardMint,
+    depositor.publicKey,
+    false,
+    TOKEN_2022_PROGRAM_ID
+  );
+  const receiptNonce = 0;
+  const [stakeReceiptKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      depositor.publicKey.toBuffer(),
+      stakePoolKey.toBuffer(),
+      new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+      Buffer.from("stakeDepositReceipt", "utf-8"),
+    ],
+    program.programId
+  );
+  const getAmount = async (tokenAccount: anchor.web3.PublicKey) =>
+    new anchor.BN(
+      (
+        await getAccount(
+          program.provider.connection,
+          tokenAccount,
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        )
+      ).amount.toString()
+    );
+
+  before(async () => {
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.createAccount({
+          fromPubkey: program.provider.publicKey,
+          newAccountPubkey: rewardMint,
+          space: mintLen,
+          lamports:
+            await program.provider.connection.getMinimumBalanceForRentExemption(
+              mintLen
+            ),
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          rewardMint,
+          program.provider.publicKey,
+          program.provider.publicKey,
+          transferFeeBps,
+          BigInt(1_000_000_000),
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(
+          rewardMint,
+          9,
+          program.provider.publicKey,
+          null,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createAssociatedTokenAccountInstruction(
+          program.provider.publicKey,
+          depositorRewardAccountKey,
+          depositor.publicKey,
+          rewardMint,
+          TOKEN_2022_PROGRAM_ID
+        )
+      ),
+      [rewardMintKeypair]
+    );
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor, stakePoolNonce),
+      initStakePool(program, mintToBeStaked, stakePoolNonce),
+    ]);
+    await addRewardPool(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      rewardMint,
+      0,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await deposit(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      depositor,
+      getAssociatedTokenAddressSync(mintToBeStaked, depositor.publicKey),
+      getAssociatedTokenAddressSync(stakeMint, depositor.publicKey),
+      new anchor.BN(1_000_000_000),
+      new anchor.BN(0),
+      receiptNonce,
+      [rewardVaultKey]
+    );
+  });
+
+  it("Claim Token-2022 rewards with transfer_checked", async () => {
+    const rewardAmount = 1_000_000_000;
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createMintToInstruction(
+          rewardMint,
+          rewardVaultKey,
+          program.provider.publicKey,
+          rewardAmount,
+          [],
+          TOKEN_2022_PROGRAM_ID
+        )
+      )
+    );
+    await program.methods
+      .claimAll()
+      .accounts({
+        claimBase: {
+          owner: depositor.publicKey,
+          stakePool: stakePoolKey,
+          stakeDepositReceipt: stakeReceiptKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+        },
+      })
+      .remainingAccounts([
+        {
+          pubkey: rewardVaultKey,
+          isWritable: true,
+          isSigner: false,
+        },
+        {
+          pubkey: depositorRewardAccountKey,
+          isWritable: true,
+          isSigner: false,
+        },
+        {
+          pubkey: rewardMint,
+          isWritable: false,
+          isSigner: false,
+        },
+      ])
+      .signers([depositor])
+      .rpc();
+    const [stakePool, rewardVaultAmount, depositorRewardAmount] =
+      await Promise.all([
+        program.account.stakePool.fetch(stakePoolKey),
+        getAmount(rewardVaultKey),
+        getAmount(depositorRewardAccountKey),
+      ]);
+    // the vault sends the full claim, the transfer fee is withheld from the owner's account
+    assertBNEqual(rewardVaultAmount, 0);
+    assertBNEqual(stakePool.rewardPools[0].lastAmount, 0);
+    assertBNEqual(
+      depositorRewardAmount,
+      rewardAmount - (rewardAmount * transferFeeBps) / 10_000
+    );
+  });
+});
@@ -0,0 +1,209 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createMintToInstruction,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMintLen,
+} from "@solana/spl-token";
+import { initStakePool } from "@mithraic-labs/token-staking";
+import { assertBNEqual } from "./genericTests";
+
+describe("token-2022", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const depositor = new anchor.web3.Keypair();
+  const mintKeypair = new anchor.web3.Keypair();
+  const mint = mintKeypair.publicKey;
+  const transferFeeBps = 100;
+  const stakePoolNonce = 46;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mint.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [vaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("vault", "utf-8")],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const depositorMintAccountKey = getAssociatedTokenAddressSync(
+    mint,
+    depositor.publicKey,
+    false,
+    TOKEN_2022_PROGRAM_ID
+  );
+  const stakeMintAccountKey = getAssociatedTokenAddressSync(
+    stakeMint,
+    depositor.publicKey
+  );
+  const receiptNonce = 0;
+  const [stakeReceiptKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      depositor.publicKey.toBuffer(),
+      stakePoolKey.toBuffer(),
+      new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+      Buffer.from("stakeDepositReceipt", "utf-8"),
+    ],
+    program.programId
+  );
+
+  before(async () => {
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.createAccount({
+          fromPubkey: program.provider.publicKey,
+          newAccountPubkey: mint,
+          space: mintLen,
+          lamports:
+            await program.provider.connection.getMinimumBalanceForRentExemption(
+              mintLen
+            ),
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          mint,
+          program.provider.publicKey,
+          program.provider.publicKey,
+          transferFeeBps,
+          BigInt(1_000_000_000),
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(
+          mint,
+          9,
+          program.provider.publicKey,
+          null,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createAssociatedTokenAccountInstruction(
+          program.provider.publicKey,
+          depositorMintAccountKey,
+          depositor.publicKey,
+          mint,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createMintToInstruction(
+          mint,
+          depositorMintAccountKey,
+          program.provider.publicKey,
+          10_000_000_000,
+          [],
+          TOKEN_2022_PROGRAM_ID
+        )
+      ),
+      [mintKeypair]
+    );
+    await initStakePool(
+      program,
+      mint,
+      stakePoolNonce,
+      undefined,
+      undefined,
+      undefined,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createAssociatedTokenAccountInstruction(
+          program.provider.publicKey,
+          stakeMintAccountKey,
+          depositor.publicKey,
+          stakeMint
+        )
+      )
+    );
+  });
+
+  it("Deposit credits the amount received after the transfer fee", async () => {
+    const amount = 1_000_000_000;
+    await program.methods
+      .deposit(receiptNonce, new anchor.BN(amount), new anchor.BN(0))
+      .accounts({
+        payer: depositor.publicKey,
+        owner: depositor.publicKey,
+        from: depositorMintAccountKey,
+        stakePool: stakePoolKey,
+        vault: vaultKey,
+        mint,
+        stakeMint,
+        destination: stakeMintAccountKey,
+        stakeDepositReceipt: stakeReceiptKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        mintTokenProgram: TOKEN_2022_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([depositor])
+      .rpc();
+    const received = amount - (amount * transferFeeBps) / 10_000;
+    const [receipt, vault] = await Promise.all([
+      program.account.stakeDepositReceipt.fetch(stakeReceiptKey),
+      getAccount(
+        program.provider.connection,
+        vaultKey,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      ),
+    ]);
+    assertBNEqual(receipt.depositAmount, received);
+    assertBNEqual(new anchor.BN(vault.amount.toString()), received);
+  });
+
+  it("Withdraw returns the deposit with transfer_checked", async () => {
+    const before = await getAccount(
+      program.provider.connection,
+      depositorMintAccountKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await program.methods
+      .withdraw()
+      .accounts({
+        claimBase: {
+          owner: depositor.publicKey,
+          stakePool: stakePoolKey,
+          stakeDepositReceipt: stakeReceiptKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+        vault: vaultKey,
+        mint,
+        stakeMint,
+        from: stakeMintAccountKey,
+        destination: depositorMintAccountKey,
+        mintTokenProgram: TOKEN_2022_PROGRAM_ID,
+        treasury: null,
+      })
+      .signers([depositor])
+      .rpc();
+    const after = await getAccount(
+      program.provider.connection,
+      depositorMintAccountKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    // the vault sends the full deposit, the transfer fee is withheld from the destination
+    const deposited = 990_000_000;
+    const expected = deposited - (deposited * transferFeeBps) / 10_000;
+    assertBNEqual(
+      new anchor.BN((after.amount - before.amount).toString()),
+      expected
+    );
+  });
+});
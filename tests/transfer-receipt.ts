@@ -0,0 +1,122 @@
+import * as anchor from "@coral-xyz/anchor";
+import { splTokenProgram } from "@coral-xyz/spl-token";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import { createDepositorSplAccounts, mintToBeStaked } from "./hooks";
+import {
+  TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { initStakePool } from "@mithraic-labs/token-staking";
+import { deposit } from "./utils";
+import { assertBNEqual, assertKeysEqual } from "./genericTests";
+
+describe("transfer-receipt", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const tokenProgramInstance = splTokenProgram({ programId: TOKEN_PROGRAM_ID });
+  const depositor1 = new anchor.web3.Keypair();
+  const depositor2 = new anchor.web3.Keypair();
+  const stakePoolNonce = 34;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const getStakeReceiptKey = (
+    owner: anchor.web3.PublicKey,
+    receiptNonce: number
+  ) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        owner.toBuffer(),
+        stakePoolKey.toBuffer(),
+        new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+        Buffer.from("stakeDepositReceipt", "utf-8"),
+      ],
+      program.programId
+    )[0];
+  const stakeMintAccountKey1 = getAssociatedTokenAddressSync(
+    stakeMint,
+    depositor1.publicKey
+  );
+  const stakeMintAccountKey2 = getAssociatedTokenAddressSync(
+    stakeMint,
+    depositor2.publicKey
+  );
+
+  before(async () => {
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor1, stakePoolNonce),
+      createDepositorSplAccounts(program, depositor2, stakePoolNonce),
+      initStakePool(program, mintToBeStaked, stakePoolNonce),
+    ]);
+    // deposit 1 locked token
+    await deposit(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      depositor1,
+      getAssociatedTokenAddressSync(mintToBeStaked, depositor1.publicKey),
+      stakeMintAccountKey1,
+      new anchor.BN(1_000_000_000),
+      new anchor.BN(1_000_000),
+      0
+    );
+  });
+
+  it("transfer a locked receipt to a new owner", async () => {
+    const stakeReceiptKey = getStakeReceiptKey(depositor1.publicKey, 0);
+    const newStakeReceiptKey = getStakeReceiptKey(depositor2.publicKey, 5);
+    const [receiptBefore, sTokenAccount1Before] = await Promise.all([
+      program.account.stakeDepositReceipt.fetch(stakeReceiptKey),
+      tokenProgramInstance.account.account.fetch(stakeMintAccountKey1),
+    ]);
+
+    await program.methods
+      .transferReceipt(5)
+      .accounts({
+        claimBase: {
+          owner: depositor1.publicKey,
+          stakePool: stakePoolKey,
+          stakeDepositReceipt: stakeReceiptKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+        payer: depositor1.publicKey,
+        newOwner: depositor2.publicKey,
+        newStakeDepositReceipt: newStakeReceiptKey,
+        stakeMint,
+        from: stakeMintAccountKey1,
+        destination: stakeMintAccountKey2,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([depositor1])
+      .rpc();
+
+    const [oldReceipt, newReceipt, sTokenAccount1After, sTokenAccount2After] =
+      await Promise.all([
+        program.provider.connection.getAccountInfo(stakeReceiptKey),
+        program.account.stakeDepositReceipt.fetch(newStakeReceiptKey),
+        tokenProgramInstance.account.account.fetch(stakeMintAccountKey1),
+        tokenProgramInstance.account.account.fetch(stakeMintAccountKey2),
+      ]);
+    assert.isNull(oldReceipt, "StakeDepositReceipt account not closed");
+    assertKeysEqual(newReceipt.owner, depositor2.publicKey);
+    assertKeysEqual(newReceipt.stakePool, stakePoolKey);
+    assertBNEqual(newReceipt.depositAmount, receiptBefore.depositAmount);
+    assertBNEqual(newReceipt.effectiveStake, receiptBefore.effectiveStake);
+    assertBNEqual(newReceipt.lockupDuration, receiptBefore.lockupDuration);
+    assertBNEqual(newReceipt.depositTimestamp, receiptBefore.depositTimestamp);
+    assertBNEqual(sTokenAccount1After.amount, 0);
+    assertBNEqual(sTokenAccount2After.amount, sTokenAccount1Before.amount);
+  });
+});
@@ -0,0 +1,96 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import { mintToBeStaked } from "./hooks";
+import { assert } from "chai";
+import { SCALE_FACTOR_BASE, initStakePool } from "@mithraic-labs/token-staking";
+import { assertBNEqual } from "./genericTests";
+
+describe("update-pool-config", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const stakePoolNonce = 41;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const updatePoolConfig = (
+    baseWeight: anchor.BN,
+    maxWeight: anchor.BN,
+    minDuration: anchor.BN,
+    maxDuration: anchor.BN
+  ) =>
+    program.methods
+      .updatePoolConfig(baseWeight, maxWeight, minDuration, maxDuration)
+      .accounts({
+        authority: program.provider.publicKey,
+        stakePool: stakePoolKey,
+      })
+      .rpc();
+
+  before(async () => {
+    await initStakePool(program, mintToBeStaked, stakePoolNonce);
+  });
+
+  it("update the lockup and weight configuration", async () => {
+    const baseWeight = new anchor.BN((SCALE_FACTOR_BASE / 2n).toString());
+    const maxWeight = new anchor.BN(SCALE_FACTOR_BASE.toString());
+    const minDuration = new anchor.BN(10);
+    const maxDuration = new anchor.BN(1_000);
+    await updatePoolConfig(baseWeight, maxWeight, minDuration, maxDuration);
+    const stakePool = await program.account.stakePool.fetch(stakePoolKey);
+    assertBNEqual(stakePool.baseWeight, baseWeight);
+    assertBNEqual(stakePool.maxWeight, maxWeight);
+    assertBNEqual(stakePool.minDuration, minDuration);
+    assertBNEqual(stakePool.maxDuration, maxDuration);
+  });
+
+  it("Fail to set a min duration above the max", async () => {
+    try {
+      await updatePoolConfig(
+        new anchor.BN(SCALE_FACTOR_BASE.toString()),
+        new anchor.BN(SCALE_FACTOR_BASE.toString()),
+        new anchor.BN(1_001),
+        new anchor.BN(1_000)
+      );
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidStakePoolDuration");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+
+  it("Fail to change the stake mint digit shift", async () => {
+    try {
+      await updatePoolConfig(
+        new anchor.BN(SCALE_FACTOR_BASE.toString()),
+        new anchor.BN((2n * SCALE_FACTOR_BASE).toString()),
+        new anchor.BN(0),
+        new anchor.BN(1_000)
+      );
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidMaxWeightDigitShift");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+
+  it("Fail to set a max weight below the SCALE_FACTOR_BASE", async () => {
+    try {
+      await updatePoolConfig(
+        new anchor.BN((SCALE_FACTOR_BASE / 4n).toString()),
+        new anchor.BN((SCALE_FACTOR_BASE / 2n).toString()),
+        new anchor.BN(0),
+        new anchor.BN(1_000)
+      );
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidStakePoolWeight");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+});
@@ -61,10 +61,12 @@ try{
       from: vaultMintAccount,
       stakePool: stakePoolKey,
       vault: vaultKey,
+      mint: stakePoolMint,
       stakeMint,
       destination: stakeMintAccount,
       stakeDepositReceipt: stakeReceiptKey,
       tokenProgram: SPL_TOKEN_PROGRAM_ID,
+      mintTokenProgram: SPL_TOKEN_PROGRAM_ID,
       rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       systemProgram: anchor.web3.SystemProgram.programId,
     })
@@ -0,0 +1,233 @@
+import * as anchor from "@coral-xyz/anchor";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import { createDepositorSplAccounts, mintToBeStaked } from "./hooks";
+import {
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { initStakePool } from "@mithraic-labs/token-staking";
+import { deposit } from "./utils";
+import { assertBNEqual } from "./genericTests";
+
+describe("voter-weight-record", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const depositor = new anchor.web3.Keypair();
+  const realm = new anchor.web3.Keypair().publicKey;
+  const stakePoolNonce = 49;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const mintToBeStakedAccountKey = getAssociatedTokenAddressSync(
+    mintToBeStaked,
+    depositor.publicKey
+  );
+  const stakeMintAccountKey = getAssociatedTokenAddressSync(
+    stakeMint,
+    depositor.publicKey
+  );
+  const getStakeReceiptKey = (receiptNonce: number) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        depositor.publicKey.toBuffer(),
+        stakePoolKey.toBuffer(),
+        new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+        Buffer.from("stakeDepositReceipt", "utf-8"),
+      ],
+      program.programId
+    )[0];
+  const [voterWeightRecordKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      stakePoolKey.toBuffer(),
+      realm.toBuffer(),
+      depositor.publicKey.toBuffer(),
+      Buffer.from("voterWeightRecord", "utf-8"),
+    ],
+    program.programId
+  );
+  const [maxVoterWeightRecordKey] =
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        stakePoolKey.toBuffer(),
+        realm.toBuffer(),
+        Buffer.from("maxVoterWeightRecord", "utf-8"),
+      ],
+      program.programId
+    );
+
+  // VoterWeightRecord: discriminator, realm, mint, owner, voter_weight
+  const getVoterWeight = async () => {
+    const accountInfo = await program.provider.connection.getAccountInfo(
+      voterWeightRecordKey
+    );
+    return new anchor.BN(accountInfo.data.subarray(104, 112), "le");
+  };
+  // MaxVoterWeightRecord: discriminator, realm, mint, max_voter_weight
+  const getMaxVoterWeight = async () => {
+    const accountInfo = await program.provider.connection.getAccountInfo(
+      maxVoterWeightRecordKey
+    );
+    return new anchor.BN(accountInfo.data.subarray(72, 80), "le");
+  };
+
+  before(async () => {
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor, stakePoolNonce),
+      initStakePool(program, mintToBeStaked, stakePoolNonce),
+    ]);
+    for (const receiptNonce of [0, 1]) {
+      await deposit(
+        program,
+        stakePoolNonce,
+        mintToBeStaked,
+        depositor,
+        mintToBeStakedAccountKey,
+        stakeMintAccountKey,
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(0),
+        receiptNonce
+      );
+    }
+  });
+
+  it("Create the VoterWeightRecord and MaxVoterWeightRecord", async () => {
+    await program.methods
+      .createVoterWeightRecord()
+      .accounts({
+        payer: program.provider.publicKey,
+        governingTokenOwner: depositor.publicKey,
+        stakePool: stakePoolKey,
+        realm,
+        voterWeightRecord: voterWeightRecordKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods
+      .createMaxVoterWeightRecord()
+      .accounts({
+        payer: program.provider.publicKey,
+        stakePool: stakePoolKey,
+        realm,
+        maxVoterWeightRecord: maxVoterWeightRecordKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    const accountInfo = await program.provider.connection.getAccountInfo(
+      voterWeightRecordKey
+    );
+    assert.equal(
+      accountInfo.data.subarray(0, 8).toString("hex"),
+      "2ef99b4b99f87409"
+    );
+    assert.isTrue(accountInfo.data.subarray(8, 40).equals(realm.toBuffer()));
+    assert.isTrue(
+      accountInfo.data.subarray(40, 72).equals(stakeMint.toBuffer())
+    );
+    assertBNEqual(await getVoterWeight(), 0);
+  });
+
+  it("Update weights from the owner's StakeDepositReceipts", async () => {
+    await program.methods
+      .updateVoterWeightRecord()
+      .accounts({
+        governingTokenOwner: depositor.publicKey,
+        stakePool: stakePoolKey,
+        voterWeightRecord: voterWeightRecordKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(
+        [0, 1].map((nonce) => ({
+          pubkey: getStakeReceiptKey(nonce),
+          isWritable: true,
+          isSigner: false,
+        }))
+      )
+      .signers([depositor])
+      .rpc();
+    await program.methods
+      .updateMaxVoterWeightRecord()
+      .accounts({
+        stakePool: stakePoolKey,
+        maxVoterWeightRecord: maxVoterWeightRecordKey,
+      })
+      .rpc();
+    const stakeMintAccount = await getAccount(
+      program.provider.connection,
+      stakeMintAccountKey
+    );
+    const voterWeight = await getVoterWeight();
+    assertBNEqual(
+      voterWeight,
+      new anchor.BN(stakeMintAccount.amount.toString())
+    );
+    assertBNEqual(await getMaxVoterWeight(), voterWeight);
+  });
+
+  it("Fail to count a StakeDepositReceipt twice", async () => {
+    try {
+      await program.methods
+        .updateVoterWeightRecord()
+        .accounts({
+          governingTokenOwner: depositor.publicKey,
+          stakePool: stakePoolKey,
+          voterWeightRecord: voterWeightRecordKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(
+          [0, 0].map((nonce) => ({
+            pubkey: getStakeReceiptKey(nonce),
+            isWritable: true,
+            isSigner: false,
+          }))
+        )
+        .signers([depositor])
+        .rpc();
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "DuplicateStakeDepositReceipt");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+
+  it("Fail to transfer a StakeDepositReceipt after voting", async () => {
+    const newNonce = 5;
+    try {
+      await program.methods
+        .transferReceipt(newNonce)
+        .accounts({
+          claimBase: {
+            owner: depositor.publicKey,
+            stakePool: stakePoolKey,
+            stakeDepositReceipt: getStakeReceiptKey(0),
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          payer: depositor.publicKey,
+          newOwner: depositor.publicKey,
+          newStakeDepositReceipt: getStakeReceiptKey(newNonce),
+          stakeMint,
+          from: stakeMintAccountKey,
+          destination: stakeMintAccountKey,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([depositor])
+        .rpc();
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "StakeDepositReceiptVoting");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+});
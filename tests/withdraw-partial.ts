@@ -0,0 +1,221 @@
+import * as anchor from "@coral-xyz/anchor";
+import { splTokenProgram } from "@coral-xyz/spl-token";
+import { SplTokenStaking } from "../target/types/spl_token_staking";
+import {
+  createDepositorSplAccounts,
+  mintToBeStaked,
+  rewardMint1,
+} from "./hooks";
+import {
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createTransferInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { addRewardPool, initStakePool } from "@mithraic-labs/token-staking";
+import { deposit } from "./utils";
+import { assertBNEqual } from "./genericTests";
+
+describe("withdraw-partial", () => {
+  const program = anchor.workspace
+    .SplTokenStaking as anchor.Program<SplTokenStaking>;
+  const tokenProgramInstance = splTokenProgram({ programId: TOKEN_PROGRAM_ID });
+  const depositor1 = new anchor.web3.Keypair();
+  const stakePoolNonce = 30;
+  const [stakePoolKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      new anchor.BN(stakePoolNonce).toArrayLike(Buffer, "le", 1),
+      mintToBeStaked.toBuffer(),
+      program.provider.publicKey.toBuffer(),
+      Buffer.from("stakePool", "utf-8"),
+    ],
+    program.programId
+  );
+  const [vaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("vault", "utf-8")],
+    program.programId
+  );
+  const [stakeMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [stakePoolKey.toBuffer(), Buffer.from("stakeMint", "utf-8")],
+    program.programId
+  );
+  const [rewardVaultKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      stakePoolKey.toBuffer(),
+      rewardMint1.toBuffer(),
+      Buffer.from("rewardVault", "utf-8"),
+    ],
+    program.programId
+  );
+  const mintToBeStakedAccountKey = getAssociatedTokenAddressSync(
+    mintToBeStaked,
+    depositor1.publicKey
+  );
+  const stakeMintAccountKey = getAssociatedTokenAddressSync(
+    stakeMint,
+    depositor1.publicKey
+  );
+  const depositorReward1AccountKey = getAssociatedTokenAddressSync(
+    rewardMint1,
+    depositor1.publicKey
+  );
+  const receiptNonce = 0;
+  const [stakeReceiptKey] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      depositor1.publicKey.toBuffer(),
+      stakePoolKey.toBuffer(),
+      new anchor.BN(receiptNonce).toArrayLike(Buffer, "le", 4),
+      Buffer.from("stakeDepositReceipt", "utf-8"),
+    ],
+    program.programId
+  );
+
+  const withdrawPartial = (amount: anchor.BN) =>
+    program.methods
+      .withdrawPartial(amount)
+      .accounts({
+        claimBase: {
+          owner: depositor1.publicKey,
+          stakePool: stakePoolKey,
+          stakeDepositReceipt: stakeReceiptKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+        vault: vaultKey,
+        mint: mintToBeStaked,
+        stakeMint,
+        from: stakeMintAccountKey,
+        destination: mintToBeStakedAccountKey,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        {
+          pubkey: rewardVaultKey,
+          isWritable: true,
+          isSigner: false,
+        },
+        {
+          pubkey: depositorReward1AccountKey,
+          isWritable: true,
+          isSigner: false,
+        },
+      ])
+      .signers([depositor1])
+      .rpc();
+
+  before(async () => {
+    // set up depositor account and stake pool account
+    await Promise.all([
+      createDepositorSplAccounts(program, depositor1, stakePoolNonce),
+      initStakePool(program, mintToBeStaked, stakePoolNonce),
+    ]);
+    await addRewardPool(program, stakePoolNonce, mintToBeStaked, rewardMint1);
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createAssociatedTokenAccountInstruction(
+          program.provider.publicKey,
+          depositorReward1AccountKey,
+          depositor1.publicKey,
+          rewardMint1,
+          TOKEN_PROGRAM_ID
+        )
+      )
+    );
+    // deposit 1 token
+    await deposit(
+      program,
+      stakePoolNonce,
+      mintToBeStaked,
+      depositor1,
+      mintToBeStakedAccountKey,
+      stakeMintAccountKey,
+      new anchor.BN(1_000_000_000),
+      new anchor.BN(0),
+      receiptNonce,
+      [rewardVaultKey]
+    );
+  });
+
+  it("partially withdraw unlocked tokens and claim rewards", async () => {
+    const totalReward1 = 1_000_000_000;
+    await program.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createTransferInstruction(
+          getAssociatedTokenAddressSync(
+            rewardMint1,
+            program.provider.publicKey
+          ),
+          rewardVaultKey,
+          program.provider.publicKey,
+          totalReward1
+        )
+      )
+    );
+    const [
+      stakePoolBefore,
+      receiptBefore,
+      depositerMintAccountBefore,
+      sTokenAccountBefore,
+    ] = await Promise.all([
+      program.account.stakePool.fetch(stakePoolKey),
+      program.account.stakeDepositReceipt.fetch(stakeReceiptKey),
+      tokenProgramInstance.account.account.fetch(mintToBeStakedAccountKey),
+      tokenProgramInstance.account.account.fetch(stakeMintAccountKey),
+    ]);
+
+    await withdrawPartial(new anchor.BN(250_000_000));
+
+    const [
+      stakePoolAfter,
+      receiptAfter,
+      depositerMintAccountAfter,
+      sTokenAccountAfter,
+      vaultAfter,
+      depositorReward1AccountAfter,
+    ] = await Promise.all([
+      program.account.stakePool.fetch(stakePoolKey),
+      program.account.stakeDepositReceipt.fetch(stakeReceiptKey),
+      tokenProgramInstance.account.account.fetch(mintToBeStakedAccountKey),
+      tokenProgramInstance.account.account.fetch(stakeMintAccountKey),
+      tokenProgramInstance.account.account.fetch(vaultKey),
+      tokenProgramInstance.account.account.fetch(depositorReward1AccountKey),
+    ]);
+    const removedStake = receiptBefore.effectiveStake.div(new anchor.BN(4));
+    assertBNEqual(receiptAfter.depositAmount, 750_000_000);
+    assertBNEqual(
+      receiptAfter.effectiveStake,
+      receiptBefore.effectiveStake.sub(removedStake)
+    );
+    assertBNEqual(
+      stakePoolAfter.totalWeightedStake,
+      stakePoolBefore.totalWeightedStake.sub(removedStake)
+    );
+    assertBNEqual(
+      depositerMintAccountAfter.amount,
+      depositerMintAccountBefore.amount.add(new anchor.BN(250_000_000))
+    );
+    assertBNEqual(
+      sTokenAccountAfter.amount,
+      sTokenAccountBefore.amount.sub(new anchor.BN(250_000_000))
+    );
+    assertBNEqual(vaultAfter.amount, 750_000_000);
+    assertBNEqual(depositorReward1AccountAfter.amount, totalReward1);
+    assertBNEqual(
+      receiptAfter.claimedAmounts[0],
+      stakePoolAfter.rewardPools[0].rewardsPerEffectiveStake
+    );
+  });
+
+  it("Fail to partially withdraw the full deposit", async () => {
+    const receipt = await program.account.stakeDepositReceipt.fetch(
+      stakeReceiptKey
+    );
+    try {
+      await withdrawPartial(receipt.depositAmount);
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "InvalidWithdrawAmount");
+      return;
+    }
+    assert.isTrue(false, "TX should have failed");
+  });
+});
@@ -119,9 +119,11 @@ describe("withdraw", () => {
           tokenProgram: TOKEN_PROGRAM_ID,
         },
         vault: vaultKey,
+        mint: mintToBeStaked,
         stakeMint,
         from: stakeMintAccountKey,
         destination: mintToBeStakedAccountKey,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
       })
       .remainingAccounts([
         {
@@ -233,9 +235,11 @@ describe("withdraw", () => {
           tokenProgram: TOKEN_PROGRAM_ID,
         },
         vault: vaultKey,
+        mint: mintToBeStaked,
         stakeMint,
         from: stakeMintAccountKey,
         destination: mintToBeStakedAccountKey,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
       })
       .remainingAccounts([
         {
@@ -317,9 +321,11 @@ describe("withdraw", () => {
             tokenProgram: TOKEN_PROGRAM_ID,
           },
           vault: vaultKey,
+          mint: mintToBeStaked,
           stakeMint,
           from: stakeMintAccountKey,
           destination: mintToBeStakedAccountKey,
+          mintTokenProgram: TOKEN_PROGRAM_ID,
         })
         .remainingAccounts([
           {
@@ -410,9 +416,11 @@ describe("withdraw", () => {
           tokenProgram: TOKEN_PROGRAM_ID,
         },
         vault: vaultKey,
+        mint: mintToBeStaked,
         stakeMint,
         from: stakeMintAccountKey,
         destination: mintToBeStakedAccountKey,
+        mintTokenProgram: TOKEN_PROGRAM_ID,
       })
       .remainingAccounts([
         {